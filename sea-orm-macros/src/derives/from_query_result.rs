@@ -1,6 +1,9 @@
 use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote, quote_spanned};
-use syn::{ext::IdentExt, Data, DataStruct, Field, Fields};
+use syn::{
+    ext::IdentExt, punctuated::Punctuated, token::Comma, Data, DataStruct, Field, Fields,
+    GenericArgument, Lit, Meta, PathArguments, Type,
+};
 
 /// Method to derive a [QueryResult](sea_orm::QueryResult)
 pub fn expand_derive_from_query_result(ident: Ident, data: Data) -> syn::Result<TokenStream> {
@@ -16,27 +19,100 @@ pub fn expand_derive_from_query_result(ident: Ident, data: Data) -> syn::Result<
         }
     };
 
-    let field: Vec<Ident> = fields
-        .into_iter()
-        .map(|Field { ident, .. }| format_ident!("{}", ident.unwrap().to_string()))
-        .collect();
+    let mut field = Vec::with_capacity(fields.len());
+    let mut value = Vec::with_capacity(fields.len());
+    for Field {
+        ident: field_ident,
+        attrs,
+        ty,
+        ..
+    } in fields.into_iter()
+    {
+        let field_ident = format_ident!("{}", field_ident.unwrap().to_string());
+        let name = field_ident.unraw().to_string();
 
-    let name: Vec<TokenStream> = field
-        .iter()
-        .map(|f| {
-            let s = f.unraw().to_string();
-            quote! { #s }
-        })
-        .collect();
+        let mut nested = false;
+        let mut prefix = None;
+        for attr in attrs.iter() {
+            if !attr.path.is_ident("sea_orm") {
+                continue;
+            }
+            let list = attr.parse_args_with(Punctuated::<Meta, Comma>::parse_terminated)?;
+            for meta in list.iter() {
+                match meta {
+                    Meta::Path(path) if path.is_ident("nested") => nested = true,
+                    Meta::NameValue(nv) if nv.path.is_ident("prefix") => match &nv.lit {
+                        Lit::Str(litstr) => prefix = Some((litstr.value(), meta.clone())),
+                        lit => return Err(syn::Error::new_spanned(lit, "expected a string")),
+                    },
+                    // The column attributes of `DerivePartialModel`, derived along with it
+                    Meta::NameValue(nv)
+                        if nv.path.is_ident("from_col") || nv.path.is_ident("from_expr") => {}
+                    meta => {
+                        return Err(syn::Error::new_spanned(
+                            meta,
+                            "unknown attribute, expected `nested` or `prefix`",
+                        ))
+                    }
+                }
+            }
+        }
+        let prefix = match prefix {
+            Some((_, meta)) if !nested => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "`prefix` can only be used along with `nested`",
+                ))
+            }
+            prefix => prefix.map(|(prefix, _)| prefix),
+        };
+
+        value.push(if nested {
+            // The columns of a nested model are read with the prefix appended to the current one
+            let pre = match prefix {
+                Some(prefix) => quote! { &format!("{}{}", pre, #prefix) },
+                None => quote! { pre },
+            };
+            match option_inner_type(&ty) {
+                Some(ty) => quote! {
+                    <#ty as sea_orm::FromQueryResult>::from_query_result_optional(row, #pre)?
+                },
+                None => quote! {
+                    <#ty as sea_orm::FromQueryResult>::from_query_result(row, #pre)?
+                },
+            }
+        } else {
+            quote! { row.try_get(pre, #name)? }
+        });
+        field.push(field_ident);
+    }
 
     Ok(quote!(
         #[automatically_derived]
         impl sea_orm::FromQueryResult for #ident {
             fn from_query_result(row: &sea_orm::QueryResult, pre: &str) -> std::result::Result<Self, sea_orm::DbErr> {
                 Ok(Self {
-                    #(#field: row.try_get(pre, #name)?),*
+                    #(#field: #value),*
                 })
             }
         }
     ))
 }
+
+/// The `T` of a field of type `Option<T>`
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(type_path) => type_path.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first()? {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
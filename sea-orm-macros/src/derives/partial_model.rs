@@ -0,0 +1,171 @@
+use heck::ToUpperCamelCase;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{ext::IdentExt, punctuated::Punctuated, token::Comma, Lit, Meta};
+
+use crate::util::{escape_rust_keyword, trim_starting_raw_identifier};
+
+enum Error {
+    InputNotStruct,
+    EntityNotSpecified,
+    BothFromColAndFromExpr(Span),
+    Syn(syn::Error),
+}
+
+enum ColumnAs {
+    /// The column with the same name as the field
+    Col(syn::Ident),
+    /// The column specified by `from_col`, aliased to the field name
+    ColAlias { col: syn::Ident, field: String },
+    /// The expression specified by `from_expr`, aliased to the field name
+    Expr { expr: Box<syn::Expr>, field: String },
+}
+
+struct DerivePartialModel {
+    entity: Option<syn::Type>,
+    ident: syn::Ident,
+    fields: Vec<ColumnAs>,
+}
+
+impl DerivePartialModel {
+    fn new(input: syn::DeriveInput) -> Result<Self, Error> {
+        let fields = match input.data {
+            syn::Data::Struct(syn::DataStruct {
+                fields: syn::Fields::Named(syn::FieldsNamed { named, .. }),
+                ..
+            }) => named,
+            _ => return Err(Error::InputNotStruct),
+        };
+
+        let mut entity = None;
+        for attr in input.attrs.iter() {
+            if !attr.path.is_ident("sea_orm") {
+                continue;
+            }
+            let list = attr
+                .parse_args_with(Punctuated::<Meta, Comma>::parse_terminated)
+                .map_err(Error::Syn)?;
+            for meta in list.iter() {
+                if let Meta::NameValue(nv) = meta {
+                    if nv.path.is_ident("entity") {
+                        if let Lit::Str(litstr) = &nv.lit {
+                            entity = Some(litstr.parse().map_err(Error::Syn)?);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut column_as_list = Vec::with_capacity(fields.len());
+        for field in fields.iter() {
+            let field_ident = field.ident.as_ref().unwrap();
+            let field_name = field_ident.unraw().to_string();
+            let mut from_col = None;
+            let mut from_expr = None;
+            for attr in field.attrs.iter() {
+                if !attr.path.is_ident("sea_orm") {
+                    continue;
+                }
+                let list = attr
+                    .parse_args_with(Punctuated::<Meta, Comma>::parse_terminated)
+                    .map_err(Error::Syn)?;
+                for meta in list.iter() {
+                    if let Meta::NameValue(nv) = meta {
+                        if let Lit::Str(litstr) = &nv.lit {
+                            if nv.path.is_ident("from_col") {
+                                from_col = Some(column_ident(&litstr.value()));
+                            } else if nv.path.is_ident("from_expr") {
+                                from_expr = Some(Box::new(litstr.parse().map_err(Error::Syn)?));
+                            }
+                        }
+                    }
+                }
+            }
+
+            let column_as = match (from_col, from_expr) {
+                (Some(_), Some(_)) => {
+                    return Err(Error::BothFromColAndFromExpr(field_ident.span()))
+                }
+                (Some(col), None) => ColumnAs::ColAlias {
+                    col,
+                    field: field_name,
+                },
+                (None, Some(expr)) => ColumnAs::Expr {
+                    expr,
+                    field: field_name,
+                },
+                (None, None) => ColumnAs::Col(column_ident(&field_name)),
+            };
+            column_as_list.push(column_as);
+        }
+
+        if entity.is_none()
+            && column_as_list
+                .iter()
+                .any(|column_as| !matches!(column_as, ColumnAs::Expr { .. }))
+        {
+            return Err(Error::EntityNotSpecified);
+        }
+
+        Ok(Self {
+            entity,
+            ident: input.ident,
+            fields: column_as_list,
+        })
+    }
+
+    fn expand(&self) -> syn::Result<TokenStream> {
+        Ok(self.impl_partial_model_trait())
+    }
+
+    fn impl_partial_model_trait(&self) -> TokenStream {
+        let ident = &self.ident;
+        let entity = &self.entity;
+
+        let select_col_code_gen = self.fields.iter().map(|column_as| match column_as {
+            ColumnAs::Col(col) => quote!(
+                sea_orm::QuerySelect::column(select, <#entity as sea_orm::EntityTrait>::Column::#col)
+            ),
+            ColumnAs::ColAlias { col, field } => quote!(
+                sea_orm::QuerySelect::column_as(select, <#entity as sea_orm::EntityTrait>::Column::#col, #field)
+            ),
+            ColumnAs::Expr { expr, field } => quote!(
+                sea_orm::QuerySelect::column_as(select, #expr, #field)
+            ),
+        });
+
+        quote!(
+            #[automatically_derived]
+            impl sea_orm::PartialModelTrait for #ident {
+                fn select_cols<S: sea_orm::QuerySelect>(select: S) -> S {
+                    #(let select = #select_col_code_gen;)*
+                    select
+                }
+            }
+        )
+    }
+}
+
+fn column_ident(name: &str) -> syn::Ident {
+    let ident = trim_starting_raw_identifier(name).to_upper_camel_case();
+    format_ident!("{}", escape_rust_keyword(ident))
+}
+
+/// Method to derive a [PartialModelTrait](sea_orm::PartialModelTrait)
+pub fn expand_derive_partial_model(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let ident_span = input.ident.span();
+
+    match DerivePartialModel::new(input) {
+        Ok(partial_model) => partial_model.expand(),
+        Err(Error::InputNotStruct) => Ok(quote_spanned! {
+            ident_span => compile_error!("you can only derive DerivePartialModel on structs");
+        }),
+        Err(Error::EntityNotSpecified) => Ok(quote_spanned! {
+            ident_span => compile_error!("you need to specify `#[sea_orm(entity = \"...\")]` to select columns of an entity");
+        }),
+        Err(Error::BothFromColAndFromExpr(span)) => Ok(quote_spanned! {
+            span => compile_error!("you can only specify one of `from_col` and `from_expr`");
+        }),
+        Err(Error::Syn(err)) => Err(err),
+    }
+}
@@ -574,7 +574,48 @@ pub fn derive_active_enum(input: TokenStream) -> TokenStream {
 ///     num_of_fruits: i32,
 /// }
 /// ```
-#[proc_macro_derive(FromQueryResult)]
+///
+/// ## Nested fields
+///
+/// A field annotated with `#[sea_orm(nested)]` is read by the `FromQueryResult` of its type,
+/// with the columns prefixed by `prefix` if given. An `Option` field is `None` if the nested
+/// type cannot be read, e.g. the columns of an Entity that was left joined are all `NULL`.
+/// The prefixes of the Entities selected by `select_also` are `A_`, `B_`, `C_` and `D_`.
+///
+/// ```
+/// use sea_orm::{entity::prelude::*, FromQueryResult};
+///
+/// #[derive(Debug, FromQueryResult)]
+/// struct CakeWithFruit {
+///     #[sea_orm(nested, prefix = "A_")]
+///     cake: sea_orm::tests_cfg::cake::Model,
+///     #[sea_orm(nested, prefix = "B_")]
+///     fruit: Option<sea_orm::tests_cfg::fruit::Model>,
+/// }
+/// ```
+///
+/// A `prefix` without `nested`, or any other attribute, results in a compile error.
+///
+/// ```compile_fail
+/// use sea_orm::{entity::prelude::*, FromQueryResult};
+///
+/// #[derive(Debug, FromQueryResult)]
+/// struct CakeWithFruit {
+///     #[sea_orm(prefix = "A_")]
+///     cake: sea_orm::tests_cfg::cake::Model,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use sea_orm::{entity::prelude::*, FromQueryResult};
+///
+/// #[derive(Debug, FromQueryResult)]
+/// struct CakeWithFruit {
+///     #[sea_orm(nest, prefix = "A_")]
+///     cake: sea_orm::tests_cfg::cake::Model,
+/// }
+/// ```
+#[proc_macro_derive(FromQueryResult, attributes(sea_orm))]
 pub fn derive_from_query_result(input: TokenStream) -> TokenStream {
     let DeriveInput { ident, data, .. } = parse_macro_input!(input);
 
@@ -584,6 +625,37 @@ pub fn derive_from_query_result(input: TokenStream) -> TokenStream {
     }
 }
 
+/// The DerivePartialModel derive macro will implement `sea_orm::PartialModelTrait` for a struct,
+/// selecting the columns of the entity named after its fields.
+/// It has to be derived together with `FromQueryResult`.
+///
+/// ## Column attributes
+/// - `from_col`: select the column with the given name, aliased to the field name
+/// - `from_expr`: select the given expression, aliased to the field name
+///
+/// ### Usage
+///
+/// ```
+/// use sea_orm::{entity::prelude::*, sea_query::Expr, DerivePartialModel, FromQueryResult};
+///
+/// #[derive(Debug, FromQueryResult, DerivePartialModel)]
+/// #[sea_orm(entity = "sea_orm::tests_cfg::cake::Entity")]
+/// struct PartialCake {
+///     name: String,
+///     #[sea_orm(from_col = "id")]
+///     cake_id: i32,
+///     #[sea_orm(from_expr = r#"Expr::col(sea_orm::tests_cfg::cake::Column::Name).like("%cheese%")"#)]
+///     is_cheese: bool,
+/// }
+/// ```
+#[proc_macro_derive(DerivePartialModel, attributes(sea_orm))]
+pub fn derive_partial_model(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derives::expand_derive_partial_model(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
 /// The DeriveRelation derive macro will implement RelationTrait for Relation.
 ///
 /// ### Usage
@@ -1,8 +1,9 @@
 use crate::{
-    DatabaseTransaction, DbBackend, DbErr, ExecResult, QueryResult, Statement, TransactionError,
+    DatabaseTransaction, DbBackend, DbErr, ExecResult, QueryResult, Statement, StatementOptions,
+    TransactionError,
 };
 use futures::Stream;
-use std::{future::Future, pin::Pin};
+use std::{future::Future, pin::Pin, time::Duration};
 
 /// The generic API for a database connection that can perform query or execute statements.
 /// It abstracts database connection and transaction
@@ -24,6 +25,36 @@ pub trait ConnectionTrait: Sync {
     /// Execute a [Statement] and return a collection Vec<[QueryResult]> on success
     async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr>;
 
+    /// Execute a [Statement] with [StatementOptions].
+    /// Connections that do not support the options execute the statement without them.
+    async fn execute_with_options(
+        &self,
+        stmt: Statement,
+        _options: StatementOptions,
+    ) -> Result<ExecResult, DbErr> {
+        self.execute(stmt).await
+    }
+
+    /// Execute a [Statement] with [StatementOptions] and return a query.
+    /// Connections that do not support the options execute the statement without them.
+    async fn query_one_with_options(
+        &self,
+        stmt: Statement,
+        _options: StatementOptions,
+    ) -> Result<Option<QueryResult>, DbErr> {
+        self.query_one(stmt).await
+    }
+
+    /// Execute a [Statement] with [StatementOptions] and return a collection Vec<[QueryResult]> on success.
+    /// Connections that do not support the options execute the statement without them.
+    async fn query_all_with_options(
+        &self,
+        stmt: Statement,
+        _options: StatementOptions,
+    ) -> Result<Vec<QueryResult>, DbErr> {
+        self.query_all(stmt).await
+    }
+
     /// Check if the connection supports `RETURNING` syntax on insert and update
     fn support_returning(&self) -> bool {
         let db_backend = self.get_database_backend();
@@ -92,6 +123,57 @@ impl std::fmt::Display for AccessMode {
     }
 }
 
+/// Defines how many times and how often [TransactionTrait::transaction_with_retry] re-runs a transaction
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_secs(1),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Create a new [RetryPolicy] with the default backoff, running the transaction at most `max_attempts` times
+    pub fn new(max_attempts: u32) -> Self {
+        Self::default().max_attempts(max_attempts)
+    }
+
+    /// Set the maximum number of times the transaction is run, including the first attempt
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Get the maximum number of times the transaction is run
+    pub fn get_max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Set the delay before the first retry. The delay doubles on every subsequent retry, up to `max_backoff`
+    pub fn backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Get the delay before retrying, after the given number of failed attempts
+    pub fn get_backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
 /// Spawn database transaction
 #[async_trait::async_trait]
 pub trait TransactionTrait {
@@ -133,4 +215,70 @@ pub trait TransactionTrait {
             + Send,
         T: Send,
         E: std::error::Error + Send;
+
+    /// Execute the function inside a transaction with isolation level and/or access mode,
+    /// running it again according to the [RetryPolicy] when the transaction fails due to
+    /// a serialization failure or a deadlock (see [TransactionError::is_retryable]).
+    /// As the function may be called more than once, it has to be `Fn`.
+    /// Any other error, or the error of the last attempt, is returned.
+    async fn transaction_with_retry<F, T, E>(
+        &self,
+        callback: F,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
+        retry_policy: RetryPolicy,
+    ) -> Result<T, TransactionError<E>>
+    where
+        F: for<'c> Fn(
+                &'c DatabaseTransaction,
+            ) -> Pin<Box<dyn Future<Output = Result<T, E>> + Send + 'c>>
+            + Send
+            + Sync,
+        T: Send,
+        E: std::error::Error + Send + 'static,
+        Self: Sync,
+    {
+        let mut attempt = 1;
+        loop {
+            let res = match self.begin_with_config(isolation_level, access_mode).await {
+                Ok(transaction) => transaction.run(&callback).await,
+                Err(err) => Err(TransactionError::Connection(err)),
+            };
+            match res {
+                Err(err) if attempt < retry_policy.max_attempts && err.is_retryable() => {
+                    sleep(retry_policy.get_backoff(attempt)).await;
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+}
+
+/// Wait for the given duration on the async runtime selected by the `runtime-*` features
+async fn sleep(duration: Duration) {
+    if duration.is_zero() {
+        return;
+    }
+    #[cfg(any(feature = "runtime-tokio", feature = "runtime-actix"))]
+    tokio::time::sleep(duration).await;
+    #[cfg(all(
+        feature = "runtime-async-std",
+        not(any(feature = "runtime-tokio", feature = "runtime-actix"))
+    ))]
+    async_std::task::sleep(duration).await;
+    // Without a runtime, e.g. with only the `mock` feature, wait on a separate thread
+    #[cfg(not(any(
+        feature = "runtime-tokio",
+        feature = "runtime-actix",
+        feature = "runtime-async-std"
+    )))]
+    {
+        let (tx, rx) = futures::channel::oneshot::channel();
+        std::thread::spawn(move || {
+            std::thread::sleep(duration);
+            let _ = tx.send(());
+        });
+        let _ = rx.await;
+    }
 }
@@ -1,6 +1,7 @@
 use crate::{
     error::*, AccessMode, ConnectionTrait, DatabaseTransaction, ExecResult, IsolationLevel,
-    QueryResult, Statement, StatementBuilder, StreamTrait, TransactionError, TransactionTrait,
+    QueryResult, Statement, StatementBuilder, StatementOptions, StreamTrait, TransactionError,
+    TransactionTrait,
 };
 use sea_query::{MysqlQueryBuilder, PostgresQueryBuilder, QueryBuilder, SqliteQueryBuilder};
 use std::{future::Future, pin::Pin};
@@ -105,20 +106,9 @@ impl ConnectionTrait for DatabaseConnection {
         }
     }
 
-    #[instrument(level = "trace")]
-    #[allow(unused_variables)]
     async fn execute(&self, stmt: Statement) -> Result<ExecResult, DbErr> {
-        match self {
-            #[cfg(feature = "sqlx-mysql")]
-            DatabaseConnection::SqlxMySqlPoolConnection(conn) => conn.execute(stmt).await,
-            #[cfg(feature = "sqlx-postgres")]
-            DatabaseConnection::SqlxPostgresPoolConnection(conn) => conn.execute(stmt).await,
-            #[cfg(feature = "sqlx-sqlite")]
-            DatabaseConnection::SqlxSqlitePoolConnection(conn) => conn.execute(stmt).await,
-            #[cfg(feature = "mock")]
-            DatabaseConnection::MockDatabaseConnection(conn) => conn.execute(stmt),
-            DatabaseConnection::Disconnected => Err(conn_err("Disconnected")),
-        }
+        self.execute_with_options(stmt, StatementOptions::default())
+            .await
     }
 
     #[instrument(level = "trace")]
@@ -145,16 +135,62 @@ impl ConnectionTrait for DatabaseConnection {
         }
     }
 
+    async fn query_one(&self, stmt: Statement) -> Result<Option<QueryResult>, DbErr> {
+        self.query_one_with_options(stmt, StatementOptions::default())
+            .await
+    }
+
+    async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr> {
+        self.query_all_with_options(stmt, StatementOptions::default())
+            .await
+    }
+
     #[instrument(level = "trace")]
     #[allow(unused_variables)]
-    async fn query_one(&self, stmt: Statement) -> Result<Option<QueryResult>, DbErr> {
+    async fn execute_with_options(
+        &self,
+        stmt: Statement,
+        options: StatementOptions,
+    ) -> Result<ExecResult, DbErr> {
+        match self {
+            #[cfg(feature = "sqlx-mysql")]
+            DatabaseConnection::SqlxMySqlPoolConnection(conn) => {
+                conn.execute_with_options(stmt, options).await
+            }
+            #[cfg(feature = "sqlx-postgres")]
+            DatabaseConnection::SqlxPostgresPoolConnection(conn) => {
+                conn.execute_with_options(stmt, options).await
+            }
+            #[cfg(feature = "sqlx-sqlite")]
+            DatabaseConnection::SqlxSqlitePoolConnection(conn) => {
+                conn.execute_with_options(stmt, options).await
+            }
+            #[cfg(feature = "mock")]
+            DatabaseConnection::MockDatabaseConnection(conn) => conn.execute(stmt),
+            DatabaseConnection::Disconnected => Err(conn_err("Disconnected")),
+        }
+    }
+
+    #[instrument(level = "trace")]
+    #[allow(unused_variables)]
+    async fn query_one_with_options(
+        &self,
+        stmt: Statement,
+        options: StatementOptions,
+    ) -> Result<Option<QueryResult>, DbErr> {
         match self {
             #[cfg(feature = "sqlx-mysql")]
-            DatabaseConnection::SqlxMySqlPoolConnection(conn) => conn.query_one(stmt).await,
+            DatabaseConnection::SqlxMySqlPoolConnection(conn) => {
+                conn.query_one_with_options(stmt, options).await
+            }
             #[cfg(feature = "sqlx-postgres")]
-            DatabaseConnection::SqlxPostgresPoolConnection(conn) => conn.query_one(stmt).await,
+            DatabaseConnection::SqlxPostgresPoolConnection(conn) => {
+                conn.query_one_with_options(stmt, options).await
+            }
             #[cfg(feature = "sqlx-sqlite")]
-            DatabaseConnection::SqlxSqlitePoolConnection(conn) => conn.query_one(stmt).await,
+            DatabaseConnection::SqlxSqlitePoolConnection(conn) => {
+                conn.query_one_with_options(stmt, options).await
+            }
             #[cfg(feature = "mock")]
             DatabaseConnection::MockDatabaseConnection(conn) => conn.query_one(stmt),
             DatabaseConnection::Disconnected => Err(conn_err("Disconnected")),
@@ -163,14 +199,24 @@ impl ConnectionTrait for DatabaseConnection {
 
     #[instrument(level = "trace")]
     #[allow(unused_variables)]
-    async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr> {
+    async fn query_all_with_options(
+        &self,
+        stmt: Statement,
+        options: StatementOptions,
+    ) -> Result<Vec<QueryResult>, DbErr> {
         match self {
             #[cfg(feature = "sqlx-mysql")]
-            DatabaseConnection::SqlxMySqlPoolConnection(conn) => conn.query_all(stmt).await,
+            DatabaseConnection::SqlxMySqlPoolConnection(conn) => {
+                conn.query_all_with_options(stmt, options).await
+            }
             #[cfg(feature = "sqlx-postgres")]
-            DatabaseConnection::SqlxPostgresPoolConnection(conn) => conn.query_all(stmt).await,
+            DatabaseConnection::SqlxPostgresPoolConnection(conn) => {
+                conn.query_all_with_options(stmt, options).await
+            }
             #[cfg(feature = "sqlx-sqlite")]
-            DatabaseConnection::SqlxSqlitePoolConnection(conn) => conn.query_all(stmt).await,
+            DatabaseConnection::SqlxSqlitePoolConnection(conn) => {
+                conn.query_all_with_options(stmt, options).await
+            }
             #[cfg(feature = "mock")]
             DatabaseConnection::MockDatabaseConnection(conn) => conn.query_all(stmt),
             DatabaseConnection::Disconnected => Err(conn_err("Disconnected")),
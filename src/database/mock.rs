@@ -1,10 +1,10 @@
 use crate::{
-    error::*, DatabaseConnection, DbBackend, EntityTrait, ExecResult, ExecResultHolder, Iden,
-    IdenStatic, Iterable, MockDatabaseConnection, MockDatabaseTrait, ModelTrait, QueryResult,
+    error::*, DatabaseConnection, DbBackend, EntityName, EntityTrait, ExecResult, ExecResultHolder,
+    Iden, IdenStatic, Iterable, MockDatabaseConnection, MockDatabaseTrait, ModelTrait, QueryResult,
     QueryResultRow, SelectA, SelectB, Statement,
 };
 use sea_query::{Value, ValueType, Values};
-use std::{collections::BTreeMap, sync::Arc};
+use std::{collections::BTreeMap, fmt::Debug, sync::Arc};
 use tracing::instrument;
 
 /// Defines a Mock database suitable for testing
@@ -15,6 +15,31 @@ pub struct MockDatabase {
     transaction_log: Vec<Transaction>,
     exec_results: Vec<Result<MockExecResult, DbErr>>,
     query_results: Vec<Result<Vec<MockRow>, DbErr>>,
+    exec_rules: Vec<MockRule<MockExecResult>>,
+    query_rules: Vec<MockRule<Vec<MockRow>>>,
+    /// Number of statements answered by a rule, which do not consume `exec_results`
+    exec_rule_hits: usize,
+    /// Number of queries answered by a rule, which do not consume `query_results`
+    query_rule_hits: usize,
+    unmatched_statements: Vec<Statement>,
+}
+
+/// Defines which statements a rule registered on the [MockDatabase] answers
+#[derive(Clone)]
+pub enum StatementMatcher {
+    /// The SQL is exactly the given string
+    Sql(String),
+    /// The SQL starts with the given string
+    SqlPrefix(String),
+    /// The statement selects from, inserts into, updates or deletes from the given table
+    Table(String),
+    /// The statement satisfies the given predicate
+    Predicate(Arc<dyn Fn(&Statement) -> bool + Send + Sync>),
+}
+
+struct MockRule<T> {
+    matcher: StatementMatcher,
+    result: Box<dyn Fn() -> Result<T, DbErr> + Send + Sync>,
 }
 
 /// Defines the results obtained from a [MockDatabase]
@@ -62,6 +87,11 @@ impl MockDatabase {
             transaction_log: Vec::new(),
             exec_results: Vec::new(),
             query_results: Vec::new(),
+            exec_rules: Vec::new(),
+            query_rules: Vec::new(),
+            exec_rule_hits: 0,
+            query_rule_hits: 0,
+            unmatched_statements: Vec::new(),
         }
     }
 
@@ -110,53 +140,146 @@ impl MockDatabase {
         self.query_results.extend(vec.into_iter().map(Result::Err));
         self
     }
-}
 
-impl MockDatabaseTrait for MockDatabase {
-    #[instrument(level = "trace")]
-    fn execute(&mut self, counter: usize, statement: Statement) -> Result<ExecResult, DbErr> {
+    /// Answer every statement matching `matcher` with the given [MockExecResult].
+    /// Rules are tried in the order they were added, before falling back to `exec_results`.
+    pub fn append_exec_rule(mut self, matcher: StatementMatcher, result: MockExecResult) -> Self {
+        self.exec_rules.push(MockRule {
+            matcher,
+            result: Box::new(move || Ok(result.clone())),
+        });
+        self
+    }
+
+    /// Answer every statement matching `matcher` with the [DbErr] returned by `error`
+    pub fn append_exec_error_rule<F>(mut self, matcher: StatementMatcher, error: F) -> Self
+    where
+        F: Fn() -> DbErr + Send + Sync + 'static,
+    {
+        self.exec_rules.push(MockRule {
+            matcher,
+            result: Box::new(move || Err(error())),
+        });
+        self
+    }
+
+    /// Answer every query matching `matcher` with the given rows.
+    /// Rules are tried in the order they were added, before falling back to `query_results`.
+    pub fn append_query_rule<T, I>(mut self, matcher: StatementMatcher, rows: I) -> Self
+    where
+        T: IntoMockRow,
+        I: IntoIterator<Item = T>,
+    {
+        let rows: Vec<MockRow> = rows.into_iter().map(IntoMockRow::into_mock_row).collect();
+        self.query_rules.push(MockRule {
+            matcher,
+            result: Box::new(move || Ok(rows.clone())),
+        });
+        self
+    }
+
+    /// Answer every query matching `matcher` with the [DbErr] returned by `error`
+    pub fn append_query_error_rule<F>(mut self, matcher: StatementMatcher, error: F) -> Self
+    where
+        F: Fn() -> DbErr + Send + Sync + 'static,
+    {
+        self.query_rules.push(MockRule {
+            matcher,
+            result: Box::new(move || Err(error())),
+        });
+        self
+    }
+
+    fn push_statement(&mut self, statement: Statement) {
         if let Some(transaction) = &mut self.transaction {
             transaction.push(statement);
         } else {
             self.transaction_log.push(Transaction::one(statement));
         }
+    }
+
+    fn unmatched_err(&mut self, statement: &Statement, buffer: &str) -> String {
+        self.unmatched_statements.push(statement.clone());
+        let unmatched: Vec<String> = self
+            .unmatched_statements
+            .iter()
+            .map(|stmt| format!("  {stmt}"))
+            .collect();
+        format!(
+            "No rule matches the statement and `{buffer}` buffer is empty. Unmatched statements:\n{}",
+            unmatched.join("\n")
+        )
+    }
+}
+
+impl MockDatabaseTrait for MockDatabase {
+    #[instrument(level = "trace")]
+    fn execute(&mut self, counter: usize, statement: Statement) -> Result<ExecResult, DbErr> {
+        let to_exec_result = |result| ExecResult {
+            result: ExecResultHolder::Mock(result),
+        };
+        if let Some(rule) = self
+            .exec_rules
+            .iter()
+            .find(|rule| rule.matcher.matches(&statement))
+        {
+            let result = (rule.result)();
+            self.exec_rule_hits += 1;
+            self.push_statement(statement);
+            return result.map(to_exec_result);
+        }
+        let counter = counter - self.exec_rule_hits;
         if counter < self.exec_results.len() {
-            match std::mem::replace(
+            self.push_statement(statement);
+            std::mem::replace(
                 &mut self.exec_results[counter],
                 Err(exec_err("this value has been consumed already")),
-            ) {
-                Ok(result) => Ok(ExecResult {
-                    result: ExecResultHolder::Mock(result),
-                }),
-                Err(err) => Err(err),
-            }
-        } else {
+            )
+            .map(to_exec_result)
+        } else if self.exec_rules.is_empty() {
+            self.push_statement(statement);
             Err(exec_err("`exec_results` buffer is empty"))
+        } else {
+            let err = self.unmatched_err(&statement, "exec_results");
+            self.push_statement(statement);
+            Err(exec_err(err))
         }
     }
 
     #[instrument(level = "trace")]
     fn query(&mut self, counter: usize, statement: Statement) -> Result<Vec<QueryResult>, DbErr> {
-        if let Some(transaction) = &mut self.transaction {
-            transaction.push(statement);
-        } else {
-            self.transaction_log.push(Transaction::one(statement));
+        let to_query_results = |rows: Vec<MockRow>| {
+            rows.into_iter()
+                .map(|row| QueryResult {
+                    row: QueryResultRow::Mock(row),
+                })
+                .collect()
+        };
+        if let Some(rule) = self
+            .query_rules
+            .iter()
+            .find(|rule| rule.matcher.matches(&statement))
+        {
+            let result = (rule.result)();
+            self.query_rule_hits += 1;
+            self.push_statement(statement);
+            return result.map(to_query_results);
         }
+        let counter = counter - self.query_rule_hits;
         if counter < self.query_results.len() {
-            match std::mem::replace(
+            self.push_statement(statement);
+            std::mem::replace(
                 &mut self.query_results[counter],
                 Err(query_err("this value has been consumed already")),
-            ) {
-                Ok(result) => Ok(result
-                    .into_iter()
-                    .map(|row| QueryResult {
-                        row: QueryResultRow::Mock(row),
-                    })
-                    .collect()),
-                Err(err) => Err(err),
-            }
-        } else {
+            )
+            .map(to_query_results)
+        } else if self.query_rules.is_empty() {
+            self.push_statement(statement);
             Err(query_err("`query_results` buffer is empty."))
+        } else {
+            let err = self.unmatched_err(&statement, "query_results");
+            self.push_statement(statement);
+            Err(query_err(err))
         }
     }
 
@@ -196,6 +319,14 @@ impl MockDatabaseTrait for MockDatabase {
         }
     }
 
+    #[instrument(level = "trace")]
+    fn savepoint(&mut self, stmt: Statement) {
+        match self.transaction.as_mut() {
+            Some(transaction) => transaction.push(stmt),
+            None => panic!("There is no open transaction to create a savepoint in"),
+        }
+    }
+
     fn drain_transaction_log(&mut self) -> Vec<Transaction> {
         std::mem::take(&mut self.transaction_log)
     }
@@ -205,6 +336,62 @@ impl MockDatabaseTrait for MockDatabase {
     }
 }
 
+impl StatementMatcher {
+    /// Match the statements on the table of the given entity
+    pub fn entity<E>(entity: E) -> Self
+    where
+        E: EntityName,
+    {
+        Self::Table(entity.table_name().to_owned())
+    }
+
+    /// Match the statements satisfying the given predicate
+    pub fn predicate<F>(predicate: F) -> Self
+    where
+        F: Fn(&Statement) -> bool + Send + Sync + 'static,
+    {
+        Self::Predicate(Arc::new(predicate))
+    }
+
+    /// Check if the [Statement] is matched
+    pub fn matches(&self, stmt: &Statement) -> bool {
+        match self {
+            Self::Sql(sql) => &stmt.sql == sql,
+            Self::SqlPrefix(prefix) => stmt.sql.starts_with(prefix.as_str()),
+            Self::Table(table) => {
+                let quote = match stmt.db_backend {
+                    DbBackend::MySql => '`',
+                    DbBackend::Postgres | DbBackend::Sqlite => '"',
+                };
+                ["FROM", "INTO", "UPDATE"].iter().any(|keyword| {
+                    stmt.sql
+                        .contains(&format!("{keyword} {quote}{table}{quote}"))
+                })
+            }
+            Self::Predicate(predicate) => predicate(stmt),
+        }
+    }
+}
+
+impl Debug for StatementMatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sql(sql) => f.debug_tuple("Sql").field(sql).finish(),
+            Self::SqlPrefix(prefix) => f.debug_tuple("SqlPrefix").field(prefix).finish(),
+            Self::Table(table) => f.debug_tuple("Table").field(table).finish(),
+            Self::Predicate(_) => f.debug_tuple("Predicate").finish(),
+        }
+    }
+}
+
+impl<T> Debug for MockRule<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockRule")
+            .field("matcher", &self.matcher)
+            .finish()
+    }
+}
+
 impl MockRow {
     /// Get a value from the [MockRow]
     pub fn try_get<T, I: crate::ColIdx>(&self, index: I) -> Result<T, DbErr>
@@ -396,7 +583,8 @@ impl OpenTransaction {
 #[cfg(feature = "mock")]
 mod tests {
     use crate::{
-        entity::*, error::*, tests_cfg::*, DbBackend, DbErr, IntoMockRow, MockDatabase, Statement,
+        entity::*, error::*, tests_cfg::*, DbBackend, DbErr, IntoMockRow, IsolationLevel,
+        MockDatabase, MockExecResult, QueryFilter, RetryPolicy, Statement, StatementMatcher,
         Transaction, TransactionError, TransactionTrait,
     };
     use pretty_assertions::assert_eq;
@@ -489,6 +677,163 @@ mod tests {
         );
     }
 
+    #[smol_potat::test]
+    async fn test_transaction_with_retry_1() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let db = MockDatabase::new(DbBackend::Postgres).into_connection();
+        let attempts = AtomicUsize::new(0);
+
+        let result = db
+            .transaction_with_retry::<_, (), MyErr>(
+                |txn| {
+                    attempts.fetch_add(1, Ordering::SeqCst);
+                    Box::pin(async move {
+                        let _ = cake::Entity::find().one(txn).await;
+                        Err(MyErr("test".to_owned()))
+                    })
+                },
+                None,
+                None,
+                RetryPolicy::new(3),
+            )
+            .await;
+
+        // Not a serialization failure, so the transaction is not retried
+        match result {
+            Err(TransactionError::Transaction(err)) => {
+                assert_eq!(err, MyErr("test".to_owned()))
+            }
+            _ => unreachable!(),
+        }
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(feature = "sqlx-dep")]
+    #[smol_potat::test]
+    async fn test_transaction_with_retry_2() {
+        use std::{borrow::Cow, time::Duration};
+
+        #[derive(Debug)]
+        struct SerializationFailure;
+
+        impl std::error::Error for SerializationFailure {}
+
+        impl std::fmt::Display for SerializationFailure {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "could not serialize access due to concurrent update")
+            }
+        }
+
+        impl sqlx::error::DatabaseError for SerializationFailure {
+            fn message(&self) -> &str {
+                "could not serialize access due to concurrent update"
+            }
+
+            fn code(&self) -> Option<Cow<'_, str>> {
+                Some("40001".into())
+            }
+
+            fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+                self
+            }
+
+            fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+                self
+            }
+
+            fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+                self
+            }
+        }
+
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_errors([DbErr::Query(RuntimeErr::SqlxError(sqlx::Error::Database(
+                Box::new(SerializationFailure),
+            )))])
+            .append_query_results([[cake::Model {
+                id: 1,
+                name: "Apple Cake".to_owned(),
+            }]])
+            .into_connection();
+
+        let cake = db
+            .transaction_with_retry::<_, _, DbErr>(
+                |txn| Box::pin(async move { cake::Entity::find().one(txn).await }),
+                Some(IsolationLevel::Serializable),
+                None,
+                RetryPolicy::new(3).backoff(Duration::ZERO, Duration::ZERO),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            cake,
+            Some(cake::Model {
+                id: 1,
+                name: "Apple Cake".to_owned(),
+            })
+        );
+
+        let stmts = [
+            Statement::from_string(DbBackend::Postgres, "BEGIN".to_owned()),
+            Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                r#"SELECT "cake"."id", "cake"."name" FROM "cake" LIMIT $1"#,
+                [1u64.into()],
+            ),
+        ];
+        assert_eq!(
+            db.into_transaction_log(),
+            [
+                Transaction::many(stmts.iter().cloned().chain([Statement::from_string(
+                    DbBackend::Postgres,
+                    "ROLLBACK".to_owned()
+                )])),
+                Transaction::many(stmts.iter().cloned().chain([Statement::from_string(
+                    DbBackend::Postgres,
+                    "COMMIT".to_owned()
+                )])),
+            ]
+        );
+    }
+
+    #[smol_potat::test]
+    async fn test_savepoint() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres).into_connection();
+
+        let txn = db.begin().await?;
+        txn.savepoint("item_1").await?;
+        let _ = cake::Entity::find().one(&txn).await;
+        txn.rollback_to("item_1").await?;
+        txn.release("item_1").await?;
+        txn.commit().await?;
+
+        assert_eq!(
+            db.into_transaction_log(),
+            [Transaction::many([
+                Statement::from_string(DbBackend::Postgres, "BEGIN".to_owned()),
+                Statement::from_string(DbBackend::Postgres, r#"SAVEPOINT "item_1""#.to_owned()),
+                Statement::from_sql_and_values(
+                    DbBackend::Postgres,
+                    r#"SELECT "cake"."id", "cake"."name" FROM "cake" LIMIT $1"#,
+                    [1u64.into()]
+                ),
+                Statement::from_string(
+                    DbBackend::Postgres,
+                    r#"ROLLBACK TO SAVEPOINT "item_1""#.to_owned()
+                ),
+                Statement::from_string(
+                    DbBackend::Postgres,
+                    r#"RELEASE SAVEPOINT "item_1""#.to_owned()
+                ),
+                Statement::from_string(DbBackend::Postgres, "COMMIT".to_owned()),
+            ])]
+        );
+
+        Ok(())
+    }
+
     #[smol_potat::test]
     async fn test_nested_transaction_1() {
         let db = MockDatabase::new(DbBackend::Postgres).into_connection();
@@ -860,4 +1205,107 @@ mod tests {
             Err(exec_err("this is a mock exec error"))
         );
     }
+
+    #[smol_potat::test]
+    async fn test_statement_matcher_1() -> Result<(), DbErr> {
+        let apple = fruit::Model {
+            id: 1,
+            name: "Apple".to_owned(),
+            cake_id: Some(1),
+        };
+        let cheese_cake = cake::Model {
+            id: 1,
+            name: "Cheese Cake".to_owned(),
+        };
+
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_rule(StatementMatcher::entity(fruit::Entity), [apple.clone()])
+            .append_query_rule(
+                StatementMatcher::entity(cake::Entity),
+                [cheese_cake.clone()],
+            )
+            .append_exec_rule(
+                StatementMatcher::SqlPrefix(r#"DELETE FROM "cake""#.to_owned()),
+                MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 1,
+                },
+            )
+            .into_connection();
+
+        // Rules can be matched in any order and any number of times
+        for _ in 0..2 {
+            assert_eq!(cake::Entity::find().all(&db).await?, [cheese_cake.clone()]);
+            assert_eq!(fruit::Entity::find().all(&db).await?, [apple.clone()]);
+        }
+        assert_eq!(
+            cake::Entity::delete_by_id(1).exec(&db).await?.rows_affected,
+            1
+        );
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn test_statement_matcher_2() -> Result<(), DbErr> {
+        let apple = fruit::Model {
+            id: 1,
+            name: "Apple".to_owned(),
+            cake_id: Some(1),
+        };
+        let cheese_cake = cake::Model {
+            id: 1,
+            name: "Cheese Cake".to_owned(),
+        };
+
+        // Rules are tried before the ordered queues, which keep working alongside
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_rule(
+                StatementMatcher::predicate(|stmt| stmt.sql.contains(r#"WHERE "fruit"."cake_id""#)),
+                [apple.clone()],
+            )
+            .append_query_results([[cheese_cake.clone()]])
+            .append_query_error_rule(StatementMatcher::entity(fruit::Entity), || {
+                query_err("fruit is sold out")
+            })
+            .into_connection();
+
+        assert_eq!(
+            fruit::Entity::find()
+                .filter(fruit::Column::CakeId.eq(1))
+                .all(&db)
+                .await?,
+            [apple]
+        );
+        assert_eq!(
+            fruit::Entity::find().all(&db).await,
+            Err(query_err("fruit is sold out"))
+        );
+        assert_eq!(cake::Entity::find().all(&db).await?, [cheese_cake]);
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn test_statement_matcher_unmatched() {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_rule(
+                StatementMatcher::entity(fruit::Entity),
+                Vec::<fruit::Model>::new(),
+            )
+            .into_connection();
+
+        assert!(cake::Entity::find().all(&db).await.is_err());
+        assert_eq!(
+            cake::Entity::find_by_id(1).one(&db).await,
+            Err(query_err(
+                [
+                    "No rule matches the statement and `query_results` buffer is empty. Unmatched statements:",
+                    r#"  SELECT "cake"."id", "cake"."name" FROM "cake""#,
+                    r#"  SELECT "cake"."id", "cake"."name" FROM "cake" WHERE "cake"."id" = 1 LIMIT 1"#,
+                ]
+                .join("\n")
+            ))
+        );
+    }
 }
@@ -5,6 +5,8 @@ mod db_connection;
 #[cfg(feature = "mock")]
 #[cfg_attr(docsrs, doc(cfg(feature = "mock")))]
 mod mock;
+#[cfg(feature = "sqlx-dep")]
+mod slow_statement;
 mod statement;
 mod stream;
 mod transaction;
@@ -14,6 +16,8 @@ pub use db_connection::*;
 #[cfg(feature = "mock")]
 #[cfg_attr(docsrs, doc(cfg(feature = "mock")))]
 pub use mock::*;
+#[cfg(feature = "sqlx-dep")]
+pub(crate) use slow_statement::*;
 pub use statement::*;
 use std::borrow::Cow;
 pub use stream::*;
@@ -52,6 +56,16 @@ pub struct ConnectOptions {
     pub(crate) sqlcipher_key: Option<Cow<'static, str>>,
     /// Schema search path (PostgreSQL only)
     pub(crate) schema_search_path: Option<String>,
+    /// The URIs of the read replicas
+    pub(crate) read_replicas: Vec<String>,
+    /// Maximum time a statement may run before it is cancelled
+    pub(crate) statement_timeout: Option<Duration>,
+    /// Statements running longer than the threshold are logged
+    pub(crate) slow_statement_threshold: Option<Duration>,
+    /// Slow statement logging level
+    pub(crate) slow_statement_logging_level: log::LevelFilter,
+    /// Attach the query plan of slow statements to the log record
+    pub(crate) slow_statement_explain: bool,
 }
 
 impl Database {
@@ -84,6 +98,34 @@ impl Database {
             opt.url
         )))
     }
+
+    /// Open an in-memory SQLite database and create the tables of the given entities,
+    /// with their indexes, in foreign key dependency order. Useful for fast unit tests
+    /// relying on the database to evaluate filters and joins.
+    ///
+    /// The database lives in a single connection and is dropped along with it,
+    /// so the connection is never closed for being idle or too old.
+    #[cfg(feature = "sqlx-sqlite")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sqlx-sqlite")))]
+    #[instrument(level = "trace")]
+    pub async fn connect_in_memory_for<L>() -> Result<DatabaseConnection, DbErr>
+    where
+        L: crate::EntityList,
+    {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .min_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect("sqlite::memory:")
+            .await
+            .map_err(crate::sqlx_error_to_conn_err)?;
+        let db = crate::SqlxSqliteConnector::from_sqlx_sqlite_pool(pool);
+        for stmt in crate::Schema::new(DbBackend::Sqlite).create_statements_for::<L>() {
+            crate::ConnectionTrait::execute(&db, stmt).await?;
+        }
+        Ok(db)
+    }
 }
 
 impl From<&str> for ConnectOptions {
@@ -119,6 +161,11 @@ impl ConnectOptions {
             sqlx_logging_level: log::LevelFilter::Info,
             sqlcipher_key: None,
             schema_search_path: None,
+            statement_timeout: None,
+            slow_statement_threshold: None,
+            slow_statement_logging_level: log::LevelFilter::Warn,
+            slow_statement_explain: false,
+            read_replicas: Vec::new(),
         }
     }
 
@@ -262,4 +309,71 @@ impl ConnectOptions {
         self.schema_search_path = Some(schema_search_path);
         self
     }
+
+    /// Add the URI of a read replica. Each replica gets its own pool with the same options as the primary.
+    /// `SELECT` statements executed outside of a transaction are distributed across the replicas
+    /// in round-robin order. Any other statement, including writes with `RETURNING`, as well as
+    /// transactions, `SELECT` with row locks and the reads following an insert or update
+    /// (to find the inserted or updated model), always run on the primary.
+    pub fn read_replica(&mut self, url: String) -> &mut Self {
+        self.read_replicas.push(url);
+        self
+    }
+
+    /// Get the URIs of the read replicas
+    pub fn get_read_replicas(&self) -> &[String] {
+        &self.read_replicas
+    }
+
+    /// Set the maximum time a statement may run before it is cancelled, failing with
+    /// [DbErr::StatementTimeout]. It is applied with `statement_timeout` on PostgreSQL,
+    /// `max_execution_time` on MySQL (which only limits `SELECT` statements)
+    /// and by interrupting the statement from a progress handler on SQLite,
+    /// where streams are not subject to the timeout.
+    pub fn statement_timeout(&mut self, value: Duration) -> &mut Self {
+        self.statement_timeout = Some(value);
+        self
+    }
+
+    /// Get the statement timeout, if set
+    pub fn get_statement_timeout(&self) -> Option<Duration> {
+        self.statement_timeout
+    }
+
+    /// Log the statements running longer than the threshold, along with their bound values
+    /// and elapsed time, under the `sea_orm::slow_statement` target
+    pub fn slow_statement_threshold(&mut self, value: Duration) -> &mut Self {
+        self.slow_statement_threshold = Some(value);
+        self
+    }
+
+    /// Get the slow statement threshold, if set
+    pub fn get_slow_statement_threshold(&self) -> Option<Duration> {
+        self.slow_statement_threshold
+    }
+
+    /// Set slow statement logging level (default WARN)
+    /// (ignored if `slow_statement_threshold` is not set)
+    pub fn slow_statement_logging_level(&mut self, level: log::LevelFilter) -> &mut Self {
+        self.slow_statement_logging_level = level;
+        self
+    }
+
+    /// Get the level of slow statement logging
+    pub fn get_slow_statement_logging_level(&self) -> log::LevelFilter {
+        self.slow_statement_logging_level
+    }
+
+    /// Run `EXPLAIN` for slow queries and data modifications on the same connection
+    /// and attach the query plan to the log record (default false).
+    /// Statements run inside a transaction are logged without a plan.
+    pub fn slow_statement_explain(&mut self, value: bool) -> &mut Self {
+        self.slow_statement_explain = value;
+        self
+    }
+
+    /// Get whether the query plan of slow statements is logged
+    pub fn get_slow_statement_explain(&self) -> bool {
+        self.slow_statement_explain
+    }
 }
@@ -0,0 +1,186 @@
+use crate::{ConnectOptions, DbBackend, Statement};
+use std::time::Duration;
+
+/// Logging of the statements running longer than [ConnectOptions::slow_statement_threshold]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SlowStatementLog {
+    threshold: Duration,
+    level: log::Level,
+    explain: bool,
+}
+
+impl SlowStatementLog {
+    /// Returns `None` if no threshold is set or the logging level is `Off`
+    pub(crate) fn from_options(options: &ConnectOptions) -> Option<Self> {
+        Some(Self {
+            threshold: options.slow_statement_threshold?,
+            level: options.slow_statement_logging_level.to_level()?,
+            explain: options.slow_statement_explain,
+        })
+    }
+
+    pub(crate) fn is_slow(&self, elapsed: Duration) -> bool {
+        elapsed >= self.threshold
+    }
+
+    /// The `EXPLAIN` statement to get the query plan of a slow statement with,
+    /// if enabled and the statement is a query or a data modification
+    pub(crate) fn explain_statement(&self, stmt: &Statement) -> Option<Statement> {
+        if !self.explain {
+            return None;
+        }
+        let keyword = stmt
+            .sql
+            .trim_start()
+            .split(|c: char| !c.is_ascii_alphabetic())
+            .next()?
+            .to_ascii_uppercase();
+        if !matches!(
+            keyword.as_str(),
+            "SELECT" | "INSERT" | "UPDATE" | "DELETE" | "REPLACE" | "WITH"
+        ) {
+            return None;
+        }
+        let explain = match stmt.db_backend {
+            DbBackend::MySql => "EXPLAIN FORMAT=JSON",
+            DbBackend::Postgres => "EXPLAIN",
+            DbBackend::Sqlite => "EXPLAIN QUERY PLAN",
+        };
+        Some(Statement {
+            sql: format!("{} {}", explain, stmt.sql),
+            values: stmt.values.clone(),
+            db_backend: stmt.db_backend,
+        })
+    }
+
+    /// Log the statement with its bound values, elapsed time and query plan
+    pub(crate) fn log(&self, stmt: &Statement, elapsed: Duration, plan: Option<&str>) {
+        let values = stmt.values.as_ref().map(|values| values.0.as_slice());
+        match plan {
+            Some(plan) => log::log!(
+                target: "sea_orm::slow_statement",
+                self.level,
+                "slow statement: {}; values: {:?}; elapsed: {:?}; plan:\n{}",
+                stmt.sql,
+                values.unwrap_or_default(),
+                elapsed,
+                plan
+            ),
+            None => log::log!(
+                target: "sea_orm::slow_statement",
+                self.level,
+                "slow statement: {}; values: {:?}; elapsed: {:?}",
+                stmt.sql,
+                values.unwrap_or_default(),
+                elapsed
+            ),
+        }
+    }
+}
+
+/// Log the statement if it ran longer than the slow statement threshold, along with its
+/// query plan if enabled and the statement succeeded. The plan is not fetched inside a
+/// transaction, as a failing `EXPLAIN` would abort the transaction on Postgres.
+pub(crate) async fn log_slow_statement<DB>(
+    conn: &mut sqlx::pool::PoolConnection<DB>,
+    slow_statement_log: &Option<SlowStatementLog>,
+    stmt: &Statement,
+    elapsed: Duration,
+    failed: bool,
+    in_transaction: bool,
+) where
+    DB: sqlx::Database,
+    for<'c> &'c mut DB::Connection: sqlx::Executor<'c, Database = DB>,
+    for<'q> sea_query_binder::SqlxValues: sqlx::IntoArguments<'q, DB>,
+    for<'r> String: sqlx::Decode<'r, DB> + sqlx::Type<DB>,
+    usize: sqlx::ColumnIndex<DB::Row>,
+{
+    use sqlx::Row;
+
+    let log = match slow_statement_log {
+        Some(log) if log.is_slow(elapsed) => log,
+        _ => return,
+    };
+    let mut plan = None;
+    if let Some(explain) = log
+        .explain_statement(stmt)
+        .filter(|_| !failed && !in_transaction)
+    {
+        let values = explain
+            .values
+            .clone()
+            .unwrap_or(sea_query::Values(Vec::new()));
+        let query = sqlx::query_with(&explain.sql, sea_query_binder::SqlxValues(values));
+        if let Ok(rows) = query.fetch_all(&mut **conn).await {
+            // The plan is in the `detail` column of `EXPLAIN QUERY PLAN` on SQLite
+            let column: usize = match stmt.db_backend {
+                DbBackend::Sqlite => 3,
+                DbBackend::MySql | DbBackend::Postgres => 0,
+            };
+            let lines: Vec<String> = rows
+                .iter()
+                .filter_map(|row| row.try_get(column).ok())
+                .collect();
+            plan = Some(lines.join("\n"));
+        }
+    }
+    log.log(stmt, elapsed, plan.as_deref());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SlowStatementLog;
+    use crate::{ConnectOptions, DbBackend, Statement};
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    #[test]
+    fn test_explain_statement() {
+        let mut options = ConnectOptions::new("sqlite::memory:".to_owned());
+        assert!(SlowStatementLog::from_options(&options).is_none());
+
+        options
+            .slow_statement_threshold(Duration::from_millis(100))
+            .slow_statement_explain(true);
+        let log = SlowStatementLog::from_options(&options).unwrap();
+        assert!(!log.is_slow(Duration::from_millis(99)));
+        assert!(log.is_slow(Duration::from_millis(100)));
+
+        let stmt = Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"SELECT "cake"."id" FROM "cake" WHERE "cake"."id" = $1"#,
+            [1.into()],
+        );
+        assert_eq!(
+            log.explain_statement(&stmt),
+            Some(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                r#"EXPLAIN SELECT "cake"."id" FROM "cake" WHERE "cake"."id" = $1"#,
+                [1.into()],
+            ))
+        );
+
+        let stmt = Statement::from_string(
+            DbBackend::MySql,
+            "INSERT INTO `cake` (`name`) VALUES ('Apple Pie')".to_owned(),
+        );
+        assert_eq!(
+            log.explain_statement(&stmt).unwrap().sql,
+            "EXPLAIN FORMAT=JSON INSERT INTO `cake` (`name`) VALUES ('Apple Pie')"
+        );
+
+        let stmt = Statement::from_string(
+            DbBackend::Sqlite,
+            r#"CREATE TABLE "cake" ("id" integer)"#.to_owned(),
+        );
+        assert_eq!(log.explain_statement(&stmt), None);
+
+        options.slow_statement_explain(false);
+        let log = SlowStatementLog::from_options(&options).unwrap();
+        let stmt = Statement::from_string(DbBackend::Sqlite, "SELECT 1".to_owned());
+        assert_eq!(log.explain_statement(&stmt), None);
+
+        options.slow_statement_logging_level(log::LevelFilter::Off);
+        assert!(SlowStatementLog::from_options(&options).is_none());
+    }
+}
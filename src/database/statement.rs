@@ -1,7 +1,7 @@
 use crate::DbBackend;
 use sea_query::{inject_parameters, MysqlQueryBuilder, PostgresQueryBuilder, SqliteQueryBuilder};
 pub use sea_query::{Value, Values};
-use std::fmt;
+use std::{fmt, time::Duration};
 
 /// Defines an SQL statement
 #[derive(Debug, Clone, PartialEq)]
@@ -15,6 +15,14 @@ pub struct Statement {
     pub db_backend: DbBackend,
 }
 
+/// Options for running a single [Statement], passed beside it to the connection,
+/// see [ConnectionTrait::execute_with_options](crate::ConnectionTrait::execute_with_options)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatementOptions {
+    timeout: Option<Duration>,
+    read_from_primary: bool,
+}
+
 /// Any type that can build a [Statement]
 pub trait StatementBuilder {
     /// Method to call in order to build a [Statement]
@@ -55,6 +63,42 @@ impl Statement {
     }
 }
 
+impl StatementOptions {
+    /// Create [StatementOptions] running the statement with the connection's defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the timeout of the statement, overriding the connection's statement timeout.
+    /// Streams are not cancelled by the timeout: on PostgreSQL and MySQL they run under
+    /// the connection's statement timeout, and on SQLite without a timeout.
+    ///
+    /// On PostgreSQL, the timeout is set with `SET LOCAL statement_timeout`, so that it ends with
+    /// the transaction even if the statement is cancelled. Outside of a transaction, the statement
+    /// runs in a transaction of its own.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Get the timeout of the statement, if set
+    pub fn get_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Run the statement on the primary even if it is a `SELECT`,
+    /// so it reads the writes made just before it instead of a possibly lagging read replica
+    pub fn read_from_primary(mut self) -> Self {
+        self.read_from_primary = true;
+        self
+    }
+
+    /// Check if the statement runs on the primary even if it is a `SELECT`
+    pub fn get_read_from_primary(&self) -> bool {
+        self.read_from_primary
+    }
+}
+
 impl fmt::Display for Statement {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match &self.values {
@@ -108,6 +152,14 @@ build_query_stmt!(sea_query::SelectStatement);
 build_query_stmt!(sea_query::UpdateStatement);
 build_query_stmt!(sea_query::DeleteStatement);
 
+impl StatementBuilder for sea_query::WithQuery {
+    fn build(&self, db_backend: &DbBackend) -> Statement {
+        let query_builder = db_backend.get_query_builder();
+        let stmt = sea_query::QueryStatementBuilder::build_any(self, query_builder.as_ref());
+        Statement::from_string_values_tuple(*db_backend, stmt)
+    }
+}
+
 macro_rules! build_schema_stmt {
     ($stmt: ty) => {
         impl StatementBuilder for $stmt {
@@ -8,6 +8,12 @@ pub(crate) struct MetricStream<'a> {
     metric_callback: &'a Option<crate::metric::Callback>,
     stmt: &'a Statement,
     elapsed: Option<Duration>,
+    acquire_elapsed: Duration,
+    in_transaction: bool,
+    rows: u64,
+    failed: bool,
+    #[cfg(feature = "tracing-spans")]
+    span: tracing::Span,
     stream: Pin<Box<dyn Stream<Item = Result<QueryResult, DbErr>> + 'a + Send>>,
 }
 
@@ -17,6 +23,7 @@ impl<'a> MetricStream<'a> {
         metric_callback: &'a Option<crate::metric::Callback>,
         stmt: &'a Statement,
         elapsed: Option<Duration>,
+        in_transaction: bool,
         stream: S,
     ) -> Self
     where
@@ -26,9 +33,23 @@ impl<'a> MetricStream<'a> {
             metric_callback,
             stmt,
             elapsed,
+            acquire_elapsed: Duration::ZERO,
+            in_transaction,
+            rows: 0,
+            failed: false,
+            #[cfg(feature = "tracing-spans")]
+            span: crate::tracing_spans::statement_span(
+                stmt,
+                crate::metric::OperationKind::Stream,
+                in_transaction,
+            ),
             stream: Box::pin(stream),
         }
     }
+
+    pub(crate) fn set_acquire_elapsed(&mut self, acquire_elapsed: Duration) {
+        self.acquire_elapsed = acquire_elapsed;
+    }
 }
 
 impl<'a> Stream for MetricStream<'a> {
@@ -43,21 +64,43 @@ impl<'a> Stream for MetricStream<'a> {
             .metric_callback
             .is_some()
             .then(std::time::SystemTime::now);
+        #[cfg(feature = "tracing-spans")]
+        let res = this
+            .span
+            .in_scope(|| Pin::new(&mut this.stream).poll_next(cx));
+        #[cfg(not(feature = "tracing-spans"))]
         let res = Pin::new(&mut this.stream).poll_next(cx);
         if let (Some(_start), Some(elapsed)) = (_start, &mut this.elapsed) {
             *elapsed += _start.elapsed().unwrap_or_default();
         }
+        match &res {
+            Poll::Ready(Some(Ok(_))) => this.rows += 1,
+            Poll::Ready(Some(Err(_err))) => {
+                this.failed = true;
+                #[cfg(feature = "tracing-spans")]
+                crate::tracing_spans::record_rows(&this.span, Err(_err));
+            }
+            _ => {}
+        }
         res
     }
 }
 
 impl<'a> Drop for MetricStream<'a> {
     fn drop(&mut self) {
+        #[cfg(feature = "tracing-spans")]
+        if !self.failed {
+            crate::tracing_spans::record_rows::<DbErr>(&self.span, Ok(self.rows));
+        }
         if let (Some(callback), Some(elapsed)) = (self.metric_callback.as_deref(), self.elapsed) {
             let info = crate::metric::Info {
                 elapsed,
+                acquire_elapsed: self.acquire_elapsed,
                 statement: self.stmt,
-                failed: false,
+                failed: self.failed,
+                rows: (!self.failed).then_some(self.rows),
+                kind: crate::metric::OperationKind::Stream,
+                in_transaction: self.in_transaction,
             };
             callback(&info);
         }
@@ -132,7 +132,7 @@ impl QueryStream {
                         .map_ok(Into::into)
                         .map_err(sqlx_error_to_query_err);
                     let elapsed = _start.map(|s| s.elapsed().unwrap_or_default());
-                    MetricStream::new(_metric_callback, stmt, elapsed, stream)
+                    MetricStream::new(_metric_callback, stmt, elapsed, false, stream)
                 }
                 #[cfg(feature = "sqlx-postgres")]
                 InnerConnection::Postgres(c) => {
@@ -143,7 +143,7 @@ impl QueryStream {
                         .map_ok(Into::into)
                         .map_err(sqlx_error_to_query_err);
                     let elapsed = _start.map(|s| s.elapsed().unwrap_or_default());
-                    MetricStream::new(_metric_callback, stmt, elapsed, stream)
+                    MetricStream::new(_metric_callback, stmt, elapsed, false, stream)
                 }
                 #[cfg(feature = "sqlx-sqlite")]
                 InnerConnection::Sqlite(c) => {
@@ -154,14 +154,14 @@ impl QueryStream {
                         .map_ok(Into::into)
                         .map_err(sqlx_error_to_query_err);
                     let elapsed = _start.map(|s| s.elapsed().unwrap_or_default());
-                    MetricStream::new(_metric_callback, stmt, elapsed, stream)
+                    MetricStream::new(_metric_callback, stmt, elapsed, false, stream)
                 }
                 #[cfg(feature = "mock")]
                 InnerConnection::Mock(c) => {
                     let _start = _metric_callback.is_some().then(std::time::SystemTime::now);
                     let stream = c.fetch(stmt);
                     let elapsed = _start.map(|s| s.elapsed().unwrap_or_default());
-                    MetricStream::new(_metric_callback, stmt, elapsed, stream)
+                    MetricStream::new(_metric_callback, stmt, elapsed, false, stream)
                 }
                 #[allow(unreachable_patterns)]
                 _ => unreachable!(),
@@ -169,6 +169,13 @@ impl QueryStream {
         }
         .build()
     }
+
+    /// Report the time spent waiting for the connection to the metric callback
+    #[cfg(feature = "sqlx-dep")]
+    pub(crate) fn with_acquire_elapsed(mut self, acquire_elapsed: std::time::Duration) -> Self {
+        self.with_stream_mut(|stream| stream.set_acquire_elapsed(acquire_elapsed));
+        self
+    }
 }
 
 impl Stream for QueryStream {
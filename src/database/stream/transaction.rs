@@ -40,8 +40,9 @@ impl<'a> TransactionStream<'a> {
         conn: MutexGuard<'a, InnerConnection>,
         stmt: Statement,
         metric_callback: Option<crate::metric::Callback>,
+        acquire_elapsed: std::time::Duration,
     ) -> TransactionStream<'a> {
-        TransactionStreamBuilder {
+        let mut stream = TransactionStreamBuilder {
             stmt,
             conn,
             metric_callback,
@@ -55,7 +56,7 @@ impl<'a> TransactionStream<'a> {
                         .map_ok(Into::into)
                         .map_err(sqlx_error_to_query_err);
                     let elapsed = _start.map(|s| s.elapsed().unwrap_or_default());
-                    MetricStream::new(_metric_callback, stmt, elapsed, stream)
+                    MetricStream::new(_metric_callback, stmt, elapsed, true, stream)
                 }
                 #[cfg(feature = "sqlx-postgres")]
                 InnerConnection::Postgres(c) => {
@@ -66,7 +67,7 @@ impl<'a> TransactionStream<'a> {
                         .map_ok(Into::into)
                         .map_err(sqlx_error_to_query_err);
                     let elapsed = _start.map(|s| s.elapsed().unwrap_or_default());
-                    MetricStream::new(_metric_callback, stmt, elapsed, stream)
+                    MetricStream::new(_metric_callback, stmt, elapsed, true, stream)
                 }
                 #[cfg(feature = "sqlx-sqlite")]
                 InnerConnection::Sqlite(c) => {
@@ -77,20 +78,22 @@ impl<'a> TransactionStream<'a> {
                         .map_ok(Into::into)
                         .map_err(sqlx_error_to_query_err);
                     let elapsed = _start.map(|s| s.elapsed().unwrap_or_default());
-                    MetricStream::new(_metric_callback, stmt, elapsed, stream)
+                    MetricStream::new(_metric_callback, stmt, elapsed, true, stream)
                 }
                 #[cfg(feature = "mock")]
                 InnerConnection::Mock(c) => {
                     let _start = _metric_callback.is_some().then(std::time::SystemTime::now);
                     let stream = c.fetch(stmt);
                     let elapsed = _start.map(|s| s.elapsed().unwrap_or_default());
-                    MetricStream::new(_metric_callback, stmt, elapsed, stream)
+                    MetricStream::new(_metric_callback, stmt, elapsed, true, stream)
                 }
                 #[allow(unreachable_patterns)]
                 _ => unreachable!(),
             },
         }
-        .build()
+        .build();
+        stream.with_stream_mut(|stream| stream.set_acquire_elapsed(acquire_elapsed));
+        stream
     }
 }
 
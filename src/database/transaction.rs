@@ -1,14 +1,17 @@
 use crate::{
     debug_print, error::*, AccessMode, ConnectionTrait, DbBackend, DbErr, ExecResult,
-    InnerConnection, IsolationLevel, QueryResult, Statement, StreamTrait, TransactionStream,
-    TransactionTrait,
+    InnerConnection, IsolationLevel, QueryResult, Statement, StatementOptions, StreamTrait,
+    TransactionStream, TransactionTrait,
 };
 #[cfg(feature = "sqlx-dep")]
-use crate::{sqlx_error_to_exec_err, sqlx_error_to_query_err};
+use crate::{
+    metric::OperationKind, sqlx_error_to_exec_err, sqlx_error_to_query_err, SlowStatementLog,
+};
 use futures::lock::Mutex;
+use sea_query::{Alias, Iden};
 #[cfg(feature = "sqlx-dep")]
 use sqlx::{pool::PoolConnection, TransactionManager};
-use std::{future::Future, pin::Pin, sync::Arc};
+use std::{future::Future, pin::Pin, sync::Arc, time::Duration};
 use tracing::instrument;
 
 // a Transaction is just a sugar for a connection where START TRANSACTION has been executed
@@ -19,6 +22,9 @@ pub struct DatabaseTransaction {
     backend: DbBackend,
     open: bool,
     metric_callback: Option<crate::metric::Callback>,
+    #[cfg(feature = "sqlx-dep")]
+    slow_statement_log: Option<SlowStatementLog>,
+    statement_timeout: Option<Duration>,
 }
 
 impl std::fmt::Debug for DatabaseTransaction {
@@ -32,6 +38,7 @@ impl DatabaseTransaction {
     pub(crate) async fn new_mysql(
         inner: PoolConnection<sqlx::MySql>,
         metric_callback: Option<crate::metric::Callback>,
+        slow_statement_log: Option<SlowStatementLog>,
         isolation_level: Option<IsolationLevel>,
         access_mode: Option<AccessMode>,
     ) -> Result<DatabaseTransaction, DbErr> {
@@ -39,16 +46,19 @@ impl DatabaseTransaction {
             Arc::new(Mutex::new(InnerConnection::MySql(inner))),
             DbBackend::MySql,
             metric_callback,
+            None,
             isolation_level,
             access_mode,
         )
         .await
+        .map(|txn| txn.with_slow_statement_log(slow_statement_log))
     }
 
     #[cfg(feature = "sqlx-postgres")]
     pub(crate) async fn new_postgres(
         inner: PoolConnection<sqlx::Postgres>,
         metric_callback: Option<crate::metric::Callback>,
+        slow_statement_log: Option<SlowStatementLog>,
         isolation_level: Option<IsolationLevel>,
         access_mode: Option<AccessMode>,
     ) -> Result<DatabaseTransaction, DbErr> {
@@ -56,16 +66,20 @@ impl DatabaseTransaction {
             Arc::new(Mutex::new(InnerConnection::Postgres(inner))),
             DbBackend::Postgres,
             metric_callback,
+            None,
             isolation_level,
             access_mode,
         )
         .await
+        .map(|txn| txn.with_slow_statement_log(slow_statement_log))
     }
 
     #[cfg(feature = "sqlx-sqlite")]
     pub(crate) async fn new_sqlite(
         inner: PoolConnection<sqlx::Sqlite>,
         metric_callback: Option<crate::metric::Callback>,
+        slow_statement_log: Option<SlowStatementLog>,
+        statement_timeout: Option<Duration>,
         isolation_level: Option<IsolationLevel>,
         access_mode: Option<AccessMode>,
     ) -> Result<DatabaseTransaction, DbErr> {
@@ -73,10 +87,12 @@ impl DatabaseTransaction {
             Arc::new(Mutex::new(InnerConnection::Sqlite(inner))),
             DbBackend::Sqlite,
             metric_callback,
+            statement_timeout,
             isolation_level,
             access_mode,
         )
         .await
+        .map(|txn| txn.with_slow_statement_log(slow_statement_log))
     }
 
     #[cfg(feature = "mock")]
@@ -91,6 +107,7 @@ impl DatabaseTransaction {
             metric_callback,
             None,
             None,
+            None,
         )
         .await
     }
@@ -100,6 +117,7 @@ impl DatabaseTransaction {
         conn: Arc<Mutex<InnerConnection>>,
         backend: DbBackend,
         metric_callback: Option<crate::metric::Callback>,
+        statement_timeout: Option<Duration>,
         isolation_level: Option<IsolationLevel>,
         access_mode: Option<AccessMode>,
     ) -> Result<DatabaseTransaction, DbErr> {
@@ -108,6 +126,9 @@ impl DatabaseTransaction {
             backend,
             open: true,
             metric_callback,
+            #[cfg(feature = "sqlx-dep")]
+            slow_statement_log: None,
+            statement_timeout,
         };
         match *res.conn.lock().await {
             #[cfg(feature = "sqlx-mysql")]
@@ -152,6 +173,12 @@ impl DatabaseTransaction {
         Ok(res)
     }
 
+    #[cfg(feature = "sqlx-dep")]
+    fn with_slow_statement_log(mut self, slow_statement_log: Option<SlowStatementLog>) -> Self {
+        self.slow_statement_log = slow_statement_log;
+        self
+    }
+
     /// Runs a transaction to completion returning an rolling back the transaction on
     /// encountering an error if it fails
     #[instrument(level = "trace", skip(callback))]
@@ -245,6 +272,66 @@ impl DatabaseTransaction {
         Ok(())
     }
 
+    /// Create a savepoint with the given name inside this transaction
+    #[instrument(level = "trace")]
+    pub async fn savepoint(&self, name: &str) -> Result<(), DbErr> {
+        let sql = format!("SAVEPOINT {}", self.quote_savepoint(name));
+        self.execute_savepoint(sql).await
+    }
+
+    /// Roll back to the savepoint with the given name, discarding the changes made after it.
+    /// The savepoint is kept, so it can be rolled back to again.
+    #[instrument(level = "trace")]
+    pub async fn rollback_to(&self, name: &str) -> Result<(), DbErr> {
+        let sql = format!("ROLLBACK TO SAVEPOINT {}", self.quote_savepoint(name));
+        self.execute_savepoint(sql).await
+    }
+
+    /// Release the savepoint with the given name, keeping the changes made after it
+    #[instrument(level = "trace")]
+    pub async fn release(&self, name: &str) -> Result<(), DbErr> {
+        let sql = format!("RELEASE SAVEPOINT {}", self.quote_savepoint(name));
+        self.execute_savepoint(sql).await
+    }
+
+    fn quote_savepoint(&self, name: &str) -> String {
+        let quote = match self.backend {
+            DbBackend::MySql => '`',
+            DbBackend::Postgres | DbBackend::Sqlite => '"',
+        };
+        format!("{quote}{}{quote}", Alias::new(name).quoted(quote))
+    }
+
+    #[allow(unused_variables)]
+    async fn execute_savepoint(&self, sql: String) -> Result<(), DbErr> {
+        debug_print!("{}", sql);
+
+        match &mut *self.conn.lock().await {
+            #[cfg(feature = "sqlx-mysql")]
+            InnerConnection::MySql(conn) => sqlx::Executor::execute(conn, sql.as_str())
+                .await
+                .map(|_| ())
+                .map_err(sqlx_error_to_exec_err),
+            #[cfg(feature = "sqlx-postgres")]
+            InnerConnection::Postgres(conn) => sqlx::Executor::execute(conn, sql.as_str())
+                .await
+                .map(|_| ())
+                .map_err(sqlx_error_to_exec_err),
+            #[cfg(feature = "sqlx-sqlite")]
+            InnerConnection::Sqlite(conn) => sqlx::Executor::execute(conn, sql.as_str())
+                .await
+                .map(|_| ())
+                .map_err(sqlx_error_to_exec_err),
+            #[cfg(feature = "mock")]
+            InnerConnection::Mock(conn) => {
+                conn.savepoint(Statement::from_string(self.backend, sql));
+                Ok(())
+            }
+            #[allow(unreachable_patterns)]
+            _ => Err(conn_err("Disconnected")),
+        }
+    }
+
     // the rollback is queued and will be performed on next async operation, like returning the connection to the pool
     #[instrument(level = "trace")]
     fn start_rollback(&mut self) -> Result<(), DbErr> {
@@ -279,13 +366,17 @@ impl DatabaseTransaction {
     }
 
     #[cfg(feature = "sqlx-dep")]
-    fn map_err_ignore_not_found<T: std::fmt::Debug>(
-        err: Result<Option<T>, sqlx::Error>,
-    ) -> Result<Option<T>, DbErr> {
-        if let Err(sqlx::Error::RowNotFound) = err {
-            Ok(None)
-        } else {
-            err.map_err(sqlx_error_to_query_err)
+    fn statement_context(
+        &self,
+        kind: OperationKind,
+        acquire_elapsed: Duration,
+    ) -> crate::driver::StatementContext<'_> {
+        crate::driver::StatementContext {
+            metric_callback: &self.metric_callback,
+            slow_statement_log: &self.slow_statement_log,
+            kind,
+            acquire_elapsed,
+            in_transaction: true,
         }
     }
 }
@@ -303,35 +394,46 @@ impl ConnectionTrait for DatabaseTransaction {
         self.backend
     }
 
+    async fn execute(&self, stmt: Statement) -> Result<ExecResult, DbErr> {
+        self.execute_with_options(stmt, StatementOptions::default())
+            .await
+    }
+
     #[instrument(level = "trace")]
     #[allow(unused_variables)]
-    async fn execute(&self, stmt: Statement) -> Result<ExecResult, DbErr> {
+    async fn execute_with_options(
+        &self,
+        stmt: Statement,
+        options: StatementOptions,
+    ) -> Result<ExecResult, DbErr> {
         debug_print!("{}", stmt);
 
-        match &mut *self.conn.lock().await {
+        let acquire_start = std::time::Instant::now();
+        let mut conn = self.conn.lock().await;
+        let acquire_elapsed = acquire_start.elapsed();
+        match &mut *conn {
             #[cfg(feature = "sqlx-mysql")]
             InnerConnection::MySql(conn) => {
-                let query = crate::driver::sqlx_mysql::sqlx_query(&stmt);
-                crate::metric::metric!(self.metric_callback, &stmt, {
-                    query.execute(conn).await.map(Into::into)
-                })
-                .map_err(sqlx_error_to_exec_err)
+                let ctx = self.statement_context(OperationKind::Execute, acquire_elapsed);
+                crate::driver::sqlx_mysql::sqlx_execute(conn, stmt, options.get_timeout(), ctx)
+                    .await
             }
             #[cfg(feature = "sqlx-postgres")]
             InnerConnection::Postgres(conn) => {
-                let query = crate::driver::sqlx_postgres::sqlx_query(&stmt);
-                crate::metric::metric!(self.metric_callback, &stmt, {
-                    query.execute(conn).await.map(Into::into)
-                })
-                .map_err(sqlx_error_to_exec_err)
+                let ctx = self.statement_context(OperationKind::Execute, acquire_elapsed);
+                crate::driver::sqlx_postgres::sqlx_execute(conn, stmt, options.get_timeout(), ctx)
+                    .await
             }
             #[cfg(feature = "sqlx-sqlite")]
             InnerConnection::Sqlite(conn) => {
-                let query = crate::driver::sqlx_sqlite::sqlx_query(&stmt);
-                crate::metric::metric!(self.metric_callback, &stmt, {
-                    query.execute(conn).await.map(Into::into)
-                })
-                .map_err(sqlx_error_to_exec_err)
+                let ctx = self.statement_context(OperationKind::Execute, acquire_elapsed);
+                crate::driver::sqlx_sqlite::sqlx_execute(
+                    conn,
+                    stmt,
+                    options.get_timeout().or(self.statement_timeout),
+                    ctx,
+                )
+                .await
             }
             #[cfg(feature = "mock")]
             InnerConnection::Mock(conn) => return conn.execute(stmt),
@@ -372,38 +474,46 @@ impl ConnectionTrait for DatabaseTransaction {
         }
     }
 
+    async fn query_one(&self, stmt: Statement) -> Result<Option<QueryResult>, DbErr> {
+        self.query_one_with_options(stmt, StatementOptions::default())
+            .await
+    }
+
     #[instrument(level = "trace")]
     #[allow(unused_variables)]
-    async fn query_one(&self, stmt: Statement) -> Result<Option<QueryResult>, DbErr> {
+    async fn query_one_with_options(
+        &self,
+        stmt: Statement,
+        options: StatementOptions,
+    ) -> Result<Option<QueryResult>, DbErr> {
         debug_print!("{}", stmt);
 
-        match &mut *self.conn.lock().await {
+        let acquire_start = std::time::Instant::now();
+        let mut conn = self.conn.lock().await;
+        let acquire_elapsed = acquire_start.elapsed();
+        match &mut *conn {
             #[cfg(feature = "sqlx-mysql")]
             InnerConnection::MySql(conn) => {
-                let query = crate::driver::sqlx_mysql::sqlx_query(&stmt);
-                crate::metric::metric!(self.metric_callback, &stmt, {
-                    Self::map_err_ignore_not_found(
-                        query.fetch_one(conn).await.map(|row| Some(row.into())),
-                    )
-                })
+                let ctx = self.statement_context(OperationKind::QueryOne, acquire_elapsed);
+                crate::driver::sqlx_mysql::sqlx_query_one(conn, stmt, options.get_timeout(), ctx)
+                    .await
             }
             #[cfg(feature = "sqlx-postgres")]
             InnerConnection::Postgres(conn) => {
-                let query = crate::driver::sqlx_postgres::sqlx_query(&stmt);
-                crate::metric::metric!(self.metric_callback, &stmt, {
-                    Self::map_err_ignore_not_found(
-                        query.fetch_one(conn).await.map(|row| Some(row.into())),
-                    )
-                })
+                let ctx = self.statement_context(OperationKind::QueryOne, acquire_elapsed);
+                crate::driver::sqlx_postgres::sqlx_query_one(conn, stmt, options.get_timeout(), ctx)
+                    .await
             }
             #[cfg(feature = "sqlx-sqlite")]
             InnerConnection::Sqlite(conn) => {
-                let query = crate::driver::sqlx_sqlite::sqlx_query(&stmt);
-                crate::metric::metric!(self.metric_callback, &stmt, {
-                    Self::map_err_ignore_not_found(
-                        query.fetch_one(conn).await.map(|row| Some(row.into())),
-                    )
-                })
+                let ctx = self.statement_context(OperationKind::QueryOne, acquire_elapsed);
+                crate::driver::sqlx_sqlite::sqlx_query_one(
+                    conn,
+                    stmt,
+                    options.get_timeout().or(self.statement_timeout),
+                    ctx,
+                )
+                .await
             }
             #[cfg(feature = "mock")]
             InnerConnection::Mock(conn) => return conn.query_one(stmt),
@@ -412,44 +522,46 @@ impl ConnectionTrait for DatabaseTransaction {
         }
     }
 
+    async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr> {
+        self.query_all_with_options(stmt, StatementOptions::default())
+            .await
+    }
+
     #[instrument(level = "trace")]
     #[allow(unused_variables)]
-    async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr> {
+    async fn query_all_with_options(
+        &self,
+        stmt: Statement,
+        options: StatementOptions,
+    ) -> Result<Vec<QueryResult>, DbErr> {
         debug_print!("{}", stmt);
 
-        match &mut *self.conn.lock().await {
+        let acquire_start = std::time::Instant::now();
+        let mut conn = self.conn.lock().await;
+        let acquire_elapsed = acquire_start.elapsed();
+        match &mut *conn {
             #[cfg(feature = "sqlx-mysql")]
             InnerConnection::MySql(conn) => {
-                let query = crate::driver::sqlx_mysql::sqlx_query(&stmt);
-                crate::metric::metric!(self.metric_callback, &stmt, {
-                    query
-                        .fetch_all(conn)
-                        .await
-                        .map(|rows| rows.into_iter().map(|r| r.into()).collect())
-                        .map_err(sqlx_error_to_query_err)
-                })
+                let ctx = self.statement_context(OperationKind::QueryAll, acquire_elapsed);
+                crate::driver::sqlx_mysql::sqlx_query_all(conn, stmt, options.get_timeout(), ctx)
+                    .await
             }
             #[cfg(feature = "sqlx-postgres")]
             InnerConnection::Postgres(conn) => {
-                let query = crate::driver::sqlx_postgres::sqlx_query(&stmt);
-                crate::metric::metric!(self.metric_callback, &stmt, {
-                    query
-                        .fetch_all(conn)
-                        .await
-                        .map(|rows| rows.into_iter().map(|r| r.into()).collect())
-                        .map_err(sqlx_error_to_query_err)
-                })
+                let ctx = self.statement_context(OperationKind::QueryAll, acquire_elapsed);
+                crate::driver::sqlx_postgres::sqlx_query_all(conn, stmt, options.get_timeout(), ctx)
+                    .await
             }
             #[cfg(feature = "sqlx-sqlite")]
             InnerConnection::Sqlite(conn) => {
-                let query = crate::driver::sqlx_sqlite::sqlx_query(&stmt);
-                crate::metric::metric!(self.metric_callback, &stmt, {
-                    query
-                        .fetch_all(conn)
-                        .await
-                        .map(|rows| rows.into_iter().map(|r| r.into()).collect())
-                        .map_err(sqlx_error_to_query_err)
-                })
+                let ctx = self.statement_context(OperationKind::QueryAll, acquire_elapsed);
+                crate::driver::sqlx_sqlite::sqlx_query_all(
+                    conn,
+                    stmt,
+                    options.get_timeout().or(self.statement_timeout),
+                    ctx,
+                )
+                .await
             }
             #[cfg(feature = "mock")]
             InnerConnection::Mock(conn) => return conn.query_all(stmt),
@@ -468,11 +580,13 @@ impl StreamTrait for DatabaseTransaction {
         stmt: Statement,
     ) -> Pin<Box<dyn Future<Output = Result<Self::Stream<'a>, DbErr>> + 'a + Send>> {
         Box::pin(async move {
+            let acquire_start = std::time::Instant::now();
             let conn = self.conn.lock().await;
             Ok(crate::TransactionStream::build(
                 conn,
                 stmt,
                 self.metric_callback.clone(),
+                acquire_start.elapsed(),
             ))
         })
     }
@@ -482,14 +596,7 @@ impl StreamTrait for DatabaseTransaction {
 impl TransactionTrait for DatabaseTransaction {
     #[instrument(level = "trace")]
     async fn begin(&self) -> Result<DatabaseTransaction, DbErr> {
-        DatabaseTransaction::begin(
-            Arc::clone(&self.conn),
-            self.backend,
-            self.metric_callback.clone(),
-            None,
-            None,
-        )
-        .await
+        self.begin_with_config(None, None).await
     }
 
     #[instrument(level = "trace")]
@@ -498,14 +605,18 @@ impl TransactionTrait for DatabaseTransaction {
         isolation_level: Option<IsolationLevel>,
         access_mode: Option<AccessMode>,
     ) -> Result<DatabaseTransaction, DbErr> {
-        DatabaseTransaction::begin(
+        let txn = DatabaseTransaction::begin(
             Arc::clone(&self.conn),
             self.backend,
             self.metric_callback.clone(),
+            self.statement_timeout,
             isolation_level,
             access_mode,
         )
-        .await
+        .await?;
+        #[cfg(feature = "sqlx-dep")]
+        let txn = txn.with_slow_statement_log(self.slow_statement_log);
+        Ok(txn)
     }
 
     /// Execute the function inside a transaction.
@@ -575,6 +686,29 @@ where
 
 impl<E> std::error::Error for TransactionError<E> where E: std::error::Error {}
 
+impl<E> TransactionError<E>
+where
+    E: std::error::Error + 'static,
+{
+    /// Check if the transaction failed due to a serialization failure or a deadlock.
+    /// An error returned by the callback is retryable if it, or any of its sources, is a retryable [DbErr].
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            TransactionError::Connection(e) => e.is_retryable(),
+            TransactionError::Transaction(e) => {
+                let mut source: Option<&(dyn std::error::Error + 'static)> = Some(e);
+                while let Some(err) = source {
+                    if let Some(db_err) = err.downcast_ref::<DbErr>() {
+                        return db_err.is_retryable();
+                    }
+                    source = err.source();
+                }
+                false
+            }
+        }
+    }
+}
+
 impl<E> From<DbErr> for TransactionError<E>
 where
     E: std::error::Error,
@@ -42,6 +42,10 @@ pub trait MockDatabaseTrait: Send + Debug {
     /// Roll back a transaction since errors were encountered
     fn rollback(&mut self);
 
+    /// Create, roll back to or release a named savepoint inside the open transaction.
+    /// The default implementation ignores the statement.
+    fn savepoint(&mut self, _stmt: Statement) {}
+
     /// Get all logs from a [MockDatabase] and return a [Transaction]
     fn drain_transaction_log(&mut self) -> Vec<Transaction>;
 
@@ -194,4 +198,18 @@ impl MockDatabaseConnection {
             .expect("Failed to acquire mocker")
             .rollback()
     }
+
+    /// Create, roll back to or release a named savepoint
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mocker mutex is being held by another thread.
+    #[instrument(level = "trace")]
+    pub fn savepoint(&self, statement: Statement) {
+        debug_print!("{}", statement);
+        self.mocker
+            .lock()
+            .expect("Failed to acquire mocker")
+            .savepoint(statement)
+    }
 }
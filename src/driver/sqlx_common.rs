@@ -1,12 +1,29 @@
-use crate::{DbErr, RuntimeErr};
+use crate::{
+    log_slow_statement, metric::OperationKind, DbErr, RuntimeErr, SlowStatementLog, Statement,
+    StatementOptions,
+};
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
 /// Converts an [sqlx::error] execution error to a [DbErr]
 pub fn sqlx_error_to_exec_err(err: sqlx::Error) -> DbErr {
+    if is_statement_timeout(&err) {
+        return DbErr::StatementTimeout(RuntimeErr::SqlxError(err));
+    }
     DbErr::Exec(RuntimeErr::SqlxError(err))
 }
 
 /// Converts an [sqlx::error] query error to a [DbErr]
 pub fn sqlx_error_to_query_err(err: sqlx::Error) -> DbErr {
+    if is_statement_timeout(&err) {
+        return DbErr::StatementTimeout(RuntimeErr::SqlxError(err));
+    }
     DbErr::Query(RuntimeErr::SqlxError(err))
 }
 
@@ -14,3 +31,189 @@ pub fn sqlx_error_to_query_err(err: sqlx::Error) -> DbErr {
 pub fn sqlx_error_to_conn_err(err: sqlx::Error) -> DbErr {
     DbErr::Conn(RuntimeErr::SqlxError(err))
 }
+
+/// Check if the statement was cancelled because it exceeded the statement timeout
+fn is_statement_timeout(err: &sqlx::Error) -> bool {
+    let e = match err {
+        sqlx::Error::Database(e) => e,
+        _ => return false,
+    };
+    #[cfg(feature = "sqlx-mysql")]
+    if let Some(err) = e.try_downcast_ref::<sqlx::mysql::MySqlDatabaseError>() {
+        // 3024 Query execution was interrupted, maximum statement execution time exceeded
+        return err.number() == 3024;
+    }
+    #[cfg(feature = "sqlx-postgres")]
+    if let Some(err) = e.try_downcast_ref::<sqlx::postgres::PgDatabaseError>() {
+        // 57014 query_canceled
+        return err.code() == "57014";
+    }
+    #[cfg(feature = "sqlx-sqlite")]
+    if e.try_downcast_ref::<sqlx::sqlite::SqliteError>().is_some() {
+        // 9 SQLITE_INTERRUPT
+        return e.code().as_deref() == Some("9");
+    }
+    false
+}
+
+/// Reports a statement run on a sqlx connection to the metric callback and the slow statement log
+pub(crate) struct StatementContext<'a> {
+    pub(crate) metric_callback: &'a Option<crate::metric::Callback>,
+    pub(crate) slow_statement_log: &'a Option<SlowStatementLog>,
+    pub(crate) kind: OperationKind,
+    pub(crate) acquire_elapsed: Duration,
+    pub(crate) in_transaction: bool,
+}
+
+impl StatementContext<'_> {
+    /// Run the query of the statement, reporting it to the metric callback
+    pub(crate) async fn metric<T, F>(&self, stmt: &Statement, query: F) -> Result<T, DbErr>
+    where
+        T: crate::metric::Rows,
+        F: Future<Output = Result<T, DbErr>>,
+    {
+        crate::metric::metric!(
+            self.metric_callback,
+            stmt,
+            self.kind,
+            self.acquire_elapsed,
+            self.in_transaction,
+            { query.await }
+        )
+    }
+
+    /// Log the statement started at `start` if it was slow
+    pub(crate) async fn log_slow_statement<DB, T>(
+        &self,
+        conn: &mut sqlx::pool::PoolConnection<DB>,
+        stmt: &Statement,
+        start: Instant,
+        res: &Result<T, DbErr>,
+    ) where
+        DB: sqlx::Database,
+        for<'c> &'c mut DB::Connection: sqlx::Executor<'c, Database = DB>,
+        for<'q> sea_query_binder::SqlxValues: sqlx::IntoArguments<'q, DB>,
+        for<'r> String: sqlx::Decode<'r, DB> + sqlx::Type<DB>,
+        usize: sqlx::ColumnIndex<DB::Row>,
+    {
+        log_slow_statement(
+            conn,
+            self.slow_statement_log,
+            stmt,
+            start.elapsed(),
+            res.is_err(),
+            self.in_transaction,
+        )
+        .await;
+    }
+}
+
+/// A set of read replica pools, handed out in round-robin order
+pub(crate) struct ReplicaPools<DB>
+where
+    DB: sqlx::Database,
+{
+    pools: Vec<sqlx::Pool<DB>>,
+    next: Arc<AtomicUsize>,
+}
+
+impl<DB> Clone for ReplicaPools<DB>
+where
+    DB: sqlx::Database,
+{
+    fn clone(&self) -> Self {
+        Self {
+            pools: self.pools.clone(),
+            next: Arc::clone(&self.next),
+        }
+    }
+}
+
+impl<DB> Default for ReplicaPools<DB>
+where
+    DB: sqlx::Database,
+{
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl<DB> ReplicaPools<DB>
+where
+    DB: sqlx::Database,
+{
+    pub(crate) fn new(pools: Vec<sqlx::Pool<DB>>) -> Self {
+        Self {
+            pools,
+            next: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Select the pool to run a query on. Plain `SELECT` statements go to the next replica,
+    /// unless there is none, the statement locks rows or it must read from the primary.
+    /// Any other statement, which may write, runs on the primary.
+    pub(crate) fn read_pool<'a>(
+        &'a self,
+        primary: &'a sqlx::Pool<DB>,
+        stmt: &Statement,
+        options: StatementOptions,
+    ) -> &'a sqlx::Pool<DB> {
+        if self.pools.is_empty() || options.get_read_from_primary() || !is_read_query(&stmt.sql) {
+            return primary;
+        }
+        let i = self.next.fetch_add(1, Ordering::Relaxed) % self.pools.len();
+        &self.pools[i]
+    }
+
+    pub(crate) async fn close(self) {
+        for pool in self.pools {
+            pool.close().await;
+        }
+    }
+}
+
+/// Check if the statement is a `SELECT` which does not take row locks,
+/// i.e. it was not built with `QuerySelect::lock*`
+fn is_read_query(sql: &str) -> bool {
+    let sql = sql.trim_start().to_uppercase();
+    sql.starts_with("SELECT")
+        && ![
+            " FOR UPDATE",
+            " FOR NO KEY UPDATE",
+            " FOR SHARE",
+            " FOR KEY SHARE",
+            " LOCK IN SHARE MODE",
+        ]
+        .iter()
+        .any(|clause| sql.contains(clause))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_read_query;
+
+    #[test]
+    fn test_is_read_query() {
+        assert!(is_read_query(
+            r#"SELECT "cake"."id", "cake"."name" FROM "cake""#
+        ));
+        assert!(!is_read_query(
+            r#"SELECT "cake"."id" FROM "cake" FOR UPDATE"#
+        ));
+        assert!(!is_read_query(
+            "SELECT `cake`.`id` FROM `cake` LOCK IN SHARE MODE"
+        ));
+        assert!(!is_read_query(
+            r#"SELECT "cake"."id" FROM "cake" FOR NO KEY UPDATE"#
+        ));
+        assert!(!is_read_query(
+            r#"INSERT INTO "cake" ("name") VALUES ($1) RETURNING "id""#
+        ));
+        assert!(!is_read_query(
+            r#"DELETE FROM "cake" WHERE "cake"."id" = $1 RETURNING "id", "name""#
+        ));
+        assert!(!is_read_query(
+            r#"WITH "c" AS (DELETE FROM "cake" RETURNING "id") SELECT "id" FROM "c""#
+        ));
+    }
+}
@@ -1,18 +1,25 @@
+use futures::future::BoxFuture;
 use sea_query::Values;
-use std::{future::Future, pin::Pin, sync::Arc};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use sqlx::{
     mysql::{MySqlConnectOptions, MySqlQueryResult, MySqlRow},
     pool::PoolConnection,
-    Executor, MySql, MySqlPool,
+    Executor, MySql, MySqlConnection, MySqlPool,
 };
 
 use sea_query_binder::SqlxValues;
 use tracing::instrument;
 
 use crate::{
-    debug_print, error::*, executor::*, AccessMode, ConnectOptions, DatabaseConnection,
-    DatabaseTransaction, DbBackend, IsolationLevel, QueryStream, Statement, TransactionError,
+    debug_print, error::*, executor::*, metric::OperationKind, AccessMode, ConnectOptions,
+    DatabaseConnection, DatabaseTransaction, DbBackend, IsolationLevel, QueryStream,
+    SlowStatementLog, Statement, StatementOptions, TransactionError,
 };
 
 use super::sqlx_common::*;
@@ -25,7 +32,9 @@ pub struct SqlxMySqlConnector;
 #[derive(Clone)]
 pub struct SqlxMySqlPoolConnection {
     pub(crate) pool: MySqlPool,
+    replicas: ReplicaPools<MySql>,
     metric_callback: Option<crate::metric::Callback>,
+    slow_statement_log: Option<SlowStatementLog>,
 }
 
 impl std::fmt::Debug for SqlxMySqlPoolConnection {
@@ -43,8 +52,23 @@ impl SqlxMySqlConnector {
     /// Add configuration options for the MySQL database
     #[instrument(level = "trace")]
     pub async fn connect(options: ConnectOptions) -> Result<DatabaseConnection, DbErr> {
-        let mut opt = options
-            .url
+        let pool = Self::connect_pool(&options, &options.url).await?;
+        let mut replicas = Vec::with_capacity(options.read_replicas.len());
+        for url in options.read_replicas.iter() {
+            replicas.push(Self::connect_pool(&options, url).await?);
+        }
+        Ok(DatabaseConnection::SqlxMySqlPoolConnection(
+            SqlxMySqlPoolConnection {
+                pool,
+                replicas: ReplicaPools::new(replicas),
+                metric_callback: None,
+                slow_statement_log: SlowStatementLog::from_options(&options),
+            },
+        ))
+    }
+
+    async fn connect_pool(options: &ConnectOptions, url: &str) -> Result<MySqlPool, DbErr> {
+        let mut opt = url
             .parse::<MySqlConnectOptions>()
             .map_err(sqlx_error_to_conn_err)?;
         use sqlx::ConnectOptions;
@@ -53,15 +77,25 @@ impl SqlxMySqlConnector {
         } else {
             opt.log_statements(options.sqlx_logging_level);
         }
-        match options.pool_options().connect_with(opt).await {
-            Ok(pool) => Ok(DatabaseConnection::SqlxMySqlPoolConnection(
-                SqlxMySqlPoolConnection {
-                    pool,
-                    metric_callback: None,
-                },
-            )),
-            Err(e) => Err(sqlx_error_to_conn_err(e)),
+        let mut pool_options = options.clone().pool_options();
+        if let Some(timeout) = options.statement_timeout {
+            let sql = format!(
+                "SET SESSION max_execution_time = {}",
+                timeout.as_millis().max(1)
+            );
+            pool_options = pool_options.after_connect(move |conn, _| {
+                let sql = sql.clone();
+                Box::pin(async move {
+                    sqlx::Executor::execute(conn, sql.as_str())
+                        .await
+                        .map(|_| ())
+                })
+            });
         }
+        pool_options
+            .connect_with(opt)
+            .await
+            .map_err(sqlx_error_to_conn_err)
     }
 }
 
@@ -70,7 +104,9 @@ impl SqlxMySqlConnector {
     pub fn from_sqlx_mysql_pool(pool: MySqlPool) -> DatabaseConnection {
         DatabaseConnection::SqlxMySqlPoolConnection(SqlxMySqlPoolConnection {
             pool,
+            replicas: ReplicaPools::default(),
             metric_callback: None,
+            slow_statement_log: None,
         })
     }
 }
@@ -79,16 +115,23 @@ impl SqlxMySqlPoolConnection {
     /// Execute a [Statement] on a MySQL backend
     #[instrument(level = "trace")]
     pub async fn execute(&self, stmt: Statement) -> Result<ExecResult, DbErr> {
+        self.execute_with_options(stmt, StatementOptions::default())
+            .await
+    }
+
+    /// Execute a [Statement] with [StatementOptions] on a MySQL backend
+    #[instrument(level = "trace")]
+    pub async fn execute_with_options(
+        &self,
+        stmt: Statement,
+        options: StatementOptions,
+    ) -> Result<ExecResult, DbErr> {
         debug_print!("{}", stmt);
 
-        let query = sqlx_query(&stmt);
+        let acquire_start = std::time::Instant::now();
         if let Ok(conn) = &mut self.pool.acquire().await {
-            crate::metric::metric!(self.metric_callback, &stmt, {
-                match query.execute(conn).await {
-                    Ok(res) => Ok(res.into()),
-                    Err(err) => Err(sqlx_error_to_exec_err(err)),
-                }
-            })
+            let ctx = self.statement_context(OperationKind::Execute, acquire_start.elapsed());
+            sqlx_execute(conn, stmt, options.get_timeout(), ctx).await
         } else {
             Err(DbErr::ConnectionAcquire)
         }
@@ -112,19 +155,28 @@ impl SqlxMySqlPoolConnection {
     /// Get one result from a SQL query. Returns [Option::None] if no match was found
     #[instrument(level = "trace")]
     pub async fn query_one(&self, stmt: Statement) -> Result<Option<QueryResult>, DbErr> {
+        self.query_one_with_options(stmt, StatementOptions::default())
+            .await
+    }
+
+    /// Get one result from a SQL query with [StatementOptions]. Returns [Option::None] if no match was found
+    #[instrument(level = "trace")]
+    pub async fn query_one_with_options(
+        &self,
+        stmt: Statement,
+        options: StatementOptions,
+    ) -> Result<Option<QueryResult>, DbErr> {
         debug_print!("{}", stmt);
 
-        let query = sqlx_query(&stmt);
-        if let Ok(conn) = &mut self.pool.acquire().await {
-            crate::metric::metric!(self.metric_callback, &stmt, {
-                match query.fetch_one(conn).await {
-                    Ok(row) => Ok(Some(row.into())),
-                    Err(err) => match err {
-                        sqlx::Error::RowNotFound => Ok(None),
-                        _ => Err(sqlx_error_to_query_err(err)),
-                    },
-                }
-            })
+        let acquire_start = std::time::Instant::now();
+        if let Ok(conn) = &mut self
+            .replicas
+            .read_pool(&self.pool, &stmt, options)
+            .acquire()
+            .await
+        {
+            let ctx = self.statement_context(OperationKind::QueryOne, acquire_start.elapsed());
+            sqlx_query_one(conn, stmt, options.get_timeout(), ctx).await
         } else {
             Err(DbErr::ConnectionAcquire)
         }
@@ -133,16 +185,28 @@ impl SqlxMySqlPoolConnection {
     /// Get the results of a query returning them as a Vec<[QueryResult]>
     #[instrument(level = "trace")]
     pub async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr> {
+        self.query_all_with_options(stmt, StatementOptions::default())
+            .await
+    }
+
+    /// Get the results of a query with [StatementOptions] returning them as a Vec<[QueryResult]>
+    #[instrument(level = "trace")]
+    pub async fn query_all_with_options(
+        &self,
+        stmt: Statement,
+        options: StatementOptions,
+    ) -> Result<Vec<QueryResult>, DbErr> {
         debug_print!("{}", stmt);
 
-        let query = sqlx_query(&stmt);
-        if let Ok(conn) = &mut self.pool.acquire().await {
-            crate::metric::metric!(self.metric_callback, &stmt, {
-                match query.fetch_all(conn).await {
-                    Ok(rows) => Ok(rows.into_iter().map(|r| r.into()).collect()),
-                    Err(err) => Err(sqlx_error_to_query_err(err)),
-                }
-            })
+        let acquire_start = std::time::Instant::now();
+        if let Ok(conn) = &mut self
+            .replicas
+            .read_pool(&self.pool, &stmt, options)
+            .acquire()
+            .await
+        {
+            let ctx = self.statement_context(OperationKind::QueryAll, acquire_start.elapsed());
+            sqlx_query_all(conn, stmt, options.get_timeout(), ctx).await
         } else {
             Err(DbErr::ConnectionAcquire)
         }
@@ -153,12 +217,18 @@ impl SqlxMySqlPoolConnection {
     pub async fn stream(&self, stmt: Statement) -> Result<QueryStream, DbErr> {
         debug_print!("{}", stmt);
 
-        if let Ok(conn) = self.pool.acquire().await {
-            Ok(QueryStream::from((
-                conn,
-                stmt,
-                self.metric_callback.clone(),
-            )))
+        let acquire_start = std::time::Instant::now();
+        if let Ok(conn) = self
+            .replicas
+            .read_pool(&self.pool, &stmt, StatementOptions::default())
+            .acquire()
+            .await
+        {
+            let acquire_elapsed = acquire_start.elapsed();
+            Ok(
+                QueryStream::from((conn, stmt, self.metric_callback.clone()))
+                    .with_acquire_elapsed(acquire_elapsed),
+            )
         } else {
             Err(DbErr::ConnectionAcquire)
         }
@@ -175,6 +245,7 @@ impl SqlxMySqlPoolConnection {
             DatabaseTransaction::new_mysql(
                 conn,
                 self.metric_callback.clone(),
+                self.slow_statement_log,
                 isolation_level,
                 access_mode,
             )
@@ -204,6 +275,7 @@ impl SqlxMySqlPoolConnection {
             let transaction = DatabaseTransaction::new_mysql(
                 conn,
                 self.metric_callback.clone(),
+                self.slow_statement_log,
                 isolation_level,
                 access_mode,
             )
@@ -215,6 +287,20 @@ impl SqlxMySqlPoolConnection {
         }
     }
 
+    fn statement_context(
+        &self,
+        kind: OperationKind,
+        acquire_elapsed: Duration,
+    ) -> StatementContext<'_> {
+        StatementContext {
+            metric_callback: &self.metric_callback,
+            slow_statement_log: &self.slow_statement_log,
+            kind,
+            acquire_elapsed,
+            in_transaction: false,
+        }
+    }
+
     pub(crate) fn set_metric_callback<F>(&mut self, callback: F)
     where
         F: Fn(&crate::metric::Info<'_>) + Send + Sync + 'static,
@@ -225,6 +311,7 @@ impl SqlxMySqlPoolConnection {
     /// Explicitly close the MySQL connection
     pub async fn close(self) -> Result<(), DbErr> {
         self.pool.close().await;
+        self.replicas.close().await;
         Ok(())
     }
 }
@@ -259,22 +346,145 @@ pub(crate) async fn set_transaction_config(
     access_mode: Option<AccessMode>,
 ) -> Result<(), DbErr> {
     if let Some(isolation_level) = isolation_level {
-        let stmt = Statement {
-            sql: format!("SET TRANSACTION ISOLATION LEVEL {isolation_level}"),
-            values: None,
-            db_backend: DbBackend::MySql,
-        };
+        let stmt = Statement::from_string(
+            DbBackend::MySql,
+            format!("SET TRANSACTION ISOLATION LEVEL {isolation_level}"),
+        );
         let query = sqlx_query(&stmt);
         conn.execute(query).await.map_err(sqlx_error_to_exec_err)?;
     }
     if let Some(access_mode) = access_mode {
-        let stmt = Statement {
-            sql: format!("SET TRANSACTION {access_mode}"),
-            values: None,
-            db_backend: DbBackend::MySql,
-        };
+        let stmt =
+            Statement::from_string(DbBackend::MySql, format!("SET TRANSACTION {access_mode}"));
         let query = sqlx_query(&stmt);
         conn.execute(query).await.map_err(sqlx_error_to_exec_err)?;
     }
     Ok(())
 }
+
+/// Execute a statement on the connection, see [run_statement]
+pub(crate) async fn sqlx_execute(
+    conn: &mut PoolConnection<MySql>,
+    stmt: Statement,
+    timeout: Option<Duration>,
+    ctx: StatementContext<'_>,
+) -> Result<ExecResult, DbErr> {
+    run_statement(conn, stmt, timeout, ctx, |query, conn| {
+        Box::pin(async move {
+            match query.execute(conn).await {
+                Ok(res) => Ok(res.into()),
+                Err(err) => Err(sqlx_error_to_exec_err(err)),
+            }
+        })
+    })
+    .await
+}
+
+/// Get one result from a query on the connection, see [run_statement]
+pub(crate) async fn sqlx_query_one(
+    conn: &mut PoolConnection<MySql>,
+    stmt: Statement,
+    timeout: Option<Duration>,
+    ctx: StatementContext<'_>,
+) -> Result<Option<QueryResult>, DbErr> {
+    run_statement(conn, stmt, timeout, ctx, |query, conn| {
+        Box::pin(async move {
+            match query.fetch_one(conn).await {
+                Ok(row) => Ok(Some(row.into())),
+                Err(err) => match err {
+                    sqlx::Error::RowNotFound => Ok(None),
+                    _ => Err(sqlx_error_to_query_err(err)),
+                },
+            }
+        })
+    })
+    .await
+}
+
+/// Get the results of a query on the connection, see [run_statement]
+pub(crate) async fn sqlx_query_all(
+    conn: &mut PoolConnection<MySql>,
+    stmt: Statement,
+    timeout: Option<Duration>,
+    ctx: StatementContext<'_>,
+) -> Result<Vec<QueryResult>, DbErr> {
+    run_statement(conn, stmt, timeout, ctx, |query, conn| {
+        Box::pin(async move {
+            match query.fetch_all(conn).await {
+                Ok(rows) => Ok(rows.into_iter().map(|r| r.into()).collect()),
+                Err(err) => Err(sqlx_error_to_query_err(err)),
+            }
+        })
+    })
+    .await
+}
+
+/// Run the query of a statement with its timeout, if any, reporting it to the metric callback
+/// and the slow statement log. The timeout is an optimizer hint in the statement itself,
+/// see [with_max_execution_time].
+async fn run_statement<T, F>(
+    conn: &mut PoolConnection<MySql>,
+    stmt: Statement,
+    timeout: Option<Duration>,
+    ctx: StatementContext<'_>,
+    run: F,
+) -> Result<T, DbErr>
+where
+    T: crate::metric::Rows,
+    F: for<'c> FnOnce(
+        sqlx::query::Query<'c, MySql, SqlxValues>,
+        &'c mut MySqlConnection,
+    ) -> BoxFuture<'c, Result<T, DbErr>>,
+{
+    let stmt = with_max_execution_time(stmt, timeout);
+    let query = sqlx_query(&stmt);
+    let start = Instant::now();
+    let res = ctx.metric(&stmt, run(query, conn)).await;
+    ctx.log_slow_statement(conn, &stmt, start, &res).await;
+    res
+}
+
+/// Apply the timeout of the statement, if any, with the `MAX_EXECUTION_TIME` optimizer hint.
+/// MySQL only limits the execution time of `SELECT` statements, other statements are left unchanged.
+pub(crate) fn with_max_execution_time(mut stmt: Statement, timeout: Option<Duration>) -> Statement {
+    if let Some(timeout) = timeout {
+        let is_select = stmt
+            .sql
+            .get(..7)
+            .map_or(false, |keyword| keyword.eq_ignore_ascii_case("SELECT "));
+        if is_select {
+            stmt.sql = format!(
+                "SELECT /*+ MAX_EXECUTION_TIME({}) */ {}",
+                timeout.as_millis().max(1),
+                &stmt.sql[7..]
+            );
+        }
+    }
+    stmt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::with_max_execution_time;
+    use crate::{DbBackend, Statement};
+    use std::time::Duration;
+
+    #[test]
+    fn test_with_max_execution_time() {
+        let stmt = Statement::from_string(
+            DbBackend::MySql,
+            "SELECT `cake`.`id` FROM `cake`".to_owned(),
+        );
+        assert_eq!(with_max_execution_time(stmt.clone(), None), stmt);
+        assert_eq!(
+            with_max_execution_time(stmt, Some(Duration::from_secs(2))).sql,
+            "SELECT /*+ MAX_EXECUTION_TIME(2000) */ `cake`.`id` FROM `cake`"
+        );
+
+        let stmt = Statement::from_string(DbBackend::MySql, "DELETE FROM `cake`".to_owned());
+        assert_eq!(
+            with_max_execution_time(stmt.clone(), Some(Duration::from_secs(2))),
+            stmt
+        );
+    }
+}
@@ -1,18 +1,25 @@
+use futures::future::BoxFuture;
 use sea_query::Values;
-use std::{future::Future, pin::Pin, sync::Arc};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use sqlx::{
     pool::PoolConnection,
     postgres::{PgConnectOptions, PgQueryResult, PgRow},
-    Executor, PgPool, Postgres,
+    Executor, PgConnection, PgPool, Postgres,
 };
 
 use sea_query_binder::SqlxValues;
 use tracing::instrument;
 
 use crate::{
-    debug_print, error::*, executor::*, AccessMode, ConnectOptions, DatabaseConnection,
-    DatabaseTransaction, DbBackend, IsolationLevel, QueryStream, Statement, TransactionError,
+    debug_print, error::*, executor::*, metric::OperationKind, AccessMode, ConnectOptions,
+    DatabaseConnection, DatabaseTransaction, DbBackend, IsolationLevel, QueryStream,
+    SlowStatementLog, Statement, StatementOptions, TransactionError,
 };
 
 use super::sqlx_common::*;
@@ -25,7 +32,9 @@ pub struct SqlxPostgresConnector;
 #[derive(Clone)]
 pub struct SqlxPostgresPoolConnection {
     pub(crate) pool: PgPool,
+    replicas: ReplicaPools<Postgres>,
     metric_callback: Option<crate::metric::Callback>,
+    slow_statement_log: Option<SlowStatementLog>,
 }
 
 impl std::fmt::Debug for SqlxPostgresPoolConnection {
@@ -43,8 +52,23 @@ impl SqlxPostgresConnector {
     /// Add configuration options for the PostgreSQL database
     #[instrument(level = "trace")]
     pub async fn connect(options: ConnectOptions) -> Result<DatabaseConnection, DbErr> {
-        let mut opt = options
-            .url
+        let pool = Self::connect_pool(&options, &options.url).await?;
+        let mut replicas = Vec::with_capacity(options.read_replicas.len());
+        for url in options.read_replicas.iter() {
+            replicas.push(Self::connect_pool(&options, url).await?);
+        }
+        Ok(DatabaseConnection::SqlxPostgresPoolConnection(
+            SqlxPostgresPoolConnection {
+                pool,
+                replicas: ReplicaPools::new(replicas),
+                metric_callback: None,
+                slow_statement_log: SlowStatementLog::from_options(&options),
+            },
+        ))
+    }
+
+    async fn connect_pool(options: &ConnectOptions, url: &str) -> Result<PgPool, DbErr> {
+        let mut opt = url
             .parse::<PgConnectOptions>()
             .map_err(sqlx_error_to_conn_err)?;
         use sqlx::ConnectOptions;
@@ -53,11 +77,14 @@ impl SqlxPostgresConnector {
         } else {
             opt.log_statements(options.sqlx_logging_level);
         }
+        if let Some(timeout) = options.statement_timeout {
+            opt = opt.options([("statement_timeout", statement_timeout_millis(timeout))]);
+        }
         let set_search_path_sql = options
             .schema_search_path
             .as_ref()
             .map(|schema| format!("SET search_path = '{schema}'"));
-        let mut pool_options = options.pool_options();
+        let mut pool_options = options.clone().pool_options();
         if let Some(sql) = set_search_path_sql {
             pool_options = pool_options.after_connect(move |conn, _| {
                 let sql = sql.clone();
@@ -68,15 +95,10 @@ impl SqlxPostgresConnector {
                 })
             });
         }
-        match pool_options.connect_with(opt).await {
-            Ok(pool) => Ok(DatabaseConnection::SqlxPostgresPoolConnection(
-                SqlxPostgresPoolConnection {
-                    pool,
-                    metric_callback: None,
-                },
-            )),
-            Err(e) => Err(sqlx_error_to_conn_err(e)),
-        }
+        pool_options
+            .connect_with(opt)
+            .await
+            .map_err(sqlx_error_to_conn_err)
     }
 }
 
@@ -85,7 +107,9 @@ impl SqlxPostgresConnector {
     pub fn from_sqlx_postgres_pool(pool: PgPool) -> DatabaseConnection {
         DatabaseConnection::SqlxPostgresPoolConnection(SqlxPostgresPoolConnection {
             pool,
+            replicas: ReplicaPools::default(),
             metric_callback: None,
+            slow_statement_log: None,
         })
     }
 }
@@ -94,16 +118,23 @@ impl SqlxPostgresPoolConnection {
     /// Execute a [Statement] on a PostgreSQL backend
     #[instrument(level = "trace")]
     pub async fn execute(&self, stmt: Statement) -> Result<ExecResult, DbErr> {
+        self.execute_with_options(stmt, StatementOptions::default())
+            .await
+    }
+
+    /// Execute a [Statement] with [StatementOptions] on a PostgreSQL backend
+    #[instrument(level = "trace")]
+    pub async fn execute_with_options(
+        &self,
+        stmt: Statement,
+        options: StatementOptions,
+    ) -> Result<ExecResult, DbErr> {
         debug_print!("{}", stmt);
 
-        let query = sqlx_query(&stmt);
+        let acquire_start = std::time::Instant::now();
         if let Ok(conn) = &mut self.pool.acquire().await {
-            crate::metric::metric!(self.metric_callback, &stmt, {
-                match query.execute(conn).await {
-                    Ok(res) => Ok(res.into()),
-                    Err(err) => Err(sqlx_error_to_exec_err(err)),
-                }
-            })
+            let ctx = self.statement_context(OperationKind::Execute, acquire_start.elapsed());
+            sqlx_execute(conn, stmt, options.get_timeout(), ctx).await
         } else {
             Err(DbErr::ConnectionAcquire)
         }
@@ -127,19 +158,28 @@ impl SqlxPostgresPoolConnection {
     /// Get one result from a SQL query. Returns [Option::None] if no match was found
     #[instrument(level = "trace")]
     pub async fn query_one(&self, stmt: Statement) -> Result<Option<QueryResult>, DbErr> {
+        self.query_one_with_options(stmt, StatementOptions::default())
+            .await
+    }
+
+    /// Get one result from a SQL query with [StatementOptions]. Returns [Option::None] if no match was found
+    #[instrument(level = "trace")]
+    pub async fn query_one_with_options(
+        &self,
+        stmt: Statement,
+        options: StatementOptions,
+    ) -> Result<Option<QueryResult>, DbErr> {
         debug_print!("{}", stmt);
 
-        let query = sqlx_query(&stmt);
-        if let Ok(conn) = &mut self.pool.acquire().await {
-            crate::metric::metric!(self.metric_callback, &stmt, {
-                match query.fetch_one(conn).await {
-                    Ok(row) => Ok(Some(row.into())),
-                    Err(err) => match err {
-                        sqlx::Error::RowNotFound => Ok(None),
-                        _ => Err(sqlx_error_to_query_err(err)),
-                    },
-                }
-            })
+        let acquire_start = std::time::Instant::now();
+        if let Ok(conn) = &mut self
+            .replicas
+            .read_pool(&self.pool, &stmt, options)
+            .acquire()
+            .await
+        {
+            let ctx = self.statement_context(OperationKind::QueryOne, acquire_start.elapsed());
+            sqlx_query_one(conn, stmt, options.get_timeout(), ctx).await
         } else {
             Err(DbErr::ConnectionAcquire)
         }
@@ -148,16 +188,28 @@ impl SqlxPostgresPoolConnection {
     /// Get the results of a query returning them as a Vec<[QueryResult]>
     #[instrument(level = "trace")]
     pub async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr> {
+        self.query_all_with_options(stmt, StatementOptions::default())
+            .await
+    }
+
+    /// Get the results of a query with [StatementOptions] returning them as a Vec<[QueryResult]>
+    #[instrument(level = "trace")]
+    pub async fn query_all_with_options(
+        &self,
+        stmt: Statement,
+        options: StatementOptions,
+    ) -> Result<Vec<QueryResult>, DbErr> {
         debug_print!("{}", stmt);
 
-        let query = sqlx_query(&stmt);
-        if let Ok(conn) = &mut self.pool.acquire().await {
-            crate::metric::metric!(self.metric_callback, &stmt, {
-                match query.fetch_all(conn).await {
-                    Ok(rows) => Ok(rows.into_iter().map(|r| r.into()).collect()),
-                    Err(err) => Err(sqlx_error_to_query_err(err)),
-                }
-            })
+        let acquire_start = std::time::Instant::now();
+        if let Ok(conn) = &mut self
+            .replicas
+            .read_pool(&self.pool, &stmt, options)
+            .acquire()
+            .await
+        {
+            let ctx = self.statement_context(OperationKind::QueryAll, acquire_start.elapsed());
+            sqlx_query_all(conn, stmt, options.get_timeout(), ctx).await
         } else {
             Err(DbErr::ConnectionAcquire)
         }
@@ -168,12 +220,18 @@ impl SqlxPostgresPoolConnection {
     pub async fn stream(&self, stmt: Statement) -> Result<QueryStream, DbErr> {
         debug_print!("{}", stmt);
 
-        if let Ok(conn) = self.pool.acquire().await {
-            Ok(QueryStream::from((
-                conn,
-                stmt,
-                self.metric_callback.clone(),
-            )))
+        let acquire_start = std::time::Instant::now();
+        if let Ok(conn) = self
+            .replicas
+            .read_pool(&self.pool, &stmt, StatementOptions::default())
+            .acquire()
+            .await
+        {
+            let acquire_elapsed = acquire_start.elapsed();
+            Ok(
+                QueryStream::from((conn, stmt, self.metric_callback.clone()))
+                    .with_acquire_elapsed(acquire_elapsed),
+            )
         } else {
             Err(DbErr::ConnectionAcquire)
         }
@@ -190,6 +248,7 @@ impl SqlxPostgresPoolConnection {
             DatabaseTransaction::new_postgres(
                 conn,
                 self.metric_callback.clone(),
+                self.slow_statement_log,
                 isolation_level,
                 access_mode,
             )
@@ -219,6 +278,7 @@ impl SqlxPostgresPoolConnection {
             let transaction = DatabaseTransaction::new_postgres(
                 conn,
                 self.metric_callback.clone(),
+                self.slow_statement_log,
                 isolation_level,
                 access_mode,
             )
@@ -230,6 +290,20 @@ impl SqlxPostgresPoolConnection {
         }
     }
 
+    fn statement_context(
+        &self,
+        kind: OperationKind,
+        acquire_elapsed: Duration,
+    ) -> StatementContext<'_> {
+        StatementContext {
+            metric_callback: &self.metric_callback,
+            slow_statement_log: &self.slow_statement_log,
+            kind,
+            acquire_elapsed,
+            in_transaction: false,
+        }
+    }
+
     pub(crate) fn set_metric_callback<F>(&mut self, callback: F)
     where
         F: Fn(&crate::metric::Info<'_>) + Send + Sync + 'static,
@@ -240,6 +314,7 @@ impl SqlxPostgresPoolConnection {
     /// Explicitly close the Postgres connection
     pub async fn close(self) -> Result<(), DbErr> {
         self.pool.close().await;
+        self.replicas.close().await;
         Ok(())
     }
 }
@@ -274,22 +349,155 @@ pub(crate) async fn set_transaction_config(
     access_mode: Option<AccessMode>,
 ) -> Result<(), DbErr> {
     if let Some(isolation_level) = isolation_level {
-        let stmt = Statement {
-            sql: format!("SET TRANSACTION ISOLATION LEVEL {isolation_level}"),
-            values: None,
-            db_backend: DbBackend::Postgres,
-        };
+        let stmt = Statement::from_string(
+            DbBackend::Postgres,
+            format!("SET TRANSACTION ISOLATION LEVEL {isolation_level}"),
+        );
         let query = sqlx_query(&stmt);
         conn.execute(query).await.map_err(sqlx_error_to_exec_err)?;
     }
     if let Some(access_mode) = access_mode {
-        let stmt = Statement {
-            sql: format!("SET TRANSACTION {access_mode}"),
-            values: None,
-            db_backend: DbBackend::Postgres,
-        };
+        let stmt = Statement::from_string(
+            DbBackend::Postgres,
+            format!("SET TRANSACTION {access_mode}"),
+        );
         let query = sqlx_query(&stmt);
         conn.execute(query).await.map_err(sqlx_error_to_exec_err)?;
     }
     Ok(())
 }
+
+/// Execute a statement on the connection, see [run_statement]
+pub(crate) async fn sqlx_execute(
+    conn: &mut PoolConnection<Postgres>,
+    stmt: Statement,
+    timeout: Option<Duration>,
+    ctx: StatementContext<'_>,
+) -> Result<ExecResult, DbErr> {
+    run_statement(conn, stmt, timeout, ctx, |query, conn| {
+        Box::pin(async move {
+            match query.execute(conn).await {
+                Ok(res) => Ok(res.into()),
+                Err(err) => Err(sqlx_error_to_exec_err(err)),
+            }
+        })
+    })
+    .await
+}
+
+/// Get one result from a query on the connection, see [run_statement]
+pub(crate) async fn sqlx_query_one(
+    conn: &mut PoolConnection<Postgres>,
+    stmt: Statement,
+    timeout: Option<Duration>,
+    ctx: StatementContext<'_>,
+) -> Result<Option<QueryResult>, DbErr> {
+    run_statement(conn, stmt, timeout, ctx, |query, conn| {
+        Box::pin(async move {
+            match query.fetch_one(conn).await {
+                Ok(row) => Ok(Some(row.into())),
+                Err(err) => match err {
+                    sqlx::Error::RowNotFound => Ok(None),
+                    _ => Err(sqlx_error_to_query_err(err)),
+                },
+            }
+        })
+    })
+    .await
+}
+
+/// Get the results of a query on the connection, see [run_statement]
+pub(crate) async fn sqlx_query_all(
+    conn: &mut PoolConnection<Postgres>,
+    stmt: Statement,
+    timeout: Option<Duration>,
+    ctx: StatementContext<'_>,
+) -> Result<Vec<QueryResult>, DbErr> {
+    run_statement(conn, stmt, timeout, ctx, |query, conn| {
+        Box::pin(async move {
+            match query.fetch_all(conn).await {
+                Ok(rows) => Ok(rows.into_iter().map(|r| r.into()).collect()),
+                Err(err) => Err(sqlx_error_to_query_err(err)),
+            }
+        })
+    })
+    .await
+}
+
+/// Run the query of a statement with its timeout, if any, reporting it to the metric callback
+/// and the slow statement log.
+///
+/// The timeout is set with `SET LOCAL statement_timeout`, which ends with the transaction,
+/// so that it cannot outlive the statement if the statement is cancelled. Outside of a transaction,
+/// the statement runs in a transaction of its own. Inside a transaction, the connection's default
+/// is restored once the statement returns.
+async fn run_statement<T, F>(
+    conn: &mut PoolConnection<Postgres>,
+    stmt: Statement,
+    timeout: Option<Duration>,
+    ctx: StatementContext<'_>,
+    run: F,
+) -> Result<T, DbErr>
+where
+    T: crate::metric::Rows,
+    F: for<'c> FnOnce(
+        sqlx::query::Query<'c, Postgres, SqlxValues>,
+        &'c mut PgConnection,
+    ) -> BoxFuture<'c, Result<T, DbErr>>,
+{
+    let query = sqlx_query(&stmt);
+    let start = Instant::now();
+    let res = match timeout {
+        None => ctx.metric(&stmt, run(query, conn)).await,
+        Some(timeout) if ctx.in_transaction => {
+            set_local_statement_timeout(conn, Some(timeout)).await?;
+            let res = ctx.metric(&stmt, run(query, conn)).await;
+            // An error of the statement takes precedence, as it aborts the transaction
+            let reset = set_local_statement_timeout(conn, None).await;
+            res.and_then(|res| reset.map(|_| res))
+        }
+        Some(timeout) => {
+            // The transaction is rolled back if dropped, when the statement is cancelled
+            let mut txn = sqlx::Connection::begin(&mut **conn)
+                .await
+                .map_err(sqlx_error_to_exec_err)?;
+            set_local_statement_timeout(&mut txn, Some(timeout)).await?;
+            match ctx.metric(&stmt, run(query, &mut txn)).await {
+                Ok(res) => txn
+                    .commit()
+                    .await
+                    .map(|_| res)
+                    .map_err(sqlx_error_to_exec_err),
+                Err(err) => {
+                    txn.rollback().await.ok();
+                    Err(err)
+                }
+            }
+        }
+    };
+    ctx.log_slow_statement(conn, &stmt, start, &res).await;
+    res
+}
+
+/// Set the `statement_timeout` of the current transaction, or restore the connection's default
+async fn set_local_statement_timeout(
+    conn: &mut PgConnection,
+    timeout: Option<Duration>,
+) -> Result<(), DbErr> {
+    let sql = match timeout {
+        Some(timeout) => format!(
+            "SET LOCAL statement_timeout = {}",
+            statement_timeout_millis(timeout)
+        ),
+        None => "SET LOCAL statement_timeout TO DEFAULT".to_owned(),
+    };
+    conn.execute(sql.as_str())
+        .await
+        .map_err(sqlx_error_to_exec_err)?;
+    Ok(())
+}
+
+/// A timeout of 0 disables `statement_timeout`, so round up to at least a millisecond
+fn statement_timeout_millis(timeout: Duration) -> u128 {
+    timeout.as_millis().max(1)
+}
@@ -1,18 +1,26 @@
+use futures::future::BoxFuture;
 use sea_query::Values;
-use std::{future::Future, pin::Pin, sync::Arc};
+use std::{
+    future::Future,
+    os::raw::{c_int, c_void},
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
 use sqlx::{
     pool::PoolConnection,
     sqlite::{SqliteConnectOptions, SqliteQueryResult, SqliteRow},
-    Executor, Sqlite, SqlitePool,
+    Executor, Sqlite, SqliteConnection, SqlitePool,
 };
 
 use sea_query_binder::SqlxValues;
 use tracing::{instrument, warn};
 
 use crate::{
-    debug_print, error::*, executor::*, AccessMode, ConnectOptions, DatabaseConnection,
-    DatabaseTransaction, IsolationLevel, QueryStream, Statement, TransactionError,
+    debug_print, error::*, executor::*, metric::OperationKind, AccessMode, ConnectOptions,
+    DatabaseConnection, DatabaseTransaction, IsolationLevel, QueryStream, SlowStatementLog,
+    Statement, StatementOptions, TransactionError,
 };
 
 use super::sqlx_common::*;
@@ -25,7 +33,10 @@ pub struct SqlxSqliteConnector;
 #[derive(Clone)]
 pub struct SqlxSqlitePoolConnection {
     pub(crate) pool: SqlitePool,
+    replicas: ReplicaPools<Sqlite>,
     metric_callback: Option<crate::metric::Callback>,
+    slow_statement_log: Option<SlowStatementLog>,
+    statement_timeout: Option<Duration>,
 }
 
 impl std::fmt::Debug for SqlxSqlitePoolConnection {
@@ -44,8 +55,27 @@ impl SqlxSqliteConnector {
     #[instrument(level = "trace")]
     pub async fn connect(options: ConnectOptions) -> Result<DatabaseConnection, DbErr> {
         let mut options = options;
-        let mut opt = options
-            .url
+        if options.get_max_connections().is_none() {
+            options.max_connections(1);
+        }
+        let pool = Self::connect_pool(&options, &options.url).await?;
+        let mut replicas = Vec::with_capacity(options.read_replicas.len());
+        for url in options.read_replicas.iter() {
+            replicas.push(Self::connect_pool(&options, url).await?);
+        }
+        Ok(DatabaseConnection::SqlxSqlitePoolConnection(
+            SqlxSqlitePoolConnection {
+                pool,
+                replicas: ReplicaPools::new(replicas),
+                metric_callback: None,
+                slow_statement_log: SlowStatementLog::from_options(&options),
+                statement_timeout: options.statement_timeout,
+            },
+        ))
+    }
+
+    async fn connect_pool(options: &ConnectOptions, url: &str) -> Result<SqlitePool, DbErr> {
+        let mut opt = url
             .parse::<SqliteConnectOptions>()
             .map_err(sqlx_error_to_conn_err)?;
         if let Some(sqlcipher_key) = &options.sqlcipher_key {
@@ -57,18 +87,12 @@ impl SqlxSqliteConnector {
         } else {
             opt.log_statements(options.sqlx_logging_level);
         }
-        if options.get_max_connections().is_none() {
-            options.max_connections(1);
-        }
-        match options.pool_options().connect_with(opt).await {
-            Ok(pool) => Ok(DatabaseConnection::SqlxSqlitePoolConnection(
-                SqlxSqlitePoolConnection {
-                    pool,
-                    metric_callback: None,
-                },
-            )),
-            Err(e) => Err(sqlx_error_to_conn_err(e)),
-        }
+        options
+            .clone()
+            .pool_options()
+            .connect_with(opt)
+            .await
+            .map_err(sqlx_error_to_conn_err)
     }
 }
 
@@ -77,7 +101,10 @@ impl SqlxSqliteConnector {
     pub fn from_sqlx_sqlite_pool(pool: SqlitePool) -> DatabaseConnection {
         DatabaseConnection::SqlxSqlitePoolConnection(SqlxSqlitePoolConnection {
             pool,
+            replicas: ReplicaPools::default(),
             metric_callback: None,
+            slow_statement_log: None,
+            statement_timeout: None,
         })
     }
 }
@@ -86,16 +113,29 @@ impl SqlxSqlitePoolConnection {
     /// Execute a [Statement] on a SQLite backend
     #[instrument(level = "trace")]
     pub async fn execute(&self, stmt: Statement) -> Result<ExecResult, DbErr> {
+        self.execute_with_options(stmt, StatementOptions::default())
+            .await
+    }
+
+    /// Execute a [Statement] with [StatementOptions] on a SQLite backend
+    #[instrument(level = "trace")]
+    pub async fn execute_with_options(
+        &self,
+        stmt: Statement,
+        options: StatementOptions,
+    ) -> Result<ExecResult, DbErr> {
         debug_print!("{}", stmt);
 
-        let query = sqlx_query(&stmt);
+        let acquire_start = std::time::Instant::now();
         if let Ok(conn) = &mut self.pool.acquire().await {
-            crate::metric::metric!(self.metric_callback, &stmt, {
-                match query.execute(conn).await {
-                    Ok(res) => Ok(res.into()),
-                    Err(err) => Err(sqlx_error_to_exec_err(err)),
-                }
-            })
+            let ctx = self.statement_context(OperationKind::Execute, acquire_start.elapsed());
+            sqlx_execute(
+                conn,
+                stmt,
+                options.get_timeout().or(self.statement_timeout),
+                ctx,
+            )
+            .await
         } else {
             Err(DbErr::ConnectionAcquire)
         }
@@ -119,19 +159,34 @@ impl SqlxSqlitePoolConnection {
     /// Get one result from a SQL query. Returns [Option::None] if no match was found
     #[instrument(level = "trace")]
     pub async fn query_one(&self, stmt: Statement) -> Result<Option<QueryResult>, DbErr> {
+        self.query_one_with_options(stmt, StatementOptions::default())
+            .await
+    }
+
+    /// Get one result from a SQL query with [StatementOptions]. Returns [Option::None] if no match was found
+    #[instrument(level = "trace")]
+    pub async fn query_one_with_options(
+        &self,
+        stmt: Statement,
+        options: StatementOptions,
+    ) -> Result<Option<QueryResult>, DbErr> {
         debug_print!("{}", stmt);
 
-        let query = sqlx_query(&stmt);
-        if let Ok(conn) = &mut self.pool.acquire().await {
-            crate::metric::metric!(self.metric_callback, &stmt, {
-                match query.fetch_one(conn).await {
-                    Ok(row) => Ok(Some(row.into())),
-                    Err(err) => match err {
-                        sqlx::Error::RowNotFound => Ok(None),
-                        _ => Err(sqlx_error_to_query_err(err)),
-                    },
-                }
-            })
+        let acquire_start = std::time::Instant::now();
+        if let Ok(conn) = &mut self
+            .replicas
+            .read_pool(&self.pool, &stmt, options)
+            .acquire()
+            .await
+        {
+            let ctx = self.statement_context(OperationKind::QueryOne, acquire_start.elapsed());
+            sqlx_query_one(
+                conn,
+                stmt,
+                options.get_timeout().or(self.statement_timeout),
+                ctx,
+            )
+            .await
         } else {
             Err(DbErr::ConnectionAcquire)
         }
@@ -140,16 +195,34 @@ impl SqlxSqlitePoolConnection {
     /// Get the results of a query returning them as a Vec<[QueryResult]>
     #[instrument(level = "trace")]
     pub async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr> {
+        self.query_all_with_options(stmt, StatementOptions::default())
+            .await
+    }
+
+    /// Get the results of a query with [StatementOptions] returning them as a Vec<[QueryResult]>
+    #[instrument(level = "trace")]
+    pub async fn query_all_with_options(
+        &self,
+        stmt: Statement,
+        options: StatementOptions,
+    ) -> Result<Vec<QueryResult>, DbErr> {
         debug_print!("{}", stmt);
 
-        let query = sqlx_query(&stmt);
-        if let Ok(conn) = &mut self.pool.acquire().await {
-            crate::metric::metric!(self.metric_callback, &stmt, {
-                match query.fetch_all(conn).await {
-                    Ok(rows) => Ok(rows.into_iter().map(|r| r.into()).collect()),
-                    Err(err) => Err(sqlx_error_to_query_err(err)),
-                }
-            })
+        let acquire_start = std::time::Instant::now();
+        if let Ok(conn) = &mut self
+            .replicas
+            .read_pool(&self.pool, &stmt, options)
+            .acquire()
+            .await
+        {
+            let ctx = self.statement_context(OperationKind::QueryAll, acquire_start.elapsed());
+            sqlx_query_all(
+                conn,
+                stmt,
+                options.get_timeout().or(self.statement_timeout),
+                ctx,
+            )
+            .await
         } else {
             Err(DbErr::ConnectionAcquire)
         }
@@ -160,12 +233,18 @@ impl SqlxSqlitePoolConnection {
     pub async fn stream(&self, stmt: Statement) -> Result<QueryStream, DbErr> {
         debug_print!("{}", stmt);
 
-        if let Ok(conn) = self.pool.acquire().await {
-            Ok(QueryStream::from((
-                conn,
-                stmt,
-                self.metric_callback.clone(),
-            )))
+        let acquire_start = std::time::Instant::now();
+        if let Ok(conn) = self
+            .replicas
+            .read_pool(&self.pool, &stmt, StatementOptions::default())
+            .acquire()
+            .await
+        {
+            let acquire_elapsed = acquire_start.elapsed();
+            Ok(
+                QueryStream::from((conn, stmt, self.metric_callback.clone()))
+                    .with_acquire_elapsed(acquire_elapsed),
+            )
         } else {
             Err(DbErr::ConnectionAcquire)
         }
@@ -182,6 +261,8 @@ impl SqlxSqlitePoolConnection {
             DatabaseTransaction::new_sqlite(
                 conn,
                 self.metric_callback.clone(),
+                self.slow_statement_log,
+                self.statement_timeout,
                 isolation_level,
                 access_mode,
             )
@@ -211,6 +292,8 @@ impl SqlxSqlitePoolConnection {
             let transaction = DatabaseTransaction::new_sqlite(
                 conn,
                 self.metric_callback.clone(),
+                self.slow_statement_log,
+                self.statement_timeout,
                 isolation_level,
                 access_mode,
             )
@@ -222,6 +305,20 @@ impl SqlxSqlitePoolConnection {
         }
     }
 
+    fn statement_context(
+        &self,
+        kind: OperationKind,
+        acquire_elapsed: Duration,
+    ) -> StatementContext<'_> {
+        StatementContext {
+            metric_callback: &self.metric_callback,
+            slow_statement_log: &self.slow_statement_log,
+            kind,
+            acquire_elapsed,
+            in_transaction: false,
+        }
+    }
+
     pub(crate) fn set_metric_callback<F>(&mut self, callback: F)
     where
         F: Fn(&crate::metric::Info<'_>) + Send + Sync + 'static,
@@ -232,6 +329,7 @@ impl SqlxSqlitePoolConnection {
     /// Explicitly close the SQLite connection
     pub async fn close(self) -> Result<(), DbErr> {
         self.pool.close().await;
+        self.replicas.close().await;
         Ok(())
     }
 }
@@ -273,3 +371,220 @@ pub(crate) async fn set_transaction_config(
     }
     Ok(())
 }
+
+/// Execute a statement on the connection, see [run_statement]
+pub(crate) async fn sqlx_execute(
+    conn: &mut PoolConnection<Sqlite>,
+    stmt: Statement,
+    timeout: Option<Duration>,
+    ctx: StatementContext<'_>,
+) -> Result<ExecResult, DbErr> {
+    run_statement(conn, stmt, timeout, ctx, |query, conn| {
+        Box::pin(async move {
+            match query.execute(conn).await {
+                Ok(res) => Ok(res.into()),
+                Err(err) => Err(sqlx_error_to_exec_err(err)),
+            }
+        })
+    })
+    .await
+}
+
+/// Get one result from a query on the connection, see [run_statement]
+pub(crate) async fn sqlx_query_one(
+    conn: &mut PoolConnection<Sqlite>,
+    stmt: Statement,
+    timeout: Option<Duration>,
+    ctx: StatementContext<'_>,
+) -> Result<Option<QueryResult>, DbErr> {
+    run_statement(conn, stmt, timeout, ctx, |query, conn| {
+        Box::pin(async move {
+            match query.fetch_one(conn).await {
+                Ok(row) => Ok(Some(row.into())),
+                Err(err) => match err {
+                    sqlx::Error::RowNotFound => Ok(None),
+                    _ => Err(sqlx_error_to_query_err(err)),
+                },
+            }
+        })
+    })
+    .await
+}
+
+/// Get the results of a query on the connection, see [run_statement]
+pub(crate) async fn sqlx_query_all(
+    conn: &mut PoolConnection<Sqlite>,
+    stmt: Statement,
+    timeout: Option<Duration>,
+    ctx: StatementContext<'_>,
+) -> Result<Vec<QueryResult>, DbErr> {
+    run_statement(conn, stmt, timeout, ctx, |query, conn| {
+        Box::pin(async move {
+            match query.fetch_all(conn).await {
+                Ok(rows) => Ok(rows.into_iter().map(|r| r.into()).collect()),
+                Err(err) => Err(sqlx_error_to_query_err(err)),
+            }
+        })
+    })
+    .await
+}
+
+/// Run the query of a statement with its timeout, if any, reporting it to the metric callback
+/// and the slow statement log. The statement is interrupted once the timeout elapses,
+/// see [StatementInterrupt].
+async fn run_statement<T, F>(
+    conn: &mut PoolConnection<Sqlite>,
+    stmt: Statement,
+    timeout: Option<Duration>,
+    ctx: StatementContext<'_>,
+    run: F,
+) -> Result<T, DbErr>
+where
+    T: crate::metric::Rows,
+    F: for<'c> FnOnce(
+        sqlx::query::Query<'c, Sqlite, SqlxValues>,
+        &'c mut SqliteConnection,
+    ) -> BoxFuture<'c, Result<T, DbErr>>,
+{
+    let query = sqlx_query(&stmt);
+    let interrupt = StatementInterrupt::start(conn, timeout).await?;
+    let start = Instant::now();
+    let res = ctx.metric(&stmt, run(query, conn)).await;
+    interrupt.finish(conn).await;
+    ctx.log_slow_statement(conn, &stmt, start, &res).await;
+    res
+}
+
+extern "C" {
+    // Linked by sqlx, the connection handle is obtained through its `LockedSqliteHandle`
+    fn sqlite3_progress_handler(
+        db: *mut c_void,
+        instructions: c_int,
+        handler: Option<extern "C" fn(*mut c_void) -> c_int>,
+        arg: *mut c_void,
+    );
+}
+
+/// Interrupts the statement running on a SQLite connection once its timeout elapses,
+/// with a progress handler that SQLite calls periodically while the statement runs
+struct StatementInterrupt {
+    deadline: Option<Arc<Deadline>>,
+}
+
+/// The deadline of the statement running on a connection, read by the progress handler
+#[derive(Default)]
+struct Deadline {
+    at: Mutex<Option<Instant>>,
+}
+
+/// The deadlines of the connections having a progress handler, by the address of their handle.
+/// A deadline is kept while its handler is registered, and reused by the next statements
+/// on the connection until the handler is removed.
+static DEADLINES: Mutex<Vec<(usize, Arc<Deadline>)>> = Mutex::new(Vec::new());
+
+/// The number of virtual machine instructions between calls of the progress handler
+const PROGRESS_HANDLER_INSTRUCTIONS: c_int = 1000;
+
+/// The progress handler, returning non-zero to interrupt the statement once the deadline has passed
+extern "C" fn interrupt_after_deadline(deadline: *mut c_void) -> c_int {
+    // SAFETY: the handler is registered with a `Deadline` kept in `DEADLINES` until it is removed
+    let deadline = unsafe { &*(deadline as *const Deadline) };
+    let at = *deadline.at.lock().unwrap_or_else(|err| err.into_inner());
+    at.map_or(false, |at| Instant::now() >= at) as c_int
+}
+
+/// The address of the connection's handle, identifying its deadline
+fn handle_address(conn: &mut PoolConnection<Sqlite>) -> usize {
+    // Only the address is read, the handle is not used without locking it
+    #[allow(deprecated)]
+    let handle = conn.as_raw_handle();
+    handle as usize
+}
+
+impl StatementInterrupt {
+    /// Register the deadline of a statement about to run on the connection, if there is a timeout.
+    /// Without a timeout, the handler left by a cancelled statement is removed.
+    async fn start(
+        conn: &mut PoolConnection<Sqlite>,
+        timeout: Option<Duration>,
+    ) -> Result<Self, DbErr> {
+        let address = handle_address(conn);
+        let registered = DEADLINES
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .iter()
+            .find(|(a, _)| *a == address)
+            .map(|(_, deadline)| Arc::clone(deadline));
+        let at = match timeout.and_then(|timeout| Instant::now().checked_add(timeout)) {
+            Some(at) => at,
+            None => {
+                if registered.is_some() {
+                    Self {
+                        deadline: registered,
+                    }
+                    .finish(conn)
+                    .await;
+                }
+                return Ok(Self { deadline: None });
+            }
+        };
+        let deadline = registered.unwrap_or_else(|| {
+            let deadline = Arc::new(Deadline::default());
+            DEADLINES
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .push((address, Arc::clone(&deadline)));
+            deadline
+        });
+        let mut handle = conn.lock_handle().await.map_err(sqlx_error_to_conn_err)?;
+        *deadline.at.lock().unwrap_or_else(|err| err.into_inner()) = Some(at);
+        // SAFETY: the worker is not using the locked handle
+        unsafe {
+            sqlite3_progress_handler(
+                handle.as_raw_handle().cast().as_ptr(),
+                PROGRESS_HANDLER_INSTRUCTIONS,
+                Some(interrupt_after_deadline),
+                Arc::as_ptr(&deadline) as *mut c_void,
+            )
+        };
+        Ok(Self {
+            deadline: Some(deadline),
+        })
+    }
+
+    /// Remove the progress handler and its deadline once the statement has returned,
+    /// waiting for the connection worker to be idle
+    async fn finish(mut self, conn: &mut PoolConnection<Sqlite>) {
+        if self.deadline.is_none() {
+            return;
+        }
+        let address = handle_address(conn);
+        if let Ok(mut handle) = conn.lock_handle().await {
+            // SAFETY: the worker is not using the locked handle
+            unsafe {
+                sqlite3_progress_handler(
+                    handle.as_raw_handle().cast().as_ptr(),
+                    0,
+                    None,
+                    std::ptr::null_mut(),
+                )
+            };
+            DEADLINES
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .retain(|(a, _)| *a != address);
+            self.deadline = None;
+        }
+    }
+}
+
+impl Drop for StatementInterrupt {
+    fn drop(&mut self) {
+        // The statement was cancelled before the handler could be removed. Clear the deadline,
+        // so that it cannot interrupt later statements on the connection. The handler and its
+        // deadline are removed, or reused, by the next statement on the connection.
+        if let Some(deadline) = self.deadline.take() {
+            *deadline.at.lock().unwrap_or_else(|err| err.into_inner()) = None;
+        }
+    }
+}
@@ -302,6 +302,69 @@ pub trait ActiveModelTrait: Clone + Debug {
         Self::after_save(model, db, true).await
     }
 
+    /// Perform an upsert operation on an ActiveModel: insert it, or update the existing row
+    /// having the same primary key with the other columns given a value, then return the row.
+    /// It uses `ON CONFLICT .. DO UPDATE`, or `ON DUPLICATE KEY UPDATE` on MySQL.
+    ///
+    /// # Example (Postgres)
+    ///
+    /// ```
+    /// # use sea_orm::{error::*, tests_cfg::*, *};
+    /// #
+    /// # #[smol_potat::main]
+    /// # #[cfg(feature = "mock")]
+    /// # pub async fn main() -> Result<(), DbErr> {
+    /// #
+    /// # let db = MockDatabase::new(DbBackend::Postgres)
+    /// #     .append_query_results([
+    /// #         [cake::Model {
+    /// #             id: 1,
+    /// #             name: "Apple Pie".to_owned(),
+    /// #         }],
+    /// #     ])
+    /// #     .into_connection();
+    /// #
+    /// use sea_orm::{entity::*, query::*, tests_cfg::cake};
+    ///
+    /// let apple = cake::ActiveModel {
+    ///     id: Set(1),
+    ///     name: Set("Apple Pie".to_owned()),
+    /// };
+    ///
+    /// assert_eq!(
+    ///     apple.upsert(&db).await?,
+    ///     cake::Model {
+    ///         id: 1,
+    ///         name: "Apple Pie".to_owned(),
+    ///     }
+    /// );
+    ///
+    /// assert_eq!(
+    ///     db.into_transaction_log(),
+    ///     [Transaction::from_sql_and_values(
+    ///         DbBackend::Postgres,
+    ///         r#"INSERT INTO "cake" ("id", "name") VALUES ($1, $2) ON CONFLICT ("id") DO UPDATE SET "name" = "excluded"."name" RETURNING "id", "name""#,
+    ///         [1.into(), "Apple Pie".into()]
+    ///     )]
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn upsert<'a, C>(self, db: &'a C) -> Result<<Self::Entity as EntityTrait>::Model, DbErr>
+    where
+        <Self::Entity as EntityTrait>::Model: IntoActiveModel<Self>,
+        Self: ActiveModelBehavior + 'a,
+        C: ConnectionTrait,
+    {
+        let am = ActiveModelBehavior::before_save(self, db, true).await?;
+        let model = <Self::Entity as EntityTrait>::insert(am)
+            .on_conflict_primary_key()
+            .exec_with_returning(db)
+            .await?;
+        Self::after_save(model, db, true).await
+    }
+
     /// Perform the `UPDATE` operation on an ActiveModel
     ///
     /// # Example (Postgres)
@@ -1,5 +1,5 @@
 use crate::{
-    ActiveModelTrait, ColumnTrait, Delete, DeleteMany, DeleteOne, FromQueryResult, Insert,
+    ActiveModelTrait, ColumnTrait, DbErr, Delete, DeleteMany, DeleteOne, FromQueryResult, Insert,
     ModelTrait, PrimaryKeyToColumn, PrimaryKeyTrait, QueryFilter, Related, RelationBuilder,
     RelationTrait, RelationType, Select, Update, UpdateMany, UpdateOne,
 };
@@ -644,6 +644,80 @@ pub trait EntityTrait: EntityName {
         Update::many(Self::default())
     }
 
+    /// Update many models in database with different values in one statement,
+    /// only updating the columns that are `Set`.
+    /// Returns [DbErr::UpdateGetPrimaryKey] if the primary key of a model is not set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use sea_orm::{error::*, tests_cfg::*, *};
+    /// #
+    /// # #[smol_potat::main]
+    /// # #[cfg(feature = "mock")]
+    /// # pub async fn main() -> Result<(), DbErr> {
+    /// #
+    /// # let db = MockDatabase::new(DbBackend::Postgres)
+    /// #     .append_exec_results([
+    /// #         MockExecResult {
+    /// #             last_insert_id: 0,
+    /// #             rows_affected: 2,
+    /// #         },
+    /// #     ])
+    /// #     .into_connection();
+    /// #
+    /// use sea_orm::{entity::*, query::*, tests_cfg::fruit};
+    ///
+    /// let update_result = fruit::Entity::update_batch([
+    ///     fruit::ActiveModel {
+    ///         id: Set(1),
+    ///         cake_id: Set(Some(2)),
+    ///         ..Default::default()
+    ///     },
+    ///     fruit::ActiveModel {
+    ///         id: Set(2),
+    ///         cake_id: Set(None),
+    ///         ..Default::default()
+    ///     },
+    /// ])?
+    /// .exec(&db)
+    /// .await?;
+    ///
+    /// assert_eq!(update_result.rows_affected, 2);
+    ///
+    /// assert_eq!(
+    ///     db.into_transaction_log(),
+    ///     [Transaction::from_sql_and_values(
+    ///         DbBackend::Postgres,
+    ///         [
+    ///             r#"UPDATE "fruit" SET "cake_id" ="#,
+    ///             r#"(CASE WHEN ("fruit"."id" = $1) THEN $2 WHEN ("fruit"."id" = $3) THEN $4 ELSE "cake_id" END)"#,
+    ///             r#"WHERE "fruit"."id" IN ($5, $6)"#,
+    ///         ]
+    ///         .join(" ")
+    ///         .as_str(),
+    ///         [
+    ///             1i32.into(),
+    ///             2i32.into(),
+    ///             2i32.into(),
+    ///             Option::<i32>::None.into(),
+    ///             1i32.into(),
+    ///             2i32.into(),
+    ///         ]
+    ///     )]
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn update_batch<A, I>(models: I) -> Result<UpdateMany<Self>, DbErr>
+    where
+        A: ActiveModelTrait<Entity = Self>,
+        I: IntoIterator<Item = A>,
+    {
+        Update::batch(models)
+    }
+
     /// Delete an model from database
     ///
     /// - To apply where conditions / filters, see [`QueryFilter`](crate::query::QueryFilter)
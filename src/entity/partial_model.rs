@@ -0,0 +1,8 @@
+use crate::{FromQueryResult, QuerySelect};
+
+/// A subset of the columns of a [Model](crate::ModelTrait), or expressions derived from them,
+/// to be selected by [Select::into_partial_model](crate::Select::into_partial_model)
+pub trait PartialModelTrait: FromQueryResult {
+    /// Add the columns needed by this partial model to the select statement
+    fn select_cols<S: QuerySelect>(select: S) -> S;
+}
@@ -1,16 +1,17 @@
 pub use crate::{
-    error::*, sea_query::BlobSize, ActiveEnum, ActiveModelBehavior, ActiveModelTrait, ColumnDef,
-    ColumnTrait, ColumnType, ColumnTypeTrait, ConnectionTrait, CursorTrait, DatabaseConnection,
-    DbConn, EntityName, EntityTrait, EnumIter, ForeignKeyAction, Iden, IdenStatic, Linked,
-    LoaderTrait, ModelTrait, PaginatorTrait, PrimaryKeyToColumn, PrimaryKeyTrait, QueryFilter,
-    QueryResult, Related, RelationDef, RelationTrait, Select, Value,
+    error::*, sea_query::BlobSize, ActiveEnum, ActiveModelBehavior, ActiveModelTrait,
+    AggregatorTrait, ColumnDef, ColumnTrait, ColumnType, ColumnTypeTrait, ConnectionTrait,
+    CursorTrait, DatabaseConnection, DbConn, EntityName, EntityTrait, EnumIter, ForeignKeyAction,
+    Iden, IdenStatic, Linked, LoaderTrait, ModelTrait, PaginatorTrait, PartialModelTrait,
+    PrimaryKeyToColumn, PrimaryKeyTrait, QueryFilter, QueryResult, Related, RelationDef,
+    RelationTrait, Select, Value,
 };
 
 #[cfg(feature = "macros")]
 pub use crate::{
     DeriveActiveEnum, DeriveActiveModel, DeriveActiveModelBehavior, DeriveColumn,
     DeriveCustomColumn, DeriveEntity, DeriveEntityModel, DeriveIntoActiveModel, DeriveModel,
-    DerivePrimaryKey, DeriveRelation, FromJsonQueryResult,
+    DerivePartialModel, DerivePrimaryKey, DeriveRelation, FromJsonQueryResult,
 };
 
 pub use async_trait;
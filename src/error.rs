@@ -73,6 +73,17 @@ pub enum DbErr {
     /// May be the table is empty or the record does not exist
     #[error("None of the records are updated")]
     RecordNotUpdated,
+    /// The statement ran longer than its timeout and was cancelled by the database
+    #[error("Statement Timeout: {0}")]
+    StatementTimeout(#[source] RuntimeErr),
+    /// The operation is not supported by the database backend
+    #[error("{ctx} is not supported by {db} backend")]
+    BackendNotSupported {
+        /// The database backend
+        db: &'static str,
+        /// The unsupported operation
+        ctx: &'static str,
+    },
 }
 
 /// Runtime error
@@ -87,6 +98,150 @@ pub enum RuntimeErr {
     Internal(String),
 }
 
+/// An error from a database constraint, decoded from the error code returned by the driver.
+///
+/// Each variant carries the name of the violated constraint where the backend provides it:
+/// PostgreSQL reports the constraint name (the column name for `NOT NULL`),
+/// MySQL names the key, constraint or column in its message,
+/// and SQLite names the columns of a `UNIQUE` or `NOT NULL` constraint.
+/// Otherwise, as for foreign keys on SQLite, the message reported by the database is attached.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SqlErr {
+    /// Error for duplicate record in unique field or primary key field
+    #[error("Unique Constraint Violated: {0}")]
+    UniqueConstraintViolation(String),
+    /// Error for Foreign key constraint
+    #[error("Foreign Key Constraint Violated: {0}")]
+    ForeignKeyConstraintViolation(String),
+    /// Error for inserting or updating a NULL value into a NOT NULL column
+    #[error("Not Null Constraint Violated: {0}")]
+    NotNullViolation(String),
+    /// Error for a row failing a CHECK constraint
+    #[error("Check Constraint Violated: {0}")]
+    CheckViolation(String),
+}
+
+impl DbErr {
+    /// Convert generic DbErr by sqlx to SqlErr, return none if the error is not any type of SqlErr
+    pub fn sql_err(&self) -> Option<SqlErr> {
+        #[cfg(any(
+            feature = "sqlx-mysql",
+            feature = "sqlx-postgres",
+            feature = "sqlx-sqlite"
+        ))]
+        if let DbErr::Exec(RuntimeErr::SqlxError(sqlx::Error::Database(e)))
+        | DbErr::Query(RuntimeErr::SqlxError(sqlx::Error::Database(e))) = self
+        {
+            let message = e.message();
+            #[cfg(feature = "sqlx-mysql")]
+            if let Some(err) = e.try_downcast_ref::<sqlx::mysql::MySqlDatabaseError>() {
+                // The key is quoted at the end of the message, after the duplicated entry
+                let key = || {
+                    quoted_name(&message[message.rfind(" key ").unwrap_or(0)..], '\'')
+                        .unwrap_or(message)
+                        .to_owned()
+                };
+                let constraint = || {
+                    message
+                        .find("CONSTRAINT `")
+                        .and_then(|i| quoted_name(&message[i..], '`'))
+                        .unwrap_or(message)
+                        .to_owned()
+                };
+                let name = || quoted_name(message, '\'').unwrap_or(message).to_owned();
+                return match err.number() {
+                    // 1022 Can't write; duplicate key in table '%s'
+                    // 1062 Duplicate entry '%s' for key %d
+                    // 1169 Can't write, because of unique constraint, to table '%s'
+                    // 1586 Duplicate entry '%s' for key '%s'
+                    1022 | 1062 | 1169 | 1586 => Some(SqlErr::UniqueConstraintViolation(key())),
+                    // 1216 Cannot add or update a child row: a foreign key constraint fails
+                    // 1217 Cannot delete or update a parent row: a foreign key constraint fails
+                    // 1451 Cannot delete or update a parent row: a foreign key constraint fails (%s)
+                    // 1452 Cannot add or update a child row: a foreign key constraint fails (%s)
+                    // 1557 Upholding foreign key constraints for table '%s', entry '%s', key %d would lead to a duplicate entry
+                    // 1761 Foreign key constraint for table '%s', record '%s' would lead to a duplicate entry in table '%s', key '%s'
+                    // 1762 Foreign key constraint for table '%s', record '%s' would lead to a duplicate entry in a child table
+                    1216 | 1217 | 1451 | 1452 | 1557 | 1761 | 1762 => {
+                        Some(SqlErr::ForeignKeyConstraintViolation(constraint()))
+                    }
+                    // 1048 Column '%s' cannot be null
+                    1048 => Some(SqlErr::NotNullViolation(name())),
+                    // 3819 Check constraint '%s' is violated
+                    3819 => Some(SqlErr::CheckViolation(name())),
+                    _ => None,
+                };
+            }
+            #[cfg(feature = "sqlx-postgres")]
+            if let Some(err) = e.try_downcast_ref::<sqlx::postgres::PgDatabaseError>() {
+                let constraint = || err.constraint().unwrap_or(message).to_owned();
+                return match err.code() {
+                    "23505" => Some(SqlErr::UniqueConstraintViolation(constraint())),
+                    "23503" => Some(SqlErr::ForeignKeyConstraintViolation(constraint())),
+                    "23502" => Some(SqlErr::NotNullViolation(
+                        err.column().unwrap_or(message).to_owned(),
+                    )),
+                    "23514" => Some(SqlErr::CheckViolation(constraint())),
+                    _ => None,
+                };
+            }
+            #[cfg(feature = "sqlx-sqlite")]
+            if e.try_downcast_ref::<sqlx::sqlite::SqliteError>().is_some() {
+                // e.g. "UNIQUE constraint failed: cake.name, cake.bakery_id"
+                let name = || {
+                    message
+                        .split_once("constraint failed: ")
+                        .map_or(message, |(_, name)| name)
+                        .to_owned()
+                };
+                // SQLite reports the extended result code
+                return match e.code().as_deref() {
+                    // 1555 SQLITE_CONSTRAINT_PRIMARYKEY
+                    // 2067 SQLITE_CONSTRAINT_UNIQUE
+                    Some("1555") | Some("2067") => Some(SqlErr::UniqueConstraintViolation(name())),
+                    // 787 SQLITE_CONSTRAINT_FOREIGNKEY
+                    Some("787") => Some(SqlErr::ForeignKeyConstraintViolation(name())),
+                    // 1299 SQLITE_CONSTRAINT_NOTNULL
+                    Some("1299") => Some(SqlErr::NotNullViolation(name())),
+                    // 275 SQLITE_CONSTRAINT_CHECK
+                    Some("275") => Some(SqlErr::CheckViolation(name())),
+                    _ => None,
+                };
+            }
+        }
+        None
+    }
+
+    /// Check if the error is a serialization failure or a deadlock reported by the database,
+    /// in which case the transaction has been aborted and can be retried as a whole
+    pub fn is_retryable(&self) -> bool {
+        #[cfg(feature = "sqlx-dep")]
+        if let DbErr::Exec(RuntimeErr::SqlxError(sqlx::Error::Database(e)))
+        | DbErr::Query(RuntimeErr::SqlxError(sqlx::Error::Database(e))) = self
+        {
+            return matches!(
+                e.code().as_deref(),
+                // SQLSTATE 40001 serialization_failure, which MySQL also reports for deadlock (1213)
+                // SQLSTATE 40P01 deadlock_detected (PostgreSQL)
+                Some("40001") | Some("40P01")
+                // 5 SQLITE_BUSY
+                // 517 SQLITE_BUSY_SNAPSHOT
+                | Some("5") | Some("517")
+            );
+        }
+        false
+    }
+}
+
+/// The first name enclosed in `quote` in a database error message
+#[cfg(feature = "sqlx-mysql")]
+fn quoted_name(message: &str, quote: char) -> Option<&str> {
+    let (_, rest) = message.split_once(quote)?;
+    let (name, _) = rest.split_once(quote)?;
+    Some(name)
+}
+
 impl PartialEq for DbErr {
     fn eq(&self, other: &Self) -> bool {
         self.to_string() == other.to_string()
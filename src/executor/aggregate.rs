@@ -0,0 +1,540 @@
+use crate::{
+    error::*, executor::select::build_select, ConnectionTrait, EntityTrait, IntoSimpleExpr,
+    QueryTrait, Select, SelectFour, SelectThree, SelectTwo, SelectTwoMany, TryGetable,
+};
+use sea_query::{Alias, Expr, Func, OrderedStatement, SelectStatement, SimpleExpr, WithClause};
+
+#[async_trait::async_trait]
+/// A Trait for any type that can aggregate the results of a select operation.
+///
+/// The aggregate is computed over the rows matching the filters and joins of the query,
+/// ignoring its `ORDER BY`, `LIMIT` and `OFFSET`, and keeps its `WITH` clause. If the query has a `GROUP BY`, the aggregate
+/// is computed over the groups, so the expression has to be grouped or an aggregate itself.
+/// The result type of [sum](AggregatorTrait::sum) and [avg](AggregatorTrait::avg) depends on
+/// the database backend, and can differ from the type of the expression.
+///
+/// ```
+/// # use sea_orm::{error::*, tests_cfg::*, *};
+/// #
+/// # #[smol_potat::main]
+/// # #[cfg(feature = "mock")]
+/// # pub async fn main() -> Result<(), DbErr> {
+/// #
+/// # let db = MockDatabase::new(DbBackend::Postgres)
+/// #     .append_query_results([[maplit::btreemap! {
+/// #         "value" => Into::<Value>::into(Some(3i32)),
+/// #     }]])
+/// #     .into_connection();
+/// #
+/// use sea_orm::{entity::*, query::*, tests_cfg::cake};
+///
+/// let max_id: Option<i32> = cake::Entity::find()
+///     .filter(cake::Column::Name.contains("cheese"))
+///     .order_by_asc(cake::Column::Name)
+///     .max(cake::Column::Id, &db)
+///     .await?;
+///
+/// assert_eq!(max_id, Some(3));
+///
+/// assert_eq!(
+///     db.into_transaction_log(),
+///     [Transaction::from_sql_and_values(
+///         DbBackend::Postgres,
+///         [
+///             r#"SELECT MAX("value") AS "value" FROM"#,
+///             r#"(SELECT "cake"."id" AS "value" FROM "cake" WHERE "cake"."name" LIKE $1) AS "sub_query""#,
+///         ]
+///         .join(" ")
+///         .as_str(),
+///         ["%cheese%".into()]
+///     )]
+/// );
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub trait AggregatorTrait: QueryTrait<QueryStatement = SelectStatement> + Sized + Send {
+    /// Split into the select statement and its `WITH` clause, if any
+    fn into_query_and_with(self) -> (SelectStatement, Option<WithClause>);
+
+    /// Check whether the select operation returns any row
+    async fn exists<C>(self, db: &C) -> Result<bool, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let (mut query, with) = self.into_query_and_with();
+        query
+            .clear_selects()
+            .clear_order_by()
+            .reset_offset()
+            .expr(Expr::cust("1"))
+            .limit(1);
+        let stmt = build_select(db.get_database_backend(), &with, &query);
+        Ok(db.query_one(stmt).await?.is_some())
+    }
+
+    /// Count the number of distinct values of an expression, excluding `NULL`
+    async fn count_distinct<E, C>(self, expr: E, db: &C) -> Result<u64, DbErr>
+    where
+        E: IntoSimpleExpr + Send,
+        C: ConnectionTrait,
+    {
+        let (query, with) = self.into_query_and_with();
+        let mut sub_query = aggregate_query(query, expr.into_simple_expr());
+        sub_query.distinct();
+        let query = outer_query(sub_query, Func::count(value_col()).into());
+        let count: Option<i64> = query_value(&with, &query, db).await?;
+        Ok(count.unwrap_or(0) as u64)
+    }
+
+    /// Sum the values of an expression, `None` if there is no value.
+    ///
+    /// The sum of an integer expression is read as an `i64` on SQLite. On PostgreSQL, it is a
+    /// `BIGINT` read as an `i64` for `SMALLINT` and `INTEGER`, and a `NUMERIC` read as a
+    /// `Decimal` for `BIGINT`. On MySQL, it is a `DECIMAL` read as a `Decimal`.
+    /// The sum of a floating point expression is read as an `f64`.
+    async fn sum<T, E, C>(self, expr: E, db: &C) -> Result<Option<T>, DbErr>
+    where
+        T: TryGetable + Send,
+        E: IntoSimpleExpr + Send,
+        C: ConnectionTrait,
+    {
+        let (query, with) = self.into_query_and_with();
+        let sub_query = aggregate_query(query, expr.into_simple_expr());
+        query_value(
+            &with,
+            &outer_query(sub_query, Func::sum(value_col()).into()),
+            db,
+        )
+        .await
+    }
+
+    /// Average the values of an expression, `None` if there is no value.
+    ///
+    /// The average is read as an `f64` on SQLite, and for a floating point expression.
+    /// The average of an integer or decimal expression is a `NUMERIC` on PostgreSQL and a
+    /// `DECIMAL` on MySQL, read as a `Decimal`.
+    async fn avg<T, E, C>(self, expr: E, db: &C) -> Result<Option<T>, DbErr>
+    where
+        T: TryGetable + Send,
+        E: IntoSimpleExpr + Send,
+        C: ConnectionTrait,
+    {
+        let (query, with) = self.into_query_and_with();
+        let sub_query = aggregate_query(query, expr.into_simple_expr());
+        query_value(
+            &with,
+            &outer_query(sub_query, Func::avg(value_col()).into()),
+            db,
+        )
+        .await
+    }
+
+    /// The minimum value of an expression, `None` if there is no value
+    async fn min<T, E, C>(self, expr: E, db: &C) -> Result<Option<T>, DbErr>
+    where
+        T: TryGetable + Send,
+        E: IntoSimpleExpr + Send,
+        C: ConnectionTrait,
+    {
+        let (query, with) = self.into_query_and_with();
+        let sub_query = aggregate_query(query, expr.into_simple_expr());
+        query_value(
+            &with,
+            &outer_query(sub_query, Func::min(value_col()).into()),
+            db,
+        )
+        .await
+    }
+
+    /// The maximum value of an expression, `None` if there is no value
+    async fn max<T, E, C>(self, expr: E, db: &C) -> Result<Option<T>, DbErr>
+    where
+        T: TryGetable + Send,
+        E: IntoSimpleExpr + Send,
+        C: ConnectionTrait,
+    {
+        let (query, with) = self.into_query_and_with();
+        let sub_query = aggregate_query(query, expr.into_simple_expr());
+        query_value(
+            &with,
+            &outer_query(sub_query, Func::max(value_col()).into()),
+            db,
+        )
+        .await
+    }
+}
+
+fn aggregate_query(mut query: SelectStatement, expr: SimpleExpr) -> SelectStatement {
+    query
+        .clear_selects()
+        .clear_order_by()
+        .reset_limit()
+        .reset_offset()
+        .expr_as(expr, Alias::new("value"));
+    query
+}
+
+/// Aggregate the `value` column of the subquery, so that a grouped query is aggregated over its groups
+fn outer_query(sub_query: SelectStatement, expr: SimpleExpr) -> SelectStatement {
+    SelectStatement::new()
+        .expr_as(expr, Alias::new("value"))
+        .from_subquery(sub_query, Alias::new("sub_query"))
+        .to_owned()
+}
+
+fn value_col() -> Expr {
+    Expr::col(Alias::new("value"))
+}
+
+async fn query_value<T, C>(
+    with: &Option<WithClause>,
+    query: &SelectStatement,
+    db: &C,
+) -> Result<Option<T>, DbErr>
+where
+    T: TryGetable,
+    C: ConnectionTrait,
+{
+    let stmt = build_select(db.get_database_backend(), with, query);
+    match db.query_one(stmt).await? {
+        Some(res) => res.try_get("", "value"),
+        None => Ok(None),
+    }
+}
+
+impl<E> AggregatorTrait for Select<E>
+where
+    E: EntityTrait,
+{
+    fn into_query_and_with(self) -> (SelectStatement, Option<WithClause>) {
+        (self.query, self.with.into_with_clause())
+    }
+}
+
+impl<E, F> AggregatorTrait for SelectTwo<E, F>
+where
+    E: EntityTrait,
+    F: EntityTrait,
+{
+    fn into_query_and_with(self) -> (SelectStatement, Option<WithClause>) {
+        (self.query, self.with)
+    }
+}
+
+impl<E, F> AggregatorTrait for SelectTwoMany<E, F>
+where
+    E: EntityTrait,
+    F: EntityTrait,
+{
+    fn into_query_and_with(self) -> (SelectStatement, Option<WithClause>) {
+        (self.query, self.with)
+    }
+}
+
+impl<E, F, G> AggregatorTrait for SelectThree<E, F, G>
+where
+    E: EntityTrait,
+    F: EntityTrait,
+    G: EntityTrait,
+{
+    fn into_query_and_with(self) -> (SelectStatement, Option<WithClause>) {
+        (self.query, self.with)
+    }
+}
+
+impl<E, F, G, H> AggregatorTrait for SelectFour<E, F, G, H>
+where
+    E: EntityTrait,
+    F: EntityTrait,
+    G: EntityTrait,
+    H: EntityTrait,
+{
+    fn into_query_and_with(self) -> (SelectStatement, Option<WithClause>) {
+        (self.query, self.with)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "mock")]
+mod tests {
+    use crate::entity::prelude::*;
+    use crate::tests_cfg::*;
+    use crate::{
+        DbBackend, MockDatabase, QueryOrder, QuerySelect, QueryTrait, Select, Transaction,
+    };
+    use pretty_assertions::assert_eq;
+    use sea_query::{Alias, Expr, SelectStatement, Value};
+
+    #[smol_potat::test]
+    async fn exists() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results([[maplit::btreemap! {
+                "?column?" => Into::<Value>::into(1i32),
+            }]])
+            .append_query_results([Vec::<cake::Model>::new()])
+            .into_connection();
+
+        assert!(
+            cake::Entity::find()
+                .filter(cake::Column::Id.gt(1))
+                .order_by_asc(cake::Column::Id)
+                .exists(&db)
+                .await?
+        );
+        assert!(
+            !cake::Entity::find()
+                .filter(cake::Column::Id.gt(10))
+                .offset(5)
+                .exists(&db)
+                .await?
+        );
+
+        assert_eq!(
+            db.into_transaction_log(),
+            [
+                Transaction::from_sql_and_values(
+                    DbBackend::Postgres,
+                    r#"SELECT 1 FROM "cake" WHERE "cake"."id" > $1 LIMIT $2"#,
+                    [1i32.into(), 1u64.into()]
+                ),
+                Transaction::from_sql_and_values(
+                    DbBackend::Postgres,
+                    r#"SELECT 1 FROM "cake" WHERE "cake"."id" > $1 LIMIT $2"#,
+                    [10i32.into(), 1u64.into()]
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn count_distinct_select_two() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results([[maplit::btreemap! {
+                "value" => Into::<Value>::into(2i64),
+            }]])
+            .into_connection();
+
+        assert_eq!(
+            cake::Entity::find()
+                .find_also_related(fruit::Entity)
+                .filter(fruit::Column::Name.contains("apple"))
+                .order_by_asc(cake::Column::Id)
+                .limit(10)
+                .count_distinct(cake::Column::Id, &db)
+                .await?,
+            2
+        );
+
+        assert_eq!(
+            db.into_transaction_log(),
+            [Transaction::from_sql_and_values(
+                DbBackend::Postgres,
+                [
+                    r#"SELECT COUNT("value") AS "value" FROM"#,
+                    r#"(SELECT DISTINCT "cake"."id" AS "value" FROM "cake""#,
+                    r#"LEFT JOIN "fruit" ON "cake"."id" = "fruit"."cake_id""#,
+                    r#"WHERE "fruit"."name" LIKE $1) AS "sub_query""#,
+                ]
+                .join(" ")
+                .as_str(),
+                ["%apple%".into()]
+            )]
+        );
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn sum_avg_min_max() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results([[maplit::btreemap! {
+                "value" => Into::<Value>::into(Some(6i64)),
+            }]])
+            .append_query_results([[maplit::btreemap! {
+                "value" => Into::<Value>::into(Some(2.0f64)),
+            }]])
+            .append_query_results([[maplit::btreemap! {
+                "value" => Into::<Value>::into(Option::<i32>::None),
+            }]])
+            .append_query_results([[maplit::btreemap! {
+                "value" => Into::<Value>::into(Some("Cheesecake".to_owned())),
+            }]])
+            .into_connection();
+
+        let sum: Option<i64> = cake::Entity::find().sum(cake::Column::Id, &db).await?;
+        let avg: Option<f64> = cake::Entity::find().avg(cake::Column::Id, &db).await?;
+        let min: Option<i32> = cake::Entity::find()
+            .filter(cake::Column::Id.gt(100))
+            .min(cake::Column::Id, &db)
+            .await?;
+        let max: Option<String> = cake::Entity::find().max(cake::Column::Name, &db).await?;
+
+        assert_eq!(sum, Some(6));
+        assert_eq!(avg, Some(2.0));
+        assert_eq!(min, None);
+        assert_eq!(max, Some("Cheesecake".to_owned()));
+
+        assert_eq!(
+            db.into_transaction_log(),
+            [
+                Transaction::from_sql_and_values(
+                    DbBackend::Postgres,
+                    [
+                        r#"SELECT SUM("value") AS "value" FROM"#,
+                        r#"(SELECT "cake"."id" AS "value" FROM "cake") AS "sub_query""#,
+                    ]
+                    .join(" ")
+                    .as_str(),
+                    []
+                ),
+                Transaction::from_sql_and_values(
+                    DbBackend::Postgres,
+                    [
+                        r#"SELECT AVG("value") AS "value" FROM"#,
+                        r#"(SELECT "cake"."id" AS "value" FROM "cake") AS "sub_query""#,
+                    ]
+                    .join(" ")
+                    .as_str(),
+                    []
+                ),
+                Transaction::from_sql_and_values(
+                    DbBackend::Postgres,
+                    [
+                        r#"SELECT MIN("value") AS "value" FROM"#,
+                        r#"(SELECT "cake"."id" AS "value" FROM "cake" WHERE "cake"."id" > $1) AS "sub_query""#,
+                    ]
+                    .join(" ")
+                    .as_str(),
+                    [100i32.into()]
+                ),
+                Transaction::from_sql_and_values(
+                    DbBackend::Postgres,
+                    [
+                        r#"SELECT MAX("value") AS "value" FROM"#,
+                        r#"(SELECT "cake"."name" AS "value" FROM "cake") AS "sub_query""#,
+                    ]
+                    .join(" ")
+                    .as_str(),
+                    []
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn max_group_by() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results([[maplit::btreemap! {
+                "value" => Into::<Value>::into(Some(3i64)),
+            }]])
+            .into_connection();
+
+        // The largest number of fruits of a cake
+        let max: Option<i64> = fruit::Entity::find()
+            .group_by(fruit::Column::CakeId)
+            .max(Expr::col(fruit::Column::Id).count(), &db)
+            .await?;
+
+        assert_eq!(max, Some(3));
+
+        assert_eq!(
+            db.into_transaction_log(),
+            [Transaction::from_sql_and_values(
+                DbBackend::Postgres,
+                [
+                    r#"SELECT MAX("value") AS "value" FROM"#,
+                    r#"(SELECT COUNT("id") AS "value" FROM "fruit""#,
+                    r#"GROUP BY "fruit"."cake_id") AS "sub_query""#,
+                ]
+                .join(" ")
+                .as_str(),
+                []
+            )]
+        );
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn exists_with_cte() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results([[maplit::btreemap! {
+                "?column?" => Into::<Value>::into(1i32),
+            }]])
+            .append_query_results([[maplit::btreemap! {
+                "value" => Into::<Value>::into(1i64),
+            }]])
+            .into_connection();
+
+        let cheese = |select: Select<cake::Entity>| {
+            select
+                .with_cte(
+                    Alias::new("cheese"),
+                    SelectStatement::new()
+                        .column(cake::Column::Id)
+                        .from(cake::Entity)
+                        .and_where(cake::Column::Name.contains("cheese"))
+                        .to_owned(),
+                )
+                .filter(
+                    cake::Column::Id.in_subquery(
+                        SelectStatement::new()
+                            .column(cake::Column::Id)
+                            .from(Alias::new("cheese"))
+                            .to_owned(),
+                    ),
+                )
+        };
+
+        assert!(cheese(cake::Entity::find())
+            .find_also_related(fruit::Entity)
+            .build(DbBackend::Postgres)
+            .to_string()
+            .starts_with(r#"WITH "cheese" ("id") AS"#));
+        assert!(cheese(cake::Entity::find()).exists(&db).await?);
+        assert_eq!(
+            cheese(cake::Entity::find())
+                .find_also_related(fruit::Entity)
+                .count_distinct(fruit::Column::Id, &db)
+                .await?,
+            1
+        );
+
+        assert_eq!(
+            db.into_transaction_log(),
+            [
+                Transaction::from_sql_and_values(
+                    DbBackend::Postgres,
+                    [
+                        r#"WITH "cheese" ("id") AS (SELECT "id" FROM "cake" WHERE "cake"."name" LIKE $1)"#,
+                        r#"SELECT 1 FROM "cake""#,
+                        r#"WHERE "cake"."id" IN (SELECT "id" FROM "cheese") LIMIT $2"#,
+                    ]
+                    .join(" ")
+                    .as_str(),
+                    ["%cheese%".into(), 1u64.into()]
+                ),
+                Transaction::from_sql_and_values(
+                    DbBackend::Postgres,
+                    [
+                        r#"WITH "cheese" ("id") AS (SELECT "id" FROM "cake" WHERE "cake"."name" LIKE $1)"#,
+                        r#"SELECT COUNT("value") AS "value" FROM"#,
+                        r#"(SELECT DISTINCT "fruit"."id" AS "value" FROM "cake""#,
+                        r#"LEFT JOIN "fruit" ON "cake"."id" = "fruit"."cake_id""#,
+                        r#"WHERE "cake"."id" IN (SELECT "id" FROM "cheese")) AS "sub_query""#,
+                    ]
+                    .join(" ")
+                    .as_str(),
+                    ["%cheese%".into()]
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -1,10 +1,11 @@
+use crate::executor::select::build_select;
 use crate::{
     ConnectionTrait, DbErr, EntityTrait, FromQueryResult, Identity, IntoIdentity, QueryOrder,
     Select, SelectModel, SelectorTrait,
 };
 use sea_query::{
     Condition, DynIden, Expr, IntoValueTuple, Order, OrderedStatement, SeaRc, SelectStatement,
-    SimpleExpr, Value, ValueTuple,
+    SimpleExpr, Value, ValueTuple, WithClause,
 };
 use std::marker::PhantomData;
 
@@ -18,6 +19,7 @@ where
     S: SelectorTrait,
 {
     pub(crate) query: SelectStatement,
+    pub(crate) with: Option<WithClause>,
     pub(crate) table: DynIden,
     pub(crate) order_columns: Identity,
     pub(crate) last: bool,
@@ -35,6 +37,7 @@ where
     {
         Self {
             query,
+            with: None,
             table,
             order_columns: order_columns.into_identity(),
             last: false,
@@ -153,7 +156,7 @@ where
     where
         C: ConnectionTrait,
     {
-        let stmt = db.get_database_backend().build(&self.query);
+        let stmt = build_select(db.get_database_backend(), &self.with, &self.query);
         let rows = db.query_all(stmt).await?;
         let mut buffer = Vec::with_capacity(rows.len());
         for row in rows.into_iter() {
@@ -172,6 +175,7 @@ where
     {
         Cursor {
             query: self.query,
+            with: self.with,
             table: self.table,
             order_columns: self.order_columns,
             last: self.last,
@@ -184,6 +188,7 @@ where
     pub fn into_json(self) -> Cursor<SelectModel<JsonValue>> {
         Cursor {
             query: self.query,
+            with: self.with,
             table: self.table,
             order_columns: self.order_columns,
             last: self.last,
@@ -225,7 +230,9 @@ where
     where
         C: IntoIdentity,
     {
-        Cursor::new(self.query, SeaRc::new(E::default()), order_columns)
+        let mut cursor = Cursor::new(self.query, SeaRc::new(E::default()), order_columns);
+        cursor.with = self.with.into_with_clause();
+        cursor
     }
 }
 
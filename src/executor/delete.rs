@@ -1,13 +1,15 @@
 use crate::{
-    error::*, ActiveModelTrait, ConnectionTrait, DeleteMany, DeleteOne, EntityTrait, Statement,
+    error::*, ActiveModelTrait, ColumnTrait, ConnectionTrait, DbBackend, DeleteMany, DeleteOne,
+    EntityTrait, Iterable, SelectModel, SelectorRaw, Statement, StatementOptions,
 };
-use sea_query::DeleteStatement;
-use std::future::Future;
+use sea_query::{DeleteStatement, Expr, Query};
+use std::{future::Future, time::Duration};
 
 /// Handles DELETE operations in a ActiveModel using [DeleteStatement]
 #[derive(Clone, Debug)]
 pub struct Deleter {
     query: DeleteStatement,
+    options: StatementOptions,
 }
 
 /// The result of a DELETE operation
@@ -43,12 +45,34 @@ where
         // so that self is dropped before entering await
         exec_delete_only(self.query, db)
     }
+
+    /// Execute a DELETE operation on many ActiveModels and return the deleted models
+    /// using `RETURNING` syntax. Returns [DbErr::BackendNotSupported] on MySQL
+    pub fn exec_with_returning<C>(
+        self,
+        db: &'a C,
+    ) -> impl Future<Output = Result<Vec<E::Model>, DbErr>> + 'a
+    where
+        C: ConnectionTrait,
+    {
+        // so that self is dropped before entering await
+        Deleter::new(self.query).exec_delete_with_returning::<E, _>(db)
+    }
 }
 
 impl Deleter {
     /// Instantiate a new [Deleter] by passing it a [DeleteStatement]
     pub fn new(query: DeleteStatement) -> Self {
-        Self { query }
+        Self {
+            query,
+            options: StatementOptions::default(),
+        }
+    }
+
+    /// Set the timeout of the delete, overriding the connection's statement timeout
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.options = self.options.timeout(timeout);
+        self
     }
 
     /// Execute a DELETE operation
@@ -57,7 +81,30 @@ impl Deleter {
         C: ConnectionTrait,
     {
         let builder = db.get_database_backend();
-        exec_delete(builder.build(&self.query), db)
+        exec_delete(builder.build(&self.query), self.options, db)
+    }
+
+    /// Execute a DELETE operation and return the deleted models using `RETURNING` syntax.
+    /// Returns [DbErr::BackendNotSupported] on MySQL
+    pub async fn exec_delete_with_returning<E, C>(mut self, db: &C) -> Result<Vec<E::Model>, DbErr>
+    where
+        E: EntityTrait,
+        C: ConnectionTrait,
+    {
+        let db_backend = db.get_database_backend();
+        if db_backend == DbBackend::MySql {
+            return Err(DbErr::BackendNotSupported {
+                db: "MySQL",
+                ctx: "DELETE RETURNING",
+            });
+        }
+        let returning =
+            Query::returning().exprs(E::Column::iter().map(|c| c.select_as(Expr::col(c))));
+        self.query.returning(returning);
+        SelectorRaw::<SelectModel<E::Model>>::from_statement(db_backend.build(&self.query))
+            .with_options(self.options)
+            .all(db)
+            .await
     }
 }
 
@@ -68,11 +115,15 @@ where
     Deleter::new(query).exec(db).await
 }
 
-async fn exec_delete<C>(statement: Statement, db: &C) -> Result<DeleteResult, DbErr>
+async fn exec_delete<C>(
+    statement: Statement,
+    options: StatementOptions,
+    db: &C,
+) -> Result<DeleteResult, DbErr>
 where
     C: ConnectionTrait,
 {
-    let result = db.execute(statement).await?;
+    let result = db.execute_with_options(statement, options).await?;
     Ok(DeleteResult {
         rows_affected: result.rows_affected(),
     })
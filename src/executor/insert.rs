@@ -1,9 +1,13 @@
 use crate::{
-    error::*, ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, Insert, IntoActiveModel,
-    Iterable, PrimaryKeyToColumn, PrimaryKeyTrait, SelectModel, SelectorRaw, Statement, TryFromU64,
+    error::*, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DbBackend, EntityTrait,
+    Insert, IntoActiveModel, Iterable, ModelTrait, PrimaryKeyToColumn, PrimaryKeyTrait,
+    QueryFilter, SelectModel, SelectorRaw, Statement, StatementOptions, TransactionTrait,
+    TryFromU64, TryInsert,
 };
-use sea_query::{Expr, FromValueTuple, Iden, InsertStatement, IntoColumnRef, Query, ValueTuple};
-use std::{future::Future, marker::PhantomData};
+use sea_query::{
+    Expr, FromValueTuple, Iden, InsertStatement, IntoColumnRef, Query, Value, ValueTuple,
+};
+use std::{future::Future, marker::PhantomData, time::Duration};
 
 /// Defines a structure to perform INSERT operations in an ActiveModel
 #[derive(Debug)]
@@ -12,8 +16,10 @@ where
     A: ActiveModelTrait,
 {
     primary_key: Option<ValueTuple>,
+    conflict_target: Option<Condition>,
     query: InsertStatement,
     model: PhantomData<A>,
+    options: StatementOptions,
 }
 
 /// The result of an INSERT operation on an ActiveModel
@@ -73,7 +79,96 @@ where
         C: ConnectionTrait,
         A: 'a,
     {
-        Inserter::<A>::new(self.primary_key, self.query).exec_with_returning(db)
+        let mut inserter = Inserter::<A>::new(self.primary_key, self.query);
+        inserter.conflict_target = self.conflict_target;
+        inserter.exec_with_returning(db)
+    }
+
+    /// Execute an insert operation and return all inserted models, in the order they were added.
+    /// Uses `RETURNING` syntax if the database supports it. Otherwise the inserted rows are
+    /// selected back by primary key within a transaction, and [DbErr::BackendNotSupported]
+    /// is returned if the primary keys of some models were not set.
+    pub fn exec_with_returning_many<'a, C>(
+        self,
+        db: &'a C,
+    ) -> impl Future<Output = Result<Vec<<A::Entity as EntityTrait>::Model>, DbErr>> + 'a
+    where
+        <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+        C: ConnectionTrait + TransactionTrait,
+        A: 'a,
+    {
+        exec_insert_with_returning_many::<A, _>(self.primary_keys, self.query, db)
+    }
+}
+
+/// The outcome of an INSERT operation that may insert nothing, see [TryInsert]
+#[derive(Debug)]
+pub enum TryInsertResult<T> {
+    /// No Model was added, so no statement was executed
+    Empty,
+    /// No row was inserted because every row conflicted with an existing one
+    Conflicted,
+    /// The rows were inserted
+    Inserted(T),
+}
+
+impl<A> TryInsert<A>
+where
+    A: ActiveModelTrait,
+{
+    /// Execute an insert operation, see [Insert::exec]
+    pub async fn exec<'a, C>(self, db: &'a C) -> Result<TryInsertResult<InsertResult<A>>, DbErr>
+    where
+        C: ConnectionTrait,
+        A: 'a,
+    {
+        if self.is_empty() {
+            return Ok(TryInsertResult::Empty);
+        }
+        match self.insert_struct.exec(db).await {
+            Ok(res) => Ok(TryInsertResult::Inserted(res)),
+            Err(DbErr::RecordNotInserted) => Ok(TryInsertResult::Conflicted),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Execute an insert operation without returning, see [Insert::exec_without_returning]
+    pub async fn exec_without_returning<'a, C>(
+        self,
+        db: &'a C,
+    ) -> Result<TryInsertResult<u64>, DbErr>
+    where
+        <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+        C: ConnectionTrait,
+        A: 'a,
+    {
+        if self.is_empty() {
+            return Ok(TryInsertResult::Empty);
+        }
+        match self.insert_struct.exec_without_returning(db).await? {
+            0 => Ok(TryInsertResult::Conflicted),
+            rows_affected => Ok(TryInsertResult::Inserted(rows_affected)),
+        }
+    }
+
+    /// Execute an insert operation and return the inserted model, see [Insert::exec_with_returning]
+    pub async fn exec_with_returning<'a, C>(
+        self,
+        db: &'a C,
+    ) -> Result<TryInsertResult<<A::Entity as EntityTrait>::Model>, DbErr>
+    where
+        <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+        C: ConnectionTrait,
+        A: 'a,
+    {
+        if self.is_empty() {
+            return Ok(TryInsertResult::Empty);
+        }
+        match self.insert_struct.exec_with_returning(db).await {
+            Ok(model) => Ok(TryInsertResult::Inserted(model)),
+            Err(DbErr::RecordNotInserted) => Ok(TryInsertResult::Conflicted),
+            Err(err) => Err(err),
+        }
     }
 }
 
@@ -85,11 +180,19 @@ where
     pub fn new(primary_key: Option<ValueTuple>, query: InsertStatement) -> Self {
         Self {
             primary_key,
+            conflict_target: None,
             query,
             model: PhantomData,
+            options: StatementOptions::default(),
         }
     }
 
+    /// Set the timeout of the insert, overriding the connection's statement timeout
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.options = self.options.timeout(timeout);
+        self
+    }
+
     /// Execute an insert operation, returning the last inserted id
     pub fn exec<'a, C>(self, db: &'a C) -> impl Future<Output = Result<InsertResult<A>, DbErr>> + '_
     where
@@ -97,7 +200,12 @@ where
         A: 'a,
     {
         let builder = db.get_database_backend();
-        exec_insert(self.primary_key, builder.build(&self.query), db)
+        exec_insert(
+            self.primary_key,
+            builder.build(&self.query),
+            self.options,
+            db,
+        )
     }
 
     /// Execute an insert operation
@@ -109,7 +217,7 @@ where
         C: ConnectionTrait,
         A: 'a,
     {
-        exec_insert_without_returning(self.query, db)
+        exec_insert_without_returning(self.query, self.options, db)
     }
 
     /// Execute an insert operation and return the inserted model (use `RETURNING` syntax if database supported)
@@ -122,13 +230,20 @@ where
         C: ConnectionTrait,
         A: 'a,
     {
-        exec_insert_with_returning::<A, _>(self.primary_key, self.query, db)
+        exec_insert_with_returning::<A, _>(
+            self.primary_key,
+            self.conflict_target,
+            self.query,
+            self.options,
+            db,
+        )
     }
 }
 
 async fn exec_insert<A, C>(
     primary_key: Option<ValueTuple>,
     statement: Statement,
+    options: StatementOptions,
     db: &C,
 ) -> Result<InsertResult<A>, DbErr>
 where
@@ -140,14 +255,14 @@ where
 
     let last_insert_id = match (primary_key, db.support_returning()) {
         (Some(value_tuple), _) => {
-            let res = db.execute(statement).await?;
+            let res = db.execute_with_options(statement, options).await?;
             if res.rows_affected() == 0 {
                 return Err(DbErr::RecordNotInserted);
             }
             FromValueTuple::from_value_tuple(value_tuple)
         }
         (None, true) => {
-            let mut rows = db.query_all(statement).await?;
+            let mut rows = db.query_all_with_options(statement, options).await?;
             let row = match rows.pop() {
                 Some(row) => row,
                 None => return Err(DbErr::RecordNotInserted),
@@ -159,7 +274,7 @@ where
                 .map_err(|_| DbErr::UnpackInsertId)?
         }
         (None, false) => {
-            let res = db.execute(statement).await?;
+            let res = db.execute_with_options(statement, options).await?;
             if res.rows_affected() == 0 {
                 return Err(DbErr::RecordNotInserted);
             }
@@ -173,19 +288,24 @@ where
 
 async fn exec_insert_without_returning<C>(
     insert_statement: InsertStatement,
+    options: StatementOptions,
     db: &C,
 ) -> Result<u64, DbErr>
 where
     C: ConnectionTrait,
 {
     let db_backend = db.get_database_backend();
-    let exec_result = db.execute(db_backend.build(&insert_statement)).await?;
+    let exec_result = db
+        .execute_with_options(db_backend.build(&insert_statement), options)
+        .await?;
     Ok(exec_result.rows_affected())
 }
 
 async fn exec_insert_with_returning<A, C>(
     primary_key: Option<ValueTuple>,
+    conflict_target: Option<Condition>,
     mut insert_statement: InsertStatement,
+    options: StatementOptions,
     db: &C,
 ) -> Result<<A::Entity as EntityTrait>::Model, DbErr>
 where
@@ -194,30 +314,122 @@ where
     A: ActiveModelTrait,
 {
     let db_backend = db.get_database_backend();
-    let found = match db.support_returning() {
+    match db.support_returning() {
         true => {
             let returning = Query::returning().exprs(
                 <A::Entity as EntityTrait>::Column::iter().map(|c| c.select_as(Expr::col(c))),
             );
             insert_statement.returning(returning);
-            SelectorRaw::<SelectModel<<A::Entity as EntityTrait>::Model>>::from_statement(
-                db_backend.build(&insert_statement),
-            )
-            .one(db)
-            .await?
+            let found =
+                SelectorRaw::<SelectModel<<A::Entity as EntityTrait>::Model>>::from_statement(
+                    db_backend.build(&insert_statement),
+                )
+                .with_options(options)
+                .one(db)
+                .await?;
+            // No row is returned if the insert was skipped on conflict
+            found.ok_or(DbErr::RecordNotInserted)
         }
         false => {
-            let insert_res =
-                exec_insert::<A, _>(primary_key, db_backend.build(&insert_statement), db).await?;
-            <A::Entity as EntityTrait>::find_by_id(insert_res.last_insert_id)
+            let statement = db_backend.build(&insert_statement);
+            let select = match conflict_target {
+                // An updated row isn't identified by `last_insert_id`
+                Some(condition) => {
+                    let res = db.execute_with_options(statement, options).await?;
+                    if res.rows_affected() == 0 {
+                        return Err(DbErr::RecordNotInserted);
+                    }
+                    <A::Entity as EntityTrait>::find().filter(condition)
+                }
+                None => {
+                    let insert_res =
+                        exec_insert::<A, _>(primary_key, statement, options, db).await?;
+                    <A::Entity as EntityTrait>::find_by_id(insert_res.last_insert_id)
+                }
+            };
+            // Read the row just inserted from the primary, as a read replica may lag behind
+            let found = select
+                .into_model()
+                .with_options(StatementOptions::new().read_from_primary())
                 .one(db)
-                .await?
+                .await?;
+            found.ok_or_else(|| DbErr::RecordNotFound("Failed to find inserted item".to_owned()))
         }
-    };
-    match found {
-        Some(model) => Ok(model),
-        None => Err(DbErr::RecordNotFound(
-            "Failed to find inserted item".to_owned(),
-        )),
     }
 }
+
+async fn exec_insert_with_returning_many<A, C>(
+    primary_keys: Vec<Option<ValueTuple>>,
+    mut insert_statement: InsertStatement,
+    db: &C,
+) -> Result<Vec<<A::Entity as EntityTrait>::Model>, DbErr>
+where
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+    C: ConnectionTrait + TransactionTrait,
+    A: ActiveModelTrait,
+{
+    type PrimaryKey<A> = <<A as ActiveModelTrait>::Entity as EntityTrait>::PrimaryKey;
+
+    if primary_keys.is_empty() {
+        return Ok(Vec::new());
+    }
+    let db_backend = db.get_database_backend();
+    if db.support_returning() {
+        let returning = Query::returning()
+            .exprs(<A::Entity as EntityTrait>::Column::iter().map(|c| c.select_as(Expr::col(c))));
+        insert_statement.returning(returning);
+        return SelectorRaw::<SelectModel<<A::Entity as EntityTrait>::Model>>::from_statement(
+            db_backend.build(&insert_statement),
+        )
+        .all(db)
+        .await;
+    }
+
+    if !primary_keys.iter().all(Option::is_some) {
+        return Err(DbErr::BackendNotSupported {
+            db: match db_backend {
+                DbBackend::MySql => "MySQL",
+                DbBackend::Sqlite => "SQLite",
+                DbBackend::Postgres => "PostgreSQL",
+            },
+            ctx: "INSERT RETURNING without primary keys",
+        });
+    }
+
+    let txn = db.begin().await?;
+    let res = txn.execute(db_backend.build(&insert_statement)).await?;
+    if res.rows_affected() == 0 {
+        return Err(DbErr::RecordNotInserted);
+    }
+    let primary_keys: Vec<Vec<Value>> = primary_keys
+        .into_iter()
+        .flatten()
+        .map(|key| key.into_iter().collect())
+        .collect();
+    let mut condition = Condition::any();
+    for key in primary_keys.iter() {
+        let mut row = Condition::all();
+        for (col, value) in PrimaryKey::<A>::iter().zip(key) {
+            row = row.add(col.into_column().eq(value.clone()));
+        }
+        condition = condition.add(row);
+    }
+    let mut found = <A::Entity as EntityTrait>::find()
+        .filter(condition)
+        .all(&txn)
+        .await?;
+    let mut models = Vec::with_capacity(primary_keys.len());
+    for key in primary_keys.iter() {
+        let idx = found
+            .iter()
+            .position(|model| {
+                PrimaryKey::<A>::iter()
+                    .map(|col| model.get(col.into_column()))
+                    .eq(key.iter().cloned())
+            })
+            .ok_or_else(|| DbErr::RecordNotFound("Failed to find inserted item".to_owned()))?;
+        models.push(found.swap_remove(idx));
+    }
+    txn.commit().await?;
+    Ok(models)
+}
@@ -1,10 +1,12 @@
+use crate::executor::select::build_select;
 use crate::{
-    error::*, ConnectionTrait, DbBackend, EntityTrait, FromQueryResult, Select, SelectModel,
-    SelectTwo, SelectTwoModel, Selector, SelectorRaw, SelectorTrait,
+    error::*, ConnectionTrait, DbBackend, EntityTrait, FromQueryResult, Select, SelectFour,
+    SelectFourModel, SelectModel, SelectThree, SelectThreeModel, SelectTwo, SelectTwoModel,
+    Selector, SelectorRaw, SelectorTrait,
 };
 use async_stream::stream;
 use futures::Stream;
-use sea_query::{Alias, Expr, SelectStatement};
+use sea_query::{Alias, Expr, SelectStatement, WithClause};
 use std::{marker::PhantomData, pin::Pin};
 
 /// Pin a Model so that stream operations can be performed on the model
@@ -18,6 +20,7 @@ where
     S: SelectorTrait + 'db,
 {
     pub(crate) query: SelectStatement,
+    pub(crate) with: Option<WithClause>,
     pub(crate) page: u64,
     pub(crate) page_size: u64,
     pub(crate) db: &'db C,
@@ -48,8 +51,7 @@ where
             .limit(self.page_size)
             .offset(self.page_size * page)
             .to_owned();
-        let builder = self.db.get_database_backend();
-        let stmt = builder.build(&query);
+        let stmt = build_select(self.db.get_database_backend(), &self.with, &query);
         let rows = self.db.query_all(stmt).await?;
         let mut buffer = Vec::with_capacity(rows.len());
         for row in rows.into_iter() {
@@ -67,7 +69,9 @@ where
     /// Get the total number of items
     pub async fn num_items(&self) -> Result<u64, DbErr> {
         let builder = self.db.get_database_backend();
-        let stmt = builder.build(
+        let stmt = build_select(
+            builder,
+            &self.with,
             SelectStatement::new()
                 .expr(Expr::cust("COUNT(*) AS num_items"))
                 .from_subquery(
@@ -233,6 +237,7 @@ where
         assert!(page_size != 0, "page_size should not be zero");
         Paginator {
             query: self.query,
+            with: self.with,
             page: 0,
             page_size,
             db,
@@ -259,6 +264,7 @@ where
 
         Paginator {
             query,
+            with: None,
             page: 0,
             page_size,
             db,
@@ -295,6 +301,42 @@ where
     }
 }
 
+impl<'db, C, M, N, O, E, F, G> PaginatorTrait<'db, C> for SelectThree<E, F, G>
+where
+    C: ConnectionTrait,
+    E: EntityTrait<Model = M>,
+    F: EntityTrait<Model = N>,
+    G: EntityTrait<Model = O>,
+    M: FromQueryResult + Sized + Send + Sync + 'db,
+    N: FromQueryResult + Sized + Send + Sync + 'db,
+    O: FromQueryResult + Sized + Send + Sync + 'db,
+{
+    type Selector = SelectThreeModel<M, N, O>;
+
+    fn paginate(self, db: &'db C, page_size: u64) -> Paginator<'db, C, Self::Selector> {
+        self.into_model().paginate(db, page_size)
+    }
+}
+
+impl<'db, C, M, N, O, P, E, F, G, H> PaginatorTrait<'db, C> for SelectFour<E, F, G, H>
+where
+    C: ConnectionTrait,
+    E: EntityTrait<Model = M>,
+    F: EntityTrait<Model = N>,
+    G: EntityTrait<Model = O>,
+    H: EntityTrait<Model = P>,
+    M: FromQueryResult + Sized + Send + Sync + 'db,
+    N: FromQueryResult + Sized + Send + Sync + 'db,
+    O: FromQueryResult + Sized + Send + Sync + 'db,
+    P: FromQueryResult + Sized + Send + Sync + 'db,
+{
+    type Selector = SelectFourModel<M, N, O, P>;
+
+    fn paginate(self, db: &'db C, page_size: u64) -> Paginator<'db, C, Self::Selector> {
+        self.into_model().paginate(db, page_size)
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "mock")]
 mod tests {
@@ -767,6 +809,71 @@ mod tests {
         Ok(())
     }
 
+    #[smol_potat::test]
+    async fn paginate_with_cte() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results([[maplit::btreemap! {
+                "num_items" => Into::<Value>::into(1i64),
+            }]])
+            .append_query_results([[fruit::Model {
+                id: 1,
+                name: "Blueberry".into(),
+                cake_id: Some(1),
+            }]])
+            .into_connection();
+
+        let paginator = fruit::Entity::find()
+            .with_cte(
+                Alias::new("cheese"),
+                SelectStatement::new()
+                    .column(cake::Column::Id)
+                    .from(cake::Entity)
+                    .to_owned(),
+            )
+            .filter(
+                fruit::Column::CakeId.in_subquery(
+                    SelectStatement::new()
+                        .column(cake::Column::Id)
+                        .from(Alias::new("cheese"))
+                        .to_owned(),
+                ),
+            )
+            .paginate(&db, 2);
+
+        assert_eq!(paginator.num_items().await?, 1);
+        assert_eq!(paginator.fetch_page(0).await?.len(), 1);
+
+        assert_eq!(
+            db.into_transaction_log(),
+            [
+                Transaction::from_sql_and_values(
+                    DbBackend::Postgres,
+                    [
+                        r#"WITH "cheese" ("id") AS (SELECT "id" FROM "cake")"#,
+                        r#"SELECT COUNT(*) AS num_items FROM"#,
+                        r#"(SELECT "fruit"."id", "fruit"."name", "fruit"."cake_id" FROM "fruit""#,
+                        r#"WHERE "fruit"."cake_id" IN (SELECT "id" FROM "cheese")) AS "sub_query""#,
+                    ]
+                    .join(" ")
+                    .as_str(),
+                    []
+                ),
+                Transaction::from_sql_and_values(
+                    DbBackend::Postgres,
+                    [
+                        r#"WITH "cheese" ("id") AS (SELECT "id" FROM "cake")"#,
+                        r#"SELECT "fruit"."id", "fruit"."name", "fruit"."cake_id" FROM "fruit""#,
+                        r#"WHERE "fruit"."cake_id" IN (SELECT "id" FROM "cheese") LIMIT $1 OFFSET $2"#,
+                    ]
+                    .join(" ")
+                    .as_str(),
+                    [2u64.into(), 0u64.into()]
+                ),
+            ]
+        );
+        Ok(())
+    }
+
     #[smol_potat::test]
     #[should_panic]
     async fn error() {
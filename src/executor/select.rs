@@ -1,12 +1,15 @@
 use crate::{
-    error::*, ConnectionTrait, EntityTrait, FromQueryResult, IdenStatic, Iterable, ModelTrait,
-    PrimaryKeyToColumn, QueryResult, Select, SelectA, SelectB, SelectTwo, SelectTwoMany, Statement,
-    StreamTrait, TryGetableMany,
+    error::*, ConnectionTrait, DbBackend, EntityTrait, FromQueryResult, IdenStatic, Iterable,
+    ModelTrait, PartialModelTrait, PrimaryKeyToColumn, QueryResult, QuerySelect, Select, SelectA,
+    SelectB, SelectC, SelectD, SelectFour, SelectThree, SelectTwo, SelectTwoMany, Statement,
+    StatementOptions, StreamTrait, TryGetableMany,
 };
+use async_stream::try_stream;
 use futures::{Stream, TryStreamExt};
-use sea_query::SelectStatement;
+use sea_query::{SelectStatement, WithClause};
 use std::marker::PhantomData;
 use std::pin::Pin;
+use std::time::Duration;
 
 #[cfg(feature = "with-json")]
 use crate::JsonValue;
@@ -19,6 +22,8 @@ where
 {
     pub(crate) query: SelectStatement,
     selector: S,
+    options: StatementOptions,
+    pub(crate) with: Option<WithClause>,
 }
 
 /// Performs a raw `SELECT` operation on a model
@@ -30,6 +35,7 @@ where
     pub(crate) stmt: Statement,
     #[allow(dead_code)]
     selector: S,
+    options: StatementOptions,
 }
 
 /// A Trait for any type that can perform SELECT queries
@@ -80,6 +86,29 @@ where
     model: PhantomData<(M, N)>,
 }
 
+/// Defines a type to get three Models
+#[derive(Clone, Debug)]
+pub struct SelectThreeModel<M, N, O>
+where
+    M: FromQueryResult,
+    N: FromQueryResult,
+    O: FromQueryResult,
+{
+    model: PhantomData<(M, N, O)>,
+}
+
+/// Defines a type to get four Models
+#[derive(Clone, Debug)]
+pub struct SelectFourModel<M, N, O, P>
+where
+    M: FromQueryResult,
+    N: FromQueryResult,
+    O: FromQueryResult,
+    P: FromQueryResult,
+{
+    model: PhantomData<(M, N, O, P)>,
+}
+
 impl<T, C> SelectorTrait for SelectGetableValue<T, C>
 where
     T: TryGetableMany,
@@ -130,6 +159,42 @@ where
     }
 }
 
+impl<M, N, O> SelectorTrait for SelectThreeModel<M, N, O>
+where
+    M: FromQueryResult + Sized,
+    N: FromQueryResult + Sized,
+    O: FromQueryResult + Sized,
+{
+    type Item = (M, Option<N>, Option<O>);
+
+    fn from_raw_query_result(res: QueryResult) -> Result<Self::Item, DbErr> {
+        Ok((
+            M::from_query_result(&res, SelectA.as_str())?,
+            N::from_query_result_optional(&res, SelectB.as_str())?,
+            O::from_query_result_optional(&res, SelectC.as_str())?,
+        ))
+    }
+}
+
+impl<M, N, O, P> SelectorTrait for SelectFourModel<M, N, O, P>
+where
+    M: FromQueryResult + Sized,
+    N: FromQueryResult + Sized,
+    O: FromQueryResult + Sized,
+    P: FromQueryResult + Sized,
+{
+    type Item = (M, Option<N>, Option<O>, Option<P>);
+
+    fn from_raw_query_result(res: QueryResult) -> Result<Self::Item, DbErr> {
+        Ok((
+            M::from_query_result(&res, SelectA.as_str())?,
+            N::from_query_result_optional(&res, SelectB.as_str())?,
+            O::from_query_result_optional(&res, SelectC.as_str())?,
+            P::from_query_result_optional(&res, SelectD.as_str())?,
+        ))
+    }
+}
+
 impl<E> Select<E>
 where
     E: EntityTrait,
@@ -140,6 +205,7 @@ where
         SelectorRaw {
             stmt,
             selector: SelectModel { model: PhantomData },
+            options: StatementOptions::default(),
         }
     }
 
@@ -151,15 +217,79 @@ where
         Selector {
             query: self.query,
             selector: SelectModel { model: PhantomData },
+            options: StatementOptions::default(),
+            with: self.with.into_with_clause(),
         }
     }
 
+    /// Return a [Selector] from `Self` that selects only the columns of a partial model
+    ///
+    /// ```
+    /// # use sea_orm::{error::*, tests_cfg::*, *};
+    /// #
+    /// # #[smol_potat::main]
+    /// # #[cfg(all(feature = "mock", feature = "macros"))]
+    /// # pub async fn main() -> Result<(), DbErr> {
+    /// #
+    /// # let db = MockDatabase::new(DbBackend::Postgres)
+    /// #     .append_query_results([[
+    /// #         maplit::btreemap! {
+    /// #             "name" => Into::<Value>::into("Chocolate Forest"),
+    /// #             "cake_id" => Into::<Value>::into(1),
+    /// #         },
+    /// #     ]])
+    /// #     .into_connection();
+    /// #
+    /// use sea_orm::{entity::*, query::*, tests_cfg::cake, DerivePartialModel, FromQueryResult};
+    ///
+    /// #[derive(Debug, PartialEq, FromQueryResult, DerivePartialModel)]
+    /// #[sea_orm(entity = "cake::Entity")]
+    /// struct PartialCake {
+    ///     name: String,
+    ///     #[sea_orm(from_col = "id")]
+    ///     cake_id: i32,
+    /// }
+    ///
+    /// let res: Vec<PartialCake> = cake::Entity::find()
+    ///     .into_partial_model::<PartialCake>()
+    ///     .all(&db)
+    ///     .await?;
+    ///
+    /// assert_eq!(
+    ///     res,
+    ///     [PartialCake {
+    ///         name: "Chocolate Forest".to_owned(),
+    ///         cake_id: 1,
+    ///     }]
+    /// );
+    ///
+    /// assert_eq!(
+    ///     db.into_transaction_log(),
+    ///     [Transaction::from_sql_and_values(
+    ///         DbBackend::Postgres,
+    ///         r#"SELECT "cake"."name", "cake"."id" AS "cake_id" FROM "cake""#,
+    ///         []
+    ///     )]
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_partial_model<M>(self) -> Selector<SelectModel<M>>
+    where
+        M: PartialModelTrait,
+    {
+        M::select_cols(QuerySelect::select_only(self)).into_model::<M>()
+    }
+
     /// Get a selectable Model as a [JsonValue] for SQL JSON operations
     #[cfg(feature = "with-json")]
     pub fn into_json(self) -> Selector<SelectModel<JsonValue>> {
         Selector {
             query: self.query,
             selector: SelectModel { model: PhantomData },
+            options: StatementOptions::default(),
+            with: self.with.into_with_clause(),
         }
     }
 
@@ -270,7 +400,9 @@ where
         T: TryGetableMany,
         C: strum::IntoEnumIterator + sea_query::Iden,
     {
-        Selector::<SelectGetableValue<T, C>>::with_columns(self.query)
+        let mut selector = Selector::<SelectGetableValue<T, C>>::with_columns(self.query);
+        selector.with = self.with.into_with_clause();
+        selector
     }
 
     /// ```
@@ -368,7 +500,9 @@ where
     where
         T: TryGetableMany,
     {
-        Selector::<SelectGetableTuple<T>>::into_tuple(self.query)
+        let mut selector = Selector::<SelectGetableTuple<T>>::into_tuple(self.query);
+        selector.with = self.with.into_with_clause();
+        selector
     }
 
     /// Get one Model from the SELECT query
@@ -413,6 +547,22 @@ where
         Selector {
             query: self.query,
             selector: SelectTwoModel { model: PhantomData },
+            options: StatementOptions::default(),
+            with: self.with,
+        }
+    }
+
+    /// Perform a conversion into a single struct reading the columns of each Entity
+    /// with the prefixes `A_` and `B_`, see `#[sea_orm(nested, prefix = "..")]` of [FromQueryResult]
+    pub fn into_nested_model<M>(self) -> Selector<SelectModel<M>>
+    where
+        M: FromQueryResult,
+    {
+        Selector {
+            query: self.query,
+            selector: SelectModel { model: PhantomData },
+            options: StatementOptions::default(),
+            with: self.with,
         }
     }
 
@@ -422,6 +572,8 @@ where
         Selector {
             query: self.query,
             selector: SelectTwoModel { model: PhantomData },
+            options: StatementOptions::default(),
+            with: self.with,
         }
     }
 
@@ -453,6 +605,208 @@ where
     }
 }
 
+impl<E, F, G> SelectThree<E, F, G>
+where
+    E: EntityTrait,
+    F: EntityTrait,
+    G: EntityTrait,
+{
+    /// Perform a conversion into a [SelectThreeModel]
+    pub fn into_model<M, N, O>(self) -> Selector<SelectThreeModel<M, N, O>>
+    where
+        M: FromQueryResult,
+        N: FromQueryResult,
+        O: FromQueryResult,
+    {
+        Selector {
+            query: self.query,
+            selector: SelectThreeModel { model: PhantomData },
+            options: StatementOptions::default(),
+            with: self.with,
+        }
+    }
+
+    /// Perform a conversion into a single struct reading the columns of each Entity
+    /// with the prefixes `A_`, `B_` and `C_`, see `#[sea_orm(nested, prefix = "..")]` of [FromQueryResult]
+    pub fn into_nested_model<M>(self) -> Selector<SelectModel<M>>
+    where
+        M: FromQueryResult,
+    {
+        Selector {
+            query: self.query,
+            selector: SelectModel { model: PhantomData },
+            options: StatementOptions::default(),
+            with: self.with,
+        }
+    }
+
+    /// Convert the Models into JsonValue
+    #[cfg(feature = "with-json")]
+    pub fn into_json(self) -> Selector<SelectThreeModel<JsonValue, JsonValue, JsonValue>> {
+        Selector {
+            query: self.query,
+            selector: SelectThreeModel { model: PhantomData },
+            options: StatementOptions::default(),
+            with: self.with,
+        }
+    }
+
+    /// Get one Model from the Select query
+    #[allow(clippy::type_complexity)]
+    pub async fn one<C>(
+        self,
+        db: &C,
+    ) -> Result<Option<(E::Model, Option<F::Model>, Option<G::Model>)>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        self.into_model().one(db).await
+    }
+
+    /// Get all Models from the Select query
+    #[allow(clippy::type_complexity)]
+    pub async fn all<C>(
+        self,
+        db: &C,
+    ) -> Result<Vec<(E::Model, Option<F::Model>, Option<G::Model>)>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        self.into_model().all(db).await
+    }
+
+    /// Stream the results of a Select operation on a Model
+    #[allow(clippy::type_complexity)]
+    pub async fn stream<'a: 'b, 'b, C>(
+        self,
+        db: &'a C,
+    ) -> Result<
+        impl Stream<Item = Result<(E::Model, Option<F::Model>, Option<G::Model>), DbErr>> + 'b,
+        DbErr,
+    >
+    where
+        C: ConnectionTrait + StreamTrait + Send,
+    {
+        self.into_model().stream(db).await
+    }
+}
+
+impl<E, F, G, H> SelectFour<E, F, G, H>
+where
+    E: EntityTrait,
+    F: EntityTrait,
+    G: EntityTrait,
+    H: EntityTrait,
+{
+    /// Perform a conversion into a [SelectFourModel]
+    pub fn into_model<M, N, O, P>(self) -> Selector<SelectFourModel<M, N, O, P>>
+    where
+        M: FromQueryResult,
+        N: FromQueryResult,
+        O: FromQueryResult,
+        P: FromQueryResult,
+    {
+        Selector {
+            query: self.query,
+            selector: SelectFourModel { model: PhantomData },
+            options: StatementOptions::default(),
+            with: self.with,
+        }
+    }
+
+    /// Perform a conversion into a single struct reading the columns of each Entity
+    /// with the prefixes `A_`, `B_`, `C_` and `D_`, see `#[sea_orm(nested, prefix = "..")]` of [FromQueryResult]
+    pub fn into_nested_model<M>(self) -> Selector<SelectModel<M>>
+    where
+        M: FromQueryResult,
+    {
+        Selector {
+            query: self.query,
+            selector: SelectModel { model: PhantomData },
+            options: StatementOptions::default(),
+            with: self.with,
+        }
+    }
+
+    /// Convert the Models into JsonValue
+    #[cfg(feature = "with-json")]
+    pub fn into_json(
+        self,
+    ) -> Selector<SelectFourModel<JsonValue, JsonValue, JsonValue, JsonValue>> {
+        Selector {
+            query: self.query,
+            selector: SelectFourModel { model: PhantomData },
+            options: StatementOptions::default(),
+            with: self.with,
+        }
+    }
+
+    /// Get one Model from the Select query
+    #[allow(clippy::type_complexity)]
+    pub async fn one<C>(
+        self,
+        db: &C,
+    ) -> Result<
+        Option<(
+            E::Model,
+            Option<F::Model>,
+            Option<G::Model>,
+            Option<H::Model>,
+        )>,
+        DbErr,
+    >
+    where
+        C: ConnectionTrait,
+    {
+        self.into_model().one(db).await
+    }
+
+    /// Get all Models from the Select query
+    #[allow(clippy::type_complexity)]
+    pub async fn all<C>(
+        self,
+        db: &C,
+    ) -> Result<
+        Vec<(
+            E::Model,
+            Option<F::Model>,
+            Option<G::Model>,
+            Option<H::Model>,
+        )>,
+        DbErr,
+    >
+    where
+        C: ConnectionTrait,
+    {
+        self.into_model().all(db).await
+    }
+
+    /// Stream the results of a Select operation on a Model
+    #[allow(clippy::type_complexity)]
+    pub async fn stream<'a: 'b, 'b, C>(
+        self,
+        db: &'a C,
+    ) -> Result<
+        impl Stream<
+                Item = Result<
+                    (
+                        E::Model,
+                        Option<F::Model>,
+                        Option<G::Model>,
+                        Option<H::Model>,
+                    ),
+                    DbErr,
+                >,
+            > + 'b,
+        DbErr,
+    >
+    where
+        C: ConnectionTrait + StreamTrait + Send,
+    {
+        self.into_model().stream(db).await
+    }
+}
+
 impl<E, F> SelectTwoMany<E, F>
 where
     E: EntityTrait,
@@ -467,6 +821,8 @@ where
         Selector {
             query: self.query,
             selector: SelectTwoModel { model: PhantomData },
+            options: StatementOptions::default(),
+            with: self.with,
         }
     }
 
@@ -476,6 +832,8 @@ where
         Selector {
             query: self.query,
             selector: SelectTwoModel { model: PhantomData },
+            options: StatementOptions::default(),
+            with: self.with,
         }
     }
 
@@ -490,6 +848,40 @@ where
         self.into_model().stream(db).await
     }
 
+    /// Stream the result of the operation, yielding each Model with its related Models
+    /// once all of them have been read.
+    ///
+    /// The rows are consolidated as they arrive, relying on the query being ordered by the
+    /// primary key of the Model (which `find_with_related` does), so only one group is kept in memory.
+    pub async fn stream_consolidated<'a: 'b, 'b, C>(
+        self,
+        db: &'a C,
+    ) -> Result<impl Stream<Item = Result<(E::Model, Vec<F::Model>), DbErr>> + 'b + Send, DbErr>
+    where
+        C: ConnectionTrait + StreamTrait + Send,
+    {
+        let rows = self.stream(db).await?;
+        Ok(try_stream! {
+            futures::pin_mut!(rows);
+            let mut last: Option<(E::Model, Vec<F::Model>)> = None;
+            while let Some((l, r)) = rows.try_next().await? {
+                match &mut last {
+                    Some((last_l, last_r)) if same_primary_key::<E>(last_l, &l) => {
+                        last_r.extend(r);
+                    }
+                    _ => {
+                        if let Some(group) = last.replace((l, r.into_iter().collect())) {
+                            yield group;
+                        }
+                    }
+                }
+            }
+            if let Some(group) = last {
+                yield group;
+            }
+        })
+    }
+
     /// Get all Models from the select operation
     ///
     /// > `SelectTwoMany::one()` method has been dropped (#486)
@@ -533,6 +925,8 @@ where
                 columns: PhantomData,
                 model: PhantomData,
             },
+            options: StatementOptions::default(),
+            with: None,
         }
     }
 
@@ -544,18 +938,31 @@ where
         Selector {
             query,
             selector: SelectGetableTuple { model: PhantomData },
+            options: StatementOptions::default(),
+            with: None,
         }
     }
 
+    /// Set the timeout of the query, overriding the connection's statement timeout
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.options = self.options.timeout(timeout);
+        self
+    }
+
+    pub(crate) fn with_options(mut self, options: StatementOptions) -> Self {
+        self.options = options;
+        self
+    }
+
     fn into_selector_raw<C>(self, db: &C) -> SelectorRaw<S>
     where
         C: ConnectionTrait,
     {
-        let builder = db.get_database_backend();
-        let stmt = builder.build(&self.query);
+        let stmt = build_select(db.get_database_backend(), &self.with, &self.query);
         SelectorRaw {
             stmt,
             selector: self.selector,
+            options: self.options,
         }
     }
 
@@ -602,6 +1009,7 @@ where
         SelectorRaw {
             stmt,
             selector: SelectModel { model: PhantomData },
+            options: StatementOptions::default(),
         }
     }
 
@@ -618,6 +1026,7 @@ where
                 columns: PhantomData,
                 model: PhantomData,
             },
+            options: StatementOptions::default(),
         }
     }
 
@@ -692,6 +1101,7 @@ where
         SelectorRaw {
             stmt: self.stmt,
             selector: SelectModel { model: PhantomData },
+            options: self.options,
         }
     }
 
@@ -756,9 +1166,21 @@ where
         SelectorRaw {
             stmt: self.stmt,
             selector: SelectModel { model: PhantomData },
+            options: self.options,
         }
     }
 
+    /// Set the timeout of the query, overriding the connection's statement timeout
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.options = self.options.timeout(timeout);
+        self
+    }
+
+    pub(crate) fn with_options(mut self, options: StatementOptions) -> Self {
+        self.options = options;
+        self
+    }
+
     /// Get an item from the Select query
     /// ```
     /// # use sea_orm::{error::*, tests_cfg::*, *};
@@ -803,7 +1225,7 @@ where
     where
         C: ConnectionTrait,
     {
-        let row = db.query_one(self.stmt).await?;
+        let row = db.query_one_with_options(self.stmt, self.options).await?;
         match row {
             Some(row) => Ok(Some(S::from_raw_query_result(row)?)),
             None => Ok(None),
@@ -854,7 +1276,7 @@ where
     where
         C: ConnectionTrait,
     {
-        let rows = db.query_all(self.stmt).await?;
+        let rows = db.query_all_with_options(self.stmt, self.options).await?;
         let mut models = Vec::new();
         for row in rows.into_iter() {
             models.push(S::from_raw_query_result(row)?);
@@ -879,6 +1301,18 @@ where
     }
 }
 
+/// Build a select statement, preceded by its `WITH` clause if any
+pub(crate) fn build_select(
+    db_backend: DbBackend,
+    with: &Option<WithClause>,
+    query: &SelectStatement,
+) -> Statement {
+    match with {
+        Some(with) => db_backend.build(&with.clone().query(query.clone())),
+        None => db_backend.build(query),
+    }
+}
+
 fn consolidate_query_result<L, R>(
     rows: Vec<(L::Model, Option<R::Model>)>,
 ) -> Vec<(L::Model, Vec<R::Model>)>
@@ -889,17 +1323,7 @@ where
     let mut acc: Vec<(L::Model, Vec<R::Model>)> = Vec::new();
     for (l, r) in rows {
         if let Some((last_l, last_r)) = acc.last_mut() {
-            let mut same_l = true;
-            for pk_col in <L::PrimaryKey as Iterable>::iter() {
-                let col = pk_col.into_column();
-                let val = l.get(col);
-                let last_val = last_l.get(col);
-                if !val.eq(&last_val) {
-                    same_l = false;
-                    break;
-                }
-            }
-            if same_l {
+            if same_primary_key::<L>(last_l, &l) {
                 if let Some(r) = r {
                     last_r.push(r);
                     continue;
@@ -914,3 +1338,13 @@ where
     }
     acc
 }
+
+fn same_primary_key<E>(a: &E::Model, b: &E::Model) -> bool
+where
+    E: EntityTrait,
+{
+    <E::PrimaryKey as Iterable>::iter().all(|pk_col| {
+        let col = pk_col.into_column();
+        a.get(col) == b.get(col)
+    })
+}
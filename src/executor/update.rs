@@ -1,14 +1,17 @@
 use crate::{
-    error::*, ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, IntoActiveModel,
-    Iterable, PrimaryKeyTrait, SelectModel, SelectorRaw, UpdateMany, UpdateOne,
+    error::*, ActiveModelTrait, ColumnTrait, ConnectionTrait, DbBackend, EntityTrait,
+    IntoActiveModel, Iterable, PrimaryKeyTrait, SelectModel, SelectorRaw, StatementOptions,
+    UpdateMany, UpdateOne,
 };
 use sea_query::{Expr, FromValueTuple, Query, UpdateStatement};
+use std::time::Duration;
 
 /// Defines an update operation
 #[derive(Clone, Debug)]
 pub struct Updater {
     query: UpdateStatement,
     check_record_exists: bool,
+    options: StatementOptions,
 }
 
 /// The result of an update operation on an ActiveModel
@@ -45,6 +48,17 @@ where
     {
         Updater::new(self.query).exec(db).await
     }
+
+    /// Execute an update operation on multiple ActiveModels and return the updated models
+    /// using `RETURNING` syntax. Returns [DbErr::BackendNotSupported] on MySQL
+    pub async fn exec_with_returning<C>(self, db: &'a C) -> Result<Vec<E::Model>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Updater::new(self.query)
+            .exec_update_with_returning::<E, _>(db)
+            .await
+    }
 }
 
 impl Updater {
@@ -53,9 +67,16 @@ impl Updater {
         Self {
             query,
             check_record_exists: false,
+            options: StatementOptions::default(),
         }
     }
 
+    /// Set the timeout of the update, overriding the connection's statement timeout
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.options = self.options.timeout(timeout);
+        self
+    }
+
     /// Check if a record exists on the ActiveModel to perform the update operation on
     pub fn check_record_exists(mut self) -> Self {
         self.check_record_exists = true;
@@ -72,7 +93,7 @@ impl Updater {
         }
         let builder = db.get_database_backend();
         let statement = builder.build(&self.query);
-        let result = db.execute(statement).await?;
+        let result = db.execute_with_options(statement, self.options).await?;
         if self.check_record_exists && result.rows_affected() == 0 {
             return Err(DbErr::RecordNotUpdated);
         }
@@ -81,6 +102,32 @@ impl Updater {
         })
     }
 
+    /// Execute an update operation and return the updated models using `RETURNING` syntax.
+    /// Returns [DbErr::BackendNotSupported] on MySQL
+    pub async fn exec_update_with_returning<E, C>(mut self, db: &C) -> Result<Vec<E::Model>, DbErr>
+    where
+        E: EntityTrait,
+        C: ConnectionTrait,
+    {
+        let db_backend = db.get_database_backend();
+        if db_backend == DbBackend::MySql {
+            return Err(DbErr::BackendNotSupported {
+                db: "MySQL",
+                ctx: "UPDATE RETURNING",
+            });
+        }
+        if self.is_noop() {
+            return Ok(Vec::new());
+        }
+        let returning =
+            Query::returning().exprs(E::Column::iter().map(|c| c.select_as(Expr::col(c))));
+        self.query.returning(returning);
+        SelectorRaw::<SelectModel<E::Model>>::from_statement(db_backend.build(&self.query))
+            .with_options(self.options)
+            .all(db)
+            .await
+    }
+
     async fn exec_update_and_return_updated<A, C>(
         mut self,
         model: A,
@@ -143,7 +190,12 @@ where
         Some(val) => ValueType::<A>::from_value_tuple(val),
         None => return Err(DbErr::UpdateGetPrimaryKey),
     };
-    let found = Entity::<A>::find_by_id(primary_key_value).one(db).await?;
+    // Read the row just updated from the primary, as a read replica may lag behind
+    let found = Entity::<A>::find_by_id(primary_key_value)
+        .into_model()
+        .with_options(StatementOptions::new().read_from_primary())
+        .one(db)
+        .await?;
     // If we cannot select the updated row from db by the cached primary key
     match found {
         Some(model) => Ok(model),
@@ -314,4 +366,92 @@ mod tests {
 
         Ok(())
     }
+
+    #[smol_potat::test]
+    async fn update_timeout() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_exec_results([MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
+            .into_connection();
+
+        let query = cake::Entity::update_many()
+            .col_expr(cake::Column::Name, Expr::value("Cheese Cake"))
+            .into_query();
+        Updater::new(query)
+            .timeout(std::time::Duration::from_secs(5))
+            .exec(&db)
+            .await?;
+
+        // The timeout is passed to the connection beside the statement, which is unchanged
+        assert_eq!(
+            db.into_transaction_log(),
+            [Transaction::from_sql_and_values(
+                DbBackend::Postgres,
+                r#"UPDATE "cake" SET "name" = $1"#,
+                ["Cheese Cake".into()]
+            )]
+        );
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn update_many_with_returning() -> Result<(), DbErr> {
+        let updated_cakes = vec![
+            cake::Model {
+                id: 1,
+                name: "Cheese Cake".to_owned(),
+            },
+            cake::Model {
+                id: 2,
+                name: "Cheese Cake".to_owned(),
+            },
+        ];
+
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results([updated_cakes.clone()])
+            .into_connection();
+
+        assert_eq!(
+            cake::Entity::update_many()
+                .col_expr(cake::Column::Name, Expr::value("Cheese Cake"))
+                .filter(cake::Column::Id.lt(3))
+                .exec_with_returning(&db)
+                .await?,
+            updated_cakes
+        );
+
+        assert_eq!(
+            db.into_transaction_log(),
+            [Transaction::from_sql_and_values(
+                DbBackend::Postgres,
+                r#"UPDATE "cake" SET "name" = $1 WHERE "cake"."id" < $2 RETURNING "id", "name""#,
+                ["Cheese Cake".into(), 3i32.into()]
+            )]
+        );
+
+        let db = MockDatabase::new(DbBackend::MySql).into_connection();
+
+        assert_eq!(
+            cake::Entity::update_many()
+                .col_expr(cake::Column::Name, Expr::value("Cheese Cake"))
+                .exec_with_returning(&db)
+                .await,
+            Err(DbErr::BackendNotSupported {
+                db: "MySQL",
+                ctx: "UPDATE RETURNING",
+            })
+        );
+        assert_eq!(
+            cake::Entity::delete_many().exec_with_returning(&db).await,
+            Err(DbErr::BackendNotSupported {
+                db: "MySQL",
+                ctx: "DELETE RETURNING",
+            })
+        );
+
+        Ok(())
+    }
 }
@@ -336,6 +336,8 @@ pub mod schema;
 #[doc(hidden)]
 #[cfg(all(feature = "macros", feature = "tests-cfg"))]
 pub mod tests_cfg;
+#[cfg(feature = "tracing-spans")]
+mod tracing_spans;
 mod util;
 
 pub use database::*;
@@ -350,8 +352,8 @@ pub use schema::*;
 pub use sea_orm_macros::{
     DeriveActiveEnum, DeriveActiveModel, DeriveActiveModelBehavior, DeriveColumn,
     DeriveCustomColumn, DeriveEntity, DeriveEntityModel, DeriveIntoActiveModel,
-    DeriveMigrationName, DeriveModel, DerivePrimaryKey, DeriveRelation, FromJsonQueryResult,
-    FromQueryResult,
+    DeriveMigrationName, DeriveModel, DerivePartialModel, DerivePrimaryKey, DeriveRelation,
+    FromJsonQueryResult, FromQueryResult,
 };
 
 pub use sea_query;
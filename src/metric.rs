@@ -8,25 +8,86 @@ pub(crate) use inner::metric;
 #[derive(Debug)]
 /// Query execution infos
 pub struct Info<'a> {
-    /// Query executiuon duration
+    /// Query executiuon duration, not including the time spent waiting for a connection
     pub elapsed: Duration,
+    /// Time spent waiting for a connection from the pool, or for the connection lock inside a transaction
+    pub acquire_elapsed: Duration,
     /// Query data
     pub statement: &'a crate::Statement,
     /// Query execution failed
     pub failed: bool,
+    /// Number of rows returned by a query, or affected by an execution. `None` if the query failed
+    pub rows: Option<u64>,
+    /// The [`ConnectionTrait`](crate::ConnectionTrait) or [`StreamTrait`](crate::StreamTrait) method that ran the query
+    pub kind: OperationKind,
+    /// Query was run inside a transaction
+    pub in_transaction: bool,
+}
+
+/// The connection method that ran a query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OperationKind {
+    /// [`ConnectionTrait::execute`](crate::ConnectionTrait::execute)
+    Execute,
+    /// [`ConnectionTrait::query_one`](crate::ConnectionTrait::query_one)
+    QueryOne,
+    /// [`ConnectionTrait::query_all`](crate::ConnectionTrait::query_all)
+    QueryAll,
+    /// [`StreamTrait::stream`](crate::StreamTrait::stream)
+    Stream,
+}
+
+/// Count the rows in the result of a query
+#[cfg(feature = "sqlx-dep")]
+pub(crate) trait Rows {
+    fn rows(&self) -> u64;
+}
+
+#[cfg(feature = "sqlx-dep")]
+impl Rows for crate::ExecResult {
+    fn rows(&self) -> u64 {
+        self.rows_affected()
+    }
+}
+
+#[cfg(feature = "sqlx-dep")]
+impl Rows for Option<crate::QueryResult> {
+    fn rows(&self) -> u64 {
+        self.is_some() as u64
+    }
+}
+
+#[cfg(feature = "sqlx-dep")]
+impl Rows for Vec<crate::QueryResult> {
+    fn rows(&self) -> u64 {
+        self.len() as u64
+    }
 }
 
 mod inner {
     #[allow(unused_macros)]
     macro_rules! metric {
-        ($metric_callback:expr, $stmt:expr, $code:block) => {{
+        ($metric_callback:expr, $stmt:expr, $kind:expr, $acquire_elapsed:expr, $in_transaction:expr, $code:block) => {{
             let _start = $metric_callback.is_some().then(std::time::SystemTime::now);
+            #[cfg(feature = "tracing-spans")]
+            let res = {
+                use tracing::Instrument;
+                let span = crate::tracing_spans::statement_span($stmt, $kind, $in_transaction);
+                let res = async { $code }.instrument(span.clone()).await;
+                crate::tracing_spans::record_result(&span, &res);
+                res
+            };
+            #[cfg(not(feature = "tracing-spans"))]
             let res = $code;
             if let (Some(_start), Some(callback)) = (_start, $metric_callback.as_deref()) {
                 let info = crate::metric::Info {
                     elapsed: _start.elapsed().unwrap_or_default(),
+                    acquire_elapsed: $acquire_elapsed,
                     statement: $stmt,
                     failed: res.is_err(),
+                    rows: res.as_ref().ok().map(crate::metric::Rows::rows),
+                    kind: $kind,
+                    in_transaction: $in_transaction,
                 };
                 callback(&info);
             }
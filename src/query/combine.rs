@@ -1,9 +1,12 @@
 use crate::{
-    ColumnTrait, EntityTrait, IdenStatic, Iterable, QueryTrait, Select, SelectTwo, SelectTwoMany,
+    ColumnTrait, EntityTrait, IdenStatic, Iterable, QueryTrait, Select, SelectFour, SelectThree,
+    SelectTwo, SelectTwoMany,
 };
 use core::marker::PhantomData;
 pub use sea_query::JoinType;
-use sea_query::{Alias, ColumnRef, Iden, Order, SeaRc, SelectExpr, SelectStatement, SimpleExpr};
+use sea_query::{
+    Alias, ColumnRef, Iden, Order, SeaRc, SelectExpr, SelectStatement, SimpleExpr, WithClause,
+};
 
 macro_rules! select_def {
     ( $ident: ident, $str: expr ) => {
@@ -27,6 +30,8 @@ macro_rules! select_def {
 
 select_def!(SelectA, "A_");
 select_def!(SelectB, "B_");
+select_def!(SelectC, "C_");
+select_def!(SelectD, "D_");
 
 impl<E> Select<E>
 where
@@ -78,7 +83,7 @@ where
         F: EntityTrait,
     {
         self = self.apply_alias(SelectA.as_str());
-        SelectTwo::new(self.into_query())
+        SelectTwo::new(self.query, self.with.into_with_clause())
     }
 
     /// Makes a SELECT operation in conjunction to another relation
@@ -87,7 +92,7 @@ where
         F: EntityTrait,
     {
         self = self.apply_alias(SelectA.as_str());
-        SelectTwoMany::new(self.into_query())
+        SelectTwoMany::new(self.query, self.with.into_with_clause())
     }
 }
 
@@ -96,43 +101,108 @@ where
     E: EntityTrait,
     F: EntityTrait,
 {
-    pub(crate) fn new(query: SelectStatement) -> Self {
-        Self::new_without_prepare(query).prepare_select()
+    pub(crate) fn new(query: SelectStatement, with: Option<WithClause>) -> Self {
+        Self::new_without_prepare(query, with).prepare_select()
     }
 
-    pub(crate) fn new_without_prepare(query: SelectStatement) -> Self {
+    pub(crate) fn new_without_prepare(query: SelectStatement, with: Option<WithClause>) -> Self {
         Self {
             query,
             entity: PhantomData,
+            with,
         }
     }
 
     fn prepare_select(mut self) -> Self {
-        prepare_select_two::<F, Self>(&mut self);
+        prepare_select_col::<F, Self, _>(&mut self, SelectB);
         self
     }
+
+    /// Selects a third Entity, joined in the query, and returns it together with the two Entities from `Self`
+    pub fn select_also<G>(self, _: G) -> SelectThree<E, F, G>
+    where
+        G: EntityTrait,
+    {
+        SelectThree::new(self.query, self.with)
+    }
 }
 
-impl<E, F> SelectTwoMany<E, F>
+impl<E, F, G> SelectThree<E, F, G>
+where
+    E: EntityTrait,
+    F: EntityTrait,
+    G: EntityTrait,
+{
+    pub(crate) fn new(query: SelectStatement, with: Option<WithClause>) -> Self {
+        Self {
+            query,
+            entity: PhantomData,
+            with,
+        }
+        .prepare_select()
+    }
+
+    fn prepare_select(mut self) -> Self {
+        prepare_select_col::<G, Self, _>(&mut self, SelectC);
+        self
+    }
+
+    /// Selects a fourth Entity, joined in the query, and returns it together with the three Entities from `Self`
+    pub fn select_also<H>(self, _: H) -> SelectFour<E, F, G, H>
+    where
+        H: EntityTrait,
+    {
+        SelectFour::new(self.query, self.with)
+    }
+}
+
+impl<E, F, G, H> SelectFour<E, F, G, H>
 where
     E: EntityTrait,
     F: EntityTrait,
+    G: EntityTrait,
+    H: EntityTrait,
 {
-    pub(crate) fn new(query: SelectStatement) -> Self {
+    pub(crate) fn new(query: SelectStatement, with: Option<WithClause>) -> Self {
         Self {
             query,
             entity: PhantomData,
+            with,
         }
         .prepare_select()
-        .prepare_order_by()
     }
 
     fn prepare_select(mut self) -> Self {
-        prepare_select_two::<F, Self>(&mut self);
+        prepare_select_col::<H, Self, _>(&mut self, SelectD);
         self
     }
+}
+
+impl<E, F> SelectTwoMany<E, F>
+where
+    E: EntityTrait,
+    F: EntityTrait,
+{
+    pub(crate) fn new(query: SelectStatement, with: Option<WithClause>) -> Self {
+        Self::new_without_prepare(query, with)
+            .prepare_select()
+            .prepare_order_by()
+    }
 
-    fn prepare_order_by(mut self) -> Self {
+    pub(crate) fn new_without_prepare(query: SelectStatement, with: Option<WithClause>) -> Self {
+        Self {
+            query,
+            entity: PhantomData,
+            with,
+        }
+    }
+
+    fn prepare_select(mut self) -> Self {
+        prepare_select_col::<F, Self, _>(&mut self, SelectB);
+        self
+    }
+
+    pub(crate) fn prepare_order_by(mut self) -> Self {
         for col in <E::PrimaryKey as Iterable>::iter() {
             self.query.order_by((E::default(), col), Order::Asc);
         }
@@ -140,13 +210,14 @@ where
     }
 }
 
-fn prepare_select_two<F, S>(selector: &mut S)
+fn prepare_select_col<F, S, A>(selector: &mut S, alias: A)
 where
     F: EntityTrait,
     S: QueryTrait<QueryStatement = SelectStatement>,
+    A: IdenStatic,
 {
     for col in <F::Column as Iterable>::iter() {
-        let alias = format!("{}{}", SelectB.as_str(), col.as_str());
+        let alias = format!("{}{}", alias.as_str(), col.as_str());
         selector.query().expr(SelectExpr {
             expr: col.select_as(col.into_expr()),
             alias: Some(SeaRc::new(Alias::new(&alias))),
@@ -157,7 +228,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::tests_cfg::{cake, fruit};
+    use crate::tests_cfg::{cake, cake_filling, filling, fruit};
     use crate::{ColumnTrait, DbBackend, EntityTrait, QueryFilter, QuerySelect, QueryTrait};
 
     #[test]
@@ -243,4 +314,52 @@ mod tests {
             ].join(" ")
         );
     }
+
+    #[test]
+    fn select_also_3() {
+        assert_eq!(
+            cake::Entity::find()
+                .left_join(fruit::Entity)
+                .left_join(filling::Entity)
+                .select_also(fruit::Entity)
+                .select_also(filling::Entity)
+                .filter(fruit::Column::Id.eq(2))
+                .build(DbBackend::MySql)
+                .to_string(),
+            [
+                "SELECT `cake`.`id` AS `A_id`, `cake`.`name` AS `A_name`,",
+                "`fruit`.`id` AS `B_id`, `fruit`.`name` AS `B_name`, `fruit`.`cake_id` AS `B_cake_id`,",
+                "`filling`.`id` AS `C_id`, `filling`.`name` AS `C_name`, `filling`.`vendor_id` AS `C_vendor_id`",
+                "FROM `cake`",
+                "LEFT JOIN `fruit` ON `cake`.`id` = `fruit`.`cake_id`",
+                "LEFT JOIN `cake_filling` ON `cake`.`id` = `cake_filling`.`cake_id`",
+                "LEFT JOIN `filling` ON `cake_filling`.`filling_id` = `filling`.`id`",
+                "WHERE `fruit`.`id` = 2",
+            ].join(" ")
+        );
+    }
+
+    #[test]
+    fn select_also_4() {
+        assert_eq!(
+            cake::Entity::find()
+                .left_join(fruit::Entity)
+                .left_join(filling::Entity)
+                .select_also(fruit::Entity)
+                .select_also(cake_filling::Entity)
+                .select_also(filling::Entity)
+                .build(DbBackend::MySql)
+                .to_string(),
+            [
+                "SELECT `cake`.`id` AS `A_id`, `cake`.`name` AS `A_name`,",
+                "`fruit`.`id` AS `B_id`, `fruit`.`name` AS `B_name`, `fruit`.`cake_id` AS `B_cake_id`,",
+                "`cake_filling`.`cake_id` AS `C_cake_id`, `cake_filling`.`filling_id` AS `C_filling_id`,",
+                "`filling`.`id` AS `D_id`, `filling`.`name` AS `D_name`, `filling`.`vendor_id` AS `D_vendor_id`",
+                "FROM `cake`",
+                "LEFT JOIN `fruit` ON `cake`.`id` = `fruit`.`cake_id`",
+                "LEFT JOIN `cake_filling` ON `cake`.`id` = `cake_filling`.`cake_id`",
+                "LEFT JOIN `filling` ON `cake_filling`.`filling_id` = `filling`.`id`",
+            ].join(" ")
+        );
+    }
 }
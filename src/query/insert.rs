@@ -1,9 +1,9 @@
 use crate::{
-    ActiveModelTrait, ActiveValue, ColumnTrait, EntityName, EntityTrait, IntoActiveModel, Iterable,
-    PrimaryKeyTrait, QueryTrait,
+    ActiveModelTrait, ActiveValue, ColumnTrait, Condition, DbErr, EntityName, EntityTrait,
+    IdenStatic, IntoActiveModel, Iterable, PrimaryKeyToColumn, PrimaryKeyTrait, QueryTrait,
 };
 use core::marker::PhantomData;
-use sea_query::{Expr, InsertStatement, OnConflict, ValueTuple};
+use sea_query::{Expr, InsertStatement, OnConflict, Value, ValueTuple};
 
 /// Performs INSERT operations on a ActiveModel
 #[derive(Debug)]
@@ -14,6 +14,11 @@ where
     pub(crate) query: InsertStatement,
     pub(crate) columns: Vec<bool>,
     pub(crate) primary_key: Option<ValueTuple>,
+    pub(crate) primary_keys: Vec<Option<ValueTuple>>,
+    /// The values of the last added model, by column
+    pub(crate) values: Vec<Option<Value>>,
+    /// Selects the upserted row when its primary key is not known
+    pub(crate) conflict_target: Option<Condition>,
     pub(crate) model: PhantomData<A>,
 }
 
@@ -38,6 +43,9 @@ where
                 .to_owned(),
             columns: Vec::new(),
             primary_key: None,
+            primary_keys: Vec::new(),
+            values: Vec::new(),
+            conflict_target: None,
             model: PhantomData,
         }
     }
@@ -119,6 +127,7 @@ where
         M: IntoActiveModel<A>,
     {
         let mut am: A = m.into_active_model();
+        self.primary_keys.push(am.get_primary_key_value());
         self.primary_key =
             if !<<A::Entity as EntityTrait>::PrimaryKey as PrimaryKeyTrait>::auto_increment() {
                 am.get_primary_key_value()
@@ -127,6 +136,7 @@ where
             };
         let mut columns = Vec::new();
         let mut values = Vec::new();
+        self.values.clear();
         let columns_empty = self.columns.is_empty();
         for (idx, col) in <A::Entity as EntityTrait>::Column::iter().enumerate() {
             let av = am.take(col);
@@ -139,9 +149,10 @@ where
             match av {
                 ActiveValue::Set(value) | ActiveValue::Unchanged(value) => {
                     columns.push(col);
-                    values.push(col.save_as(Expr::val(value)));
+                    values.push(col.save_as(Expr::val(value.clone())));
+                    self.values.push(Some(value));
                 }
-                ActiveValue::NotSet => {}
+                ActiveValue::NotSet => self.values.push(None),
             }
         }
         self.query.columns(columns);
@@ -208,6 +219,252 @@ where
         self.query.on_conflict(on_conflict);
         self
     }
+
+    /// Update the existing row having the same primary key as the inserted one,
+    /// setting every other column given a value in the inserted models
+    /// with `ON CONFLICT .. DO UPDATE` (`ON DUPLICATE KEY UPDATE` on MySQL).
+    /// Call it after adding the models.
+    ///
+    /// ```
+    /// use sea_orm::{entity::*, query::*, tests_cfg::cake, DbBackend};
+    ///
+    /// let orange = cake::ActiveModel {
+    ///     id: ActiveValue::set(2),
+    ///     name: ActiveValue::set("Orange".to_owned()),
+    /// };
+    /// assert_eq!(
+    ///     cake::Entity::insert(orange.clone())
+    ///         .on_conflict_primary_key()
+    ///         .build(DbBackend::Postgres)
+    ///         .to_string(),
+    ///     r#"INSERT INTO "cake" ("id", "name") VALUES (2, 'Orange') ON CONFLICT ("id") DO UPDATE SET "name" = "excluded"."name""#,
+    /// );
+    /// assert_eq!(
+    ///     cake::Entity::insert(orange)
+    ///         .on_conflict_primary_key()
+    ///         .build(DbBackend::MySql)
+    ///         .to_string(),
+    ///     r#"INSERT INTO `cake` (`id`, `name`) VALUES (2, 'Orange') ON DUPLICATE KEY UPDATE `name` = VALUES(`name`)"#,
+    /// );
+    /// ```
+    pub fn on_conflict_primary_key(self) -> Self {
+        let target = <A::Entity as EntityTrait>::PrimaryKey::iter()
+            .map(|key| key.into_column())
+            .collect();
+        self.on_conflict_update(target)
+    }
+
+    /// Update the existing row having the same value of a column declared with
+    /// `#[sea_orm(unique)]`, setting every other column given a value in the inserted models,
+    /// except the primary key. See [Insert::on_conflict_primary_key].
+    ///
+    /// On databases without `RETURNING`, the model returned by [Insert::exec_with_returning]
+    /// is selected by its primary key, or by the value of the unique column if the primary key is not set.
+    ///
+    /// ```
+    /// use sea_orm::{entity::*, query::*, tests_cfg::indexes, DbBackend};
+    ///
+    /// assert_eq!(
+    ///     indexes::Entity::insert(indexes::ActiveModel {
+    ///         unique_attr: ActiveValue::set(1),
+    ///         index1_attr: ActiveValue::set(2),
+    ///         index2_attr: ActiveValue::set(3),
+    ///         ..Default::default()
+    ///     })
+    ///     .on_conflict_unique(indexes::Column::UniqueAttr)?
+    ///     .build(DbBackend::Postgres)
+    ///     .to_string(),
+    ///     [
+    ///         r#"INSERT INTO "public"."indexes" ("unique_attr", "index1_attr", "index2_attr") VALUES (1, 2, 3)"#,
+    ///         r#"ON CONFLICT ("unique_attr") DO UPDATE SET"#,
+    ///         r#""index1_attr" = "excluded"."index1_attr", "index2_attr" = "excluded"."index2_attr""#,
+    ///     ]
+    ///     .join(" ")
+    /// );
+    /// # Ok::<(), sea_orm::DbErr>(())
+    /// ```
+    ///
+    /// Returns an error if the column is not declared unique.
+    pub fn on_conflict_unique(
+        self,
+        col: <A::Entity as EntityTrait>::Column,
+    ) -> Result<Self, DbErr> {
+        if !col.def().unique {
+            return Err(DbErr::Custom(format!(
+                "Column `{}` is not unique",
+                col.as_str()
+            )));
+        }
+        Ok(self.on_conflict_update(vec![col]))
+    }
+
+    fn on_conflict_update(mut self, target: Vec<<A::Entity as EntityTrait>::Column>) -> Self {
+        // The primary key identifies the upserted row even if it is auto incremented
+        if let Some(primary_key) = self.primary_keys.last().cloned().flatten() {
+            self.primary_key = Some(primary_key);
+        } else {
+            // Otherwise the row may be updated instead of inserted, so it's found by the conflicting values
+            self.conflict_target = target
+                .iter()
+                .map(|col| {
+                    let idx = <A::Entity as EntityTrait>::Column::iter()
+                        .position(|c| c.as_str() == col.as_str())?;
+                    let value = self.values.get(idx).cloned().flatten()?;
+                    Some(col.eq(value))
+                })
+                .collect::<Option<Vec<_>>>()
+                .map(|exprs| exprs.into_iter().fold(Condition::all(), Condition::add));
+        }
+        let mut update: Vec<_> = <A::Entity as EntityTrait>::Column::iter()
+            .zip(self.columns.iter())
+            .filter(|(col, has_value)| {
+                **has_value
+                    && !target.iter().any(|t| t.as_str() == col.as_str())
+                    && <A::Entity as EntityTrait>::PrimaryKey::from_column(*col).is_none()
+            })
+            .map(|(col, _)| col)
+            .collect();
+        if update.is_empty() {
+            // Set the conflicting columns to themselves, so that the row is still returned
+            update = target.clone();
+        }
+        self.on_conflict(
+            OnConflict::columns(target)
+                .update_columns(update)
+                .to_owned(),
+        )
+    }
+
+    /// Treat inserting no rows, or every row being skipped by the `ON CONFLICT` clause,
+    /// as a normal outcome instead of an error. See [TryInsert]
+    ///
+    /// ```
+    /// use sea_orm::{entity::*, query::*, sea_query::OnConflict, tests_cfg::cake, DbBackend};
+    ///
+    /// assert_eq!(
+    ///     Insert::many([cake::Model {
+    ///         id: 1,
+    ///         name: "Apple Pie".to_owned(),
+    ///     }])
+    ///     .on_conflict(OnConflict::column(cake::Column::Id).do_nothing().to_owned())
+    ///     .do_nothing()
+    ///     .build(DbBackend::Postgres)
+    ///     .to_string(),
+    ///     r#"INSERT INTO "cake" ("id", "name") VALUES (1, 'Apple Pie') ON CONFLICT ("id") DO NOTHING"#,
+    /// );
+    /// ```
+    pub fn do_nothing(self) -> TryInsert<A> {
+        TryInsert::from_insert(self)
+    }
+
+    /// Alias of [Insert::do_nothing], reads better when the models may be empty
+    pub fn on_empty_do_nothing(self) -> TryInsert<A> {
+        TryInsert::from_insert(self)
+    }
+}
+
+/// Performs INSERT operations on ActiveModels, treating an empty insert
+/// or one where every row conflicts as an outcome rather than an error
+#[derive(Debug)]
+pub struct TryInsert<A>
+where
+    A: ActiveModelTrait,
+{
+    pub(crate) insert_struct: Insert<A>,
+}
+
+impl<A> Default for TryInsert<A>
+where
+    A: ActiveModelTrait,
+{
+    fn default() -> Self {
+        Self {
+            insert_struct: Insert::new(),
+        }
+    }
+}
+
+impl<A> TryInsert<A>
+where
+    A: ActiveModelTrait,
+{
+    /// Wrap an [Insert]
+    pub fn from_insert(insert: Insert<A>) -> Self {
+        Self {
+            insert_struct: insert,
+        }
+    }
+
+    /// Insert one Model or ActiveModel
+    pub fn one<M>(m: M) -> Self
+    where
+        M: IntoActiveModel<A>,
+    {
+        Self::default().add(m)
+    }
+
+    /// Insert many Model or ActiveModel
+    pub fn many<M, I>(models: I) -> Self
+    where
+        M: IntoActiveModel<A>,
+        I: IntoIterator<Item = M>,
+    {
+        Self::default().add_many(models)
+    }
+
+    /// Add a Model to Self
+    ///
+    /// # Panics
+    ///
+    /// Panics if the column value has discrepancy across rows
+    #[allow(clippy::should_implement_trait)]
+    pub fn add<M>(mut self, m: M) -> Self
+    where
+        M: IntoActiveModel<A>,
+    {
+        self.insert_struct = self.insert_struct.add(m);
+        self
+    }
+
+    /// Add many Models to Self
+    pub fn add_many<M, I>(mut self, models: I) -> Self
+    where
+        M: IntoActiveModel<A>,
+        I: IntoIterator<Item = M>,
+    {
+        self.insert_struct = self.insert_struct.add_many(models);
+        self
+    }
+
+    /// On conflict, see [Insert::on_conflict]
+    pub fn on_conflict(mut self, on_conflict: OnConflict) -> Self {
+        self.insert_struct = self.insert_struct.on_conflict(on_conflict);
+        self
+    }
+
+    /// Check if no Model has been added
+    pub fn is_empty(&self) -> bool {
+        self.insert_struct.primary_keys.is_empty()
+    }
+}
+
+impl<A> QueryTrait for TryInsert<A>
+where
+    A: ActiveModelTrait,
+{
+    type QueryStatement = InsertStatement;
+
+    fn query(&mut self) -> &mut InsertStatement {
+        &mut self.insert_struct.query
+    }
+
+    fn as_query(&self) -> &InsertStatement {
+        &self.insert_struct.query
+    }
+
+    fn into_query(self) -> InsertStatement {
+        self.insert_struct.query
+    }
 }
 
 impl<A> QueryTrait for Insert<A>
@@ -480,4 +737,40 @@ mod tests {
 
         Ok(())
     }
+
+    #[smol_potat::test]
+    async fn insert_10() -> Result<(), DbErr> {
+        use crate::{DbBackend, MockDatabase, Statement, Transaction, TryInsertResult};
+
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results([Vec::<cake::Model>::new()])
+            .into_connection();
+
+        let res = cake::Entity::insert_many(Vec::<cake::ActiveModel>::new())
+            .on_empty_do_nothing()
+            .exec(&db)
+            .await?;
+        assert!(matches!(res, TryInsertResult::Empty));
+
+        let res = Insert::one(cake::Model {
+            id: 1,
+            name: "Apple Pie".to_owned(),
+        })
+        .on_conflict(OnConflict::column(cake::Column::Id).do_nothing().to_owned())
+        .do_nothing()
+        .exec(&db)
+        .await?;
+        assert!(matches!(res, TryInsertResult::Conflicted));
+
+        assert_eq!(
+            db.into_transaction_log(),
+            [Transaction::one(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                r#"INSERT INTO "cake" ("id", "name") VALUES ($1, $2) ON CONFLICT ("id") DO NOTHING RETURNING "id""#,
+                [1.into(), "Apple Pie".into()]
+            ))]
+        );
+
+        Ok(())
+    }
 }
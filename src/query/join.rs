@@ -1,9 +1,11 @@
 use crate::{
-    join_tbl_on_condition, unpack_table_ref, ColumnTrait, EntityTrait, IdenStatic, Iterable,
-    Linked, QuerySelect, Related, Select, SelectA, SelectB, SelectTwo, SelectTwoMany,
+    join_condition, join_tbl_on_condition, unpack_table_ref, ColumnTrait, CommonTableExpressions,
+    EntityTrait, IdenStatic, Iterable, Linked, QuerySelect, Related, Select, SelectA, SelectB,
+    SelectTwo, SelectTwoMany,
 };
+use core::marker::PhantomData;
 pub use sea_query::JoinType;
-use sea_query::{Alias, Condition, Expr, IntoIden, SeaRc, SelectExpr};
+use sea_query::{Alias, Condition, Expr, IntoIden, SeaRc, SelectExpr, SelectStatement};
 
 impl<E> Select<E>
 where
@@ -64,12 +66,85 @@ where
 
     /// Left Join with a Linked Entity and select both Entity.
     pub fn find_also_linked<L, T>(self, l: L) -> SelectTwo<E, T>
+    where
+        L: Linked<FromEntity = E, ToEntity = T>,
+        T: EntityTrait,
+    {
+        let select = self.select_linked(l);
+        SelectTwo::new_without_prepare(select.query, select.with.into_with_clause())
+    }
+
+    /// Left Join with a Linked Entity and select the linked Entity as a `Vec`
+    pub fn find_with_linked<L, T>(self, l: L) -> SelectTwoMany<E, T>
+    where
+        L: Linked<FromEntity = E, ToEntity = T>,
+        T: EntityTrait,
+    {
+        let select = self.select_linked(l);
+        SelectTwoMany::new_without_prepare(select.query, select.with.into_with_clause())
+            .prepare_order_by()
+    }
+
+    /// Filter the Entities having at least one related Entity matching the given query,
+    /// with a correlated `EXISTS` subquery
+    ///
+    /// ```
+    /// use sea_orm::{entity::*, query::*, tests_cfg::{cake, filling}, DbBackend};
+    ///
+    /// assert_eq!(
+    ///     cake::Entity::find()
+    ///         .filter_related_exists(filling::Entity, |q| {
+    ///             q.filter(filling::Column::VendorId.eq(1))
+    ///         })
+    ///         .build(DbBackend::MySql)
+    ///         .to_string(),
+    ///     [
+    ///         "SELECT `cake`.`id`, `cake`.`name` FROM `cake`",
+    ///         "WHERE EXISTS(SELECT 1 FROM `filling`",
+    ///         "INNER JOIN `cake_filling` ON `cake_filling`.`filling_id` = `filling`.`id`",
+    ///         "WHERE `cake`.`id` = `cake_filling`.`cake_id` AND `filling`.`vendor_id` = 1)",
+    ///     ]
+    ///     .join(" ")
+    /// );
+    /// ```
+    ///
+    /// The related table of a self-referencing relation is aliased as `r0` in the subquery.
+    /// The common table expressions added in the subquery with [Select::with_cte] are moved to
+    /// the `WITH` clause of the query.
+    pub fn filter_related_exists<R, F>(mut self, _: R, f: F) -> Self
+    where
+        R: EntityTrait,
+        E: Related<R>,
+        F: FnOnce(Select<R>) -> Select<R>,
+    {
+        let sub_query = related_sub_query::<E, R, F>(f, &mut self.with);
+        self.query.and_where(Expr::exists(sub_query));
+        self
+    }
+
+    /// Filter the Entities having no related Entity matching the given query,
+    /// with a correlated `NOT EXISTS` subquery
+    pub fn filter_related_not_exists<R, F>(mut self, _: R, f: F) -> Self
+    where
+        R: EntityTrait,
+        E: Related<R>,
+        F: FnOnce(Select<R>) -> Select<R>,
+    {
+        let sub_query = related_sub_query::<E, R, F>(f, &mut self.with);
+        self.query.and_where(Expr::exists(sub_query).not());
+        self
+    }
+
+    /// Left Join the Entities along the Linked path, selecting the columns of the last one
+    fn select_linked<L, T>(self, l: L) -> Self
     where
         L: Linked<FromEntity = E, ToEntity = T>,
         T: EntityTrait,
     {
         let mut slf = self;
-        for (i, mut rel) in l.link().into_iter().enumerate() {
+        let link = l.link();
+        let last = Alias::new(&format!("r{}", link.len() - 1)).into_iden();
+        for (i, mut rel) in link.into_iter().enumerate() {
             let to_tbl = Alias::new(&format!("r{i}")).into_iden();
             let from_tbl = if i > 0 {
                 Alias::new(&format!("r{}", i - 1)).into_iden()
@@ -92,21 +167,58 @@ where
                 .join_as(JoinType::LeftJoin, table_ref, to_tbl, condition);
         }
         slf = slf.apply_alias(SelectA.as_str());
-        let mut select_two = SelectTwo::new_without_prepare(slf.query);
         for col in <T::Column as Iterable>::iter() {
             let alias = format!("{}{}", SelectB.as_str(), col.as_str());
-            let expr = Expr::col((
-                Alias::new(&format!("r{}", l.link().len() - 1)).into_iden(),
-                col.into_iden(),
-            ));
-            select_two.query().expr(SelectExpr {
+            let expr = Expr::col((SeaRc::clone(&last), col.into_iden()));
+            slf.query().expr(SelectExpr {
                 expr: col.select_as(expr),
                 alias: Some(SeaRc::new(Alias::new(&alias))),
                 window: None,
             });
         }
-        select_two
+        slf
+    }
+}
+
+/// Select `1` from the related Entity, joined with the junction Entity if any,
+/// correlated with the outer query through the relation.
+/// The common table expressions added by `f` are moved to the `WITH` clause of the outer query.
+fn related_sub_query<E, R, F>(f: F, with: &mut CommonTableExpressions) -> SelectStatement
+where
+    E: EntityTrait + Related<R>,
+    R: EntityTrait,
+    F: FnOnce(Select<R>) -> Select<R>,
+{
+    let mut to = E::to();
+    let mut select = R::find();
+    // Alias the related table of a self-referencing relation, otherwise it would shadow the
+    // table of the outer query and the subquery would not be correlated with the outer row
+    if E::default().table_name() == R::default().table_name()
+        && E::default().schema_name() == R::default().schema_name()
+    {
+        let alias = Alias::new("r0").into_iden();
+        to.to_tbl = to.to_tbl.alias(SeaRc::clone(&alias));
+        select = Select {
+            query: SelectStatement::new()
+                .from_as(R::default().table_ref(), alias)
+                .to_owned(),
+            entity: PhantomData,
+            with: CommonTableExpressions::default(),
+        };
     }
+    let correlation = match E::via() {
+        Some(via) => {
+            select = select.join_rev(JoinType::InnerJoin, to);
+            join_condition(via)
+        }
+        None => join_condition(to),
+    };
+    select.query.cond_where(correlation);
+    let select = f(select);
+    with.append(select.with);
+    let mut query = select.query;
+    query.clear_selects().expr(Expr::cust("1"));
+    query
 }
 
 #[cfg(test)]
@@ -117,7 +229,7 @@ mod tests {
         RelationTrait,
     };
     use pretty_assertions::assert_eq;
-    use sea_query::{Alias, Expr, IntoCondition, JoinType};
+    use sea_query::{Alias, Expr, IntoCondition, JoinType, Query};
 
     #[test]
     fn join_1() {
@@ -559,4 +671,92 @@ mod tests {
             .join(" ")
         );
     }
+
+    #[test]
+    fn join_22() {
+        assert_eq!(
+            cake::Entity::find()
+                .find_with_linked(entity_linked::CheeseCakeToFillingVendor)
+                .build(DbBackend::MySql)
+                .to_string(),
+            [
+                r#"SELECT `cake`.`id` AS `A_id`, `cake`.`name` AS `A_name`,"#,
+                r#"`r2`.`id` AS `B_id`, `r2`.`name` AS `B_name`"#,
+                r#"FROM `cake`"#,
+                r#"LEFT JOIN `cake_filling` AS `r0` ON `cake`.`id` = `r0`.`cake_id` AND `cake`.`name` LIKE '%cheese%'"#,
+                r#"LEFT JOIN `filling` AS `r1` ON `r0`.`filling_id` = `r1`.`id`"#,
+                r#"LEFT JOIN `vendor` AS `r2` ON `r1`.`vendor_id` = `r2`.`id`"#,
+                r#"ORDER BY `cake`.`id` ASC"#,
+            ]
+            .join(" ")
+        );
+    }
+
+    #[test]
+    fn join_23() {
+        assert_eq!(
+            cake::Entity::find()
+                .filter_related_exists(fruit::Entity, |q| q.filter(fruit::Column::Name.contains("apple")))
+                .filter_related_not_exists(fruit::Entity, |q| q.filter(fruit::Column::Name.contains("pear")))
+                .build(DbBackend::MySql)
+                .to_string(),
+            [
+                r#"SELECT `cake`.`id`, `cake`.`name` FROM `cake`"#,
+                r#"WHERE EXISTS(SELECT 1 FROM `fruit` WHERE `cake`.`id` = `fruit`.`cake_id` AND `fruit`.`name` LIKE '%apple%')"#,
+                r#"AND NOT EXISTS(SELECT 1 FROM `fruit` WHERE `cake`.`id` = `fruit`.`cake_id` AND `fruit`.`name` LIKE '%pear%')"#,
+            ]
+            .join(" ")
+        );
+    }
+
+    #[test]
+    fn join_24() {
+        assert_eq!(
+            filling::Entity::find()
+                .filter_related_not_exists(cake::Entity, |q| q)
+                .build(DbBackend::Postgres)
+                .to_string(),
+            [
+                r#"SELECT "filling"."id", "filling"."name", "filling"."vendor_id" FROM "filling""#,
+                r#"WHERE NOT EXISTS(SELECT 1 FROM "cake""#,
+                r#"INNER JOIN "cake_filling" ON "cake_filling"."cake_id" = "cake"."id""#,
+                r#"WHERE "filling"."id" = "cake_filling"."filling_id")"#,
+            ]
+            .join(" ")
+        );
+    }
+
+    #[test]
+    fn join_25() {
+        assert_eq!(
+            cake::Entity::find()
+                .filter_related_exists(fruit::Entity, |q| {
+                    q.with_cte(
+                        Alias::new("red"),
+                        Query::select()
+                            .column(fruit::Column::Id)
+                            .from(fruit::Entity)
+                            .and_where(fruit::Column::Name.contains("apple"))
+                            .to_owned(),
+                    )
+                    .filter(
+                        fruit::Column::Id.in_subquery(
+                            Query::select()
+                                .column(Alias::new("id"))
+                                .from(Alias::new("red"))
+                                .to_owned(),
+                        ),
+                    )
+                })
+                .build(DbBackend::Postgres)
+                .to_string(),
+            [
+                r#"WITH "red" ("id") AS (SELECT "id" FROM "fruit" WHERE "fruit"."name" LIKE '%apple%')"#,
+                r#"SELECT "cake"."id", "cake"."name" FROM "cake""#,
+                r#"WHERE EXISTS(SELECT 1 FROM "fruit" WHERE "cake"."id" = "fruit"."cake_id""#,
+                r#"AND "fruit"."id" IN (SELECT "id" FROM "red"))"#,
+            ]
+            .join(" ")
+        );
+    }
 }
@@ -1,10 +1,14 @@
 use crate::{
-    error::*, Condition, ConnectionTrait, DbErr, EntityTrait, Identity, ModelTrait, QueryFilter,
-    Related, RelationType, Select,
+    error::*, Condition, ConnectionTrait, DbBackend, DbErr, EntityTrait, Identity, Linked,
+    ModelTrait, QueryFilter, Related, RelationType, Select, Selector, SelectorTrait,
 };
 use async_trait::async_trait;
 use sea_query::{ColumnRef, DynIden, Expr, IntoColumnRef, SimpleExpr, TableRef, ValueTuple};
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    collections::{HashMap, HashSet},
+    marker::PhantomData,
+    str::FromStr,
+};
 
 /// Entity, or a Select<Entity>; to be used as parameters in [`LoaderTrait`]
 pub trait EntityOrSelect<E: EntityTrait>: Send {
@@ -51,6 +55,21 @@ pub trait LoaderTrait {
         V: EntityTrait,
         V::Model: Send + Sync,
         <<Self as LoaderTrait>::Model as ModelTrait>::Entity: Related<R>;
+
+    /// Used to eager load the Entity at the end of a Linked path
+    async fn load_linked<L, T, C>(&self, linked: L, db: &C) -> Result<Vec<Vec<T::Model>>, DbErr>
+    where
+        C: ConnectionTrait,
+        L: Linked<FromEntity = <<Self as LoaderTrait>::Model as ModelTrait>::Entity, ToEntity = T>
+            + Send,
+        T: EntityTrait,
+        T::Model: Send + Sync;
+
+    /// Used to eager load a tree of relations, described by a [`NestedLoader`]
+    async fn load_nested<L, C>(&self, loader: L, db: &C) -> Result<Vec<L::Output>, DbErr>
+    where
+        C: ConnectionTrait,
+        L: NestedLoader<<Self as LoaderTrait>::Model>;
 }
 
 impl<E> EntityOrSelect<E> for E
@@ -117,6 +136,25 @@ where
     {
         self.as_slice().load_many_to_many(stmt, via, db).await
     }
+
+    async fn load_linked<L, T, C>(&self, linked: L, db: &C) -> Result<Vec<Vec<T::Model>>, DbErr>
+    where
+        C: ConnectionTrait,
+        L: Linked<FromEntity = <<Self as LoaderTrait>::Model as ModelTrait>::Entity, ToEntity = T>
+            + Send,
+        T: EntityTrait,
+        T::Model: Send + Sync,
+    {
+        self.as_slice().load_linked(linked, db).await
+    }
+
+    async fn load_nested<L, C>(&self, loader: L, db: &C) -> Result<Vec<L::Output>, DbErr>
+    where
+        C: ConnectionTrait,
+        L: NestedLoader<<Self as LoaderTrait>::Model>,
+    {
+        self.as_slice().load_nested(loader, db).await
+    }
 }
 
 #[async_trait]
@@ -126,6 +164,215 @@ where
 {
     type Model = M;
 
+    async fn load_one<R, S, C>(&self, stmt: S, db: &C) -> Result<Vec<Option<R::Model>>, DbErr>
+    where
+        C: ConnectionTrait,
+        R: EntityTrait,
+        R::Model: Send + Sync,
+        S: EntityOrSelect<R>,
+        <<Self as LoaderTrait>::Model as ModelTrait>::Entity: Related<R>,
+    {
+        Loader::new(self).load_one(stmt, db).await
+    }
+
+    async fn load_many<R, S, C>(&self, stmt: S, db: &C) -> Result<Vec<Vec<R::Model>>, DbErr>
+    where
+        C: ConnectionTrait,
+        R: EntityTrait,
+        R::Model: Send + Sync,
+        S: EntityOrSelect<R>,
+        <<Self as LoaderTrait>::Model as ModelTrait>::Entity: Related<R>,
+    {
+        Loader::new(self).load_many(stmt, db).await
+    }
+
+    async fn load_many_to_many<R, S, V, C>(
+        &self,
+        stmt: S,
+        via: V,
+        db: &C,
+    ) -> Result<Vec<Vec<R::Model>>, DbErr>
+    where
+        C: ConnectionTrait,
+        R: EntityTrait,
+        R::Model: Send + Sync,
+        S: EntityOrSelect<R>,
+        V: EntityTrait,
+        V::Model: Send + Sync,
+        <<Self as LoaderTrait>::Model as ModelTrait>::Entity: Related<R>,
+    {
+        Loader::new(self).load_many_to_many(stmt, via, db).await
+    }
+
+    async fn load_linked<L, T, C>(&self, linked: L, db: &C) -> Result<Vec<Vec<T::Model>>, DbErr>
+    where
+        C: ConnectionTrait,
+        L: Linked<FromEntity = <<Self as LoaderTrait>::Model as ModelTrait>::Entity, ToEntity = T>
+            + Send,
+        T: EntityTrait,
+        T::Model: Send + Sync,
+    {
+        Loader::new(self).load_linked(linked, db).await
+    }
+
+    async fn load_nested<L, C>(&self, loader: L, db: &C) -> Result<Vec<L::Output>, DbErr>
+    where
+        C: ConnectionTrait,
+        L: NestedLoader<<Self as LoaderTrait>::Model>,
+    {
+        Loader::new(self).load_nested(loader, db).await
+    }
+}
+
+/// A list of models to load relations for, querying the keys of at most `chunk_size` models at a time.
+///
+/// By default, the chunk size is as large as the bind parameter limit of the database backend allows,
+/// which is 999 on SQLite and 65535 on MySQL and PostgreSQL.
+///
+/// ```
+/// # use sea_orm::{error::*, tests_cfg::*, *};
+/// #
+/// # #[smol_potat::main]
+/// # #[cfg(feature = "mock")]
+/// # pub async fn main() -> Result<(), DbErr> {
+/// #
+/// # let db = MockDatabase::new(DbBackend::Postgres)
+/// #     .append_query_results([[fruit::Model {
+/// #         id: 1,
+/// #         name: "Apple".to_owned(),
+/// #         cake_id: Some(1),
+/// #     }]])
+/// #     .append_query_results([[fruit::Model {
+/// #         id: 2,
+/// #         name: "Pear".to_owned(),
+/// #         cake_id: Some(2),
+/// #     }]])
+/// #     .into_connection();
+/// #
+/// let cakes: Vec<cake::Model> = vec![
+///     cake::Model {
+///         id: 1,
+///         name: "Apple Cake".to_owned(),
+///     },
+///     cake::Model {
+///         id: 2,
+///         name: "Pear Cake".to_owned(),
+///     },
+/// ];
+///
+/// let fruits: Vec<Vec<fruit::Model>> = Loader::new(&cakes)
+///     .chunk_size(1)
+///     .load_many(fruit::Entity, &db)
+///     .await?;
+///
+/// assert_eq!(fruits[0][0].name, "Apple");
+/// assert_eq!(fruits[1][0].name, "Pear");
+///
+/// assert_eq!(
+///     db.into_transaction_log(),
+///     [
+///         Transaction::from_sql_and_values(
+///             DbBackend::Postgres,
+///             r#"SELECT "fruit"."id", "fruit"."name", "fruit"."cake_id" FROM "fruit" WHERE "fruit"."cake_id" IN ($1)"#,
+///             [1.into()]
+///         ),
+///         Transaction::from_sql_and_values(
+///             DbBackend::Postgres,
+///             r#"SELECT "fruit"."id", "fruit"."name", "fruit"."cake_id" FROM "fruit" WHERE "fruit"."cake_id" IN ($1)"#,
+///             [2.into()]
+///         ),
+///     ]
+/// );
+/// #
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Loader<'a, M> {
+    models: &'a [M],
+    chunk_size: Option<usize>,
+}
+
+impl<'a, M> Loader<'a, M>
+where
+    M: ModelTrait + Sync,
+{
+    /// Load the relations of a list of models
+    pub fn new(models: &'a [M]) -> Self {
+        Self {
+            models,
+            chunk_size: None,
+        }
+    }
+
+    /// Set the maximum number of models whose keys are queried in one statement
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = Some(chunk_size);
+        self
+    }
+
+    /// Load the relations of another list of models, with the same chunk size
+    fn with_models<'b, N>(&self, models: &'b [N]) -> Loader<'b, N> {
+        Loader {
+            models,
+            chunk_size: self.chunk_size,
+        }
+    }
+
+    /// Query the models matching the keys, with one statement per chunk of unique keys
+    async fn load_in_chunks<S, F, C>(
+        &self,
+        table: &TableRef,
+        col: &Identity,
+        keys: &[ValueTuple],
+        db: &C,
+        selector: F,
+    ) -> Result<Vec<S::Item>, DbErr>
+    where
+        S: SelectorTrait,
+        F: Fn(Condition) -> Selector<S>,
+        C: ConnectionTrait,
+    {
+        let mut seen = HashSet::new();
+        let keys: Vec<ValueTuple> = keys
+            .iter()
+            .filter(|key| seen.insert(format!("{key:?}")))
+            .cloned()
+            .collect();
+
+        let chunk_size = match self.chunk_size {
+            Some(chunk_size) => chunk_size,
+            None => {
+                let db_backend = db.get_database_backend();
+                let max_params: usize = match db_backend {
+                    DbBackend::MySql | DbBackend::Postgres => 65535,
+                    DbBackend::Sqlite => 999,
+                };
+                // the statement may bind parameters of its own
+                let stmt = db_backend.build(&selector(Condition::all()).query);
+                let params = stmt.values.map(|values| values.0.len()).unwrap_or(0);
+                max_params.saturating_sub(params) / identity_arity(col)
+            }
+        }
+        .max(1);
+
+        let mut data = Vec::with_capacity(keys.len());
+        for keys in keys.chunks(chunk_size) {
+            let condition = prepare_condition(table, col, keys);
+            data.extend(selector(condition).all(db).await?);
+        }
+
+        Ok(data)
+    }
+}
+
+#[async_trait]
+impl<M> LoaderTrait for Loader<'_, M>
+where
+    M: ModelTrait + Sync,
+{
+    type Model = M;
+
     async fn load_one<R, S, C>(&self, stmt: S, db: &C) -> Result<Vec<Option<R::Model>>, DbErr>
     where
         C: ConnectionTrait,
@@ -144,15 +391,18 @@ where
         }
 
         let keys: Vec<ValueTuple> = self
+            .models
             .iter()
             .map(|model: &M| extract_key(&rel_def.from_col, model))
             .collect();
 
-        let condition = prepare_condition(&rel_def.to_tbl, &rel_def.to_col, &keys);
+        let stmt = stmt.select();
 
-        let stmt = <Select<R> as QueryFilter>::filter(stmt.select(), condition);
-
-        let data = stmt.all(db).await?;
+        let data = self
+            .load_in_chunks(&rel_def.to_tbl, &rel_def.to_col, &keys, db, |condition| {
+                <Select<R> as QueryFilter>::filter(stmt.clone(), condition).into_model()
+            })
+            .await?;
 
         let hashmap: HashMap<String, <R as EntityTrait>::Model> = data.into_iter().fold(
             HashMap::<String, <R as EntityTrait>::Model>::new(),
@@ -195,15 +445,18 @@ where
         }
 
         let keys: Vec<ValueTuple> = self
+            .models
             .iter()
             .map(|model: &M| extract_key(&rel_def.from_col, model))
             .collect();
 
-        let condition = prepare_condition(&rel_def.to_tbl, &rel_def.to_col, &keys);
-
-        let stmt = <Select<R> as QueryFilter>::filter(stmt.select(), condition);
+        let stmt = stmt.select();
 
-        let data = stmt.all(db).await?;
+        let data = self
+            .load_in_chunks(&rel_def.to_tbl, &rel_def.to_col, &keys, db, |condition| {
+                <Select<R> as QueryFilter>::filter(stmt.clone(), condition).into_model()
+            })
+            .await?;
 
         let mut hashmap: HashMap<String, Vec<<R as EntityTrait>::Model>> =
             keys.iter()
@@ -270,6 +523,7 @@ where
             }
 
             let pkeys: Vec<ValueTuple> = self
+                .models
                 .iter()
                 .map(|model: &M| extract_key(&via_rel.from_col, model))
                 .collect();
@@ -278,9 +532,11 @@ where
             let mut keymap: HashMap<String, Vec<ValueTuple>> = Default::default();
 
             let keys: Vec<ValueTuple> = {
-                let condition = prepare_condition(&via_rel.to_tbl, &via_rel.to_col, &pkeys);
-                let stmt = V::find().filter(condition);
-                let data = stmt.all(db).await?;
+                let data = self
+                    .load_in_chunks(&via_rel.to_tbl, &via_rel.to_col, &pkeys, db, |condition| {
+                        V::find().filter(condition).into_model::<V::Model>()
+                    })
+                    .await?;
                 data.into_iter().for_each(|model| {
                     let pk = format!("{:?}", extract_key(&via_rel.to_col, &model));
                     let entry = keymap.entry(pk).or_default();
@@ -292,11 +548,13 @@ where
                 keymap.values().flatten().cloned().collect()
             };
 
-            let condition = prepare_condition(&rel_def.to_tbl, &rel_def.to_col, &keys);
-
-            let stmt = <Select<R> as QueryFilter>::filter(stmt.select(), condition);
+            let stmt = stmt.select();
 
-            let data = stmt.all(db).await?;
+            let data = self
+                .load_in_chunks(&rel_def.to_tbl, &rel_def.to_col, &keys, db, |condition| {
+                    <Select<R> as QueryFilter>::filter(stmt.clone(), condition).into_model()
+                })
+                .await?;
             // Map of R::PK -> R::Model
             let data: HashMap<String, <R as EntityTrait>::Model> = data
                 .into_iter()
@@ -328,6 +586,478 @@ where
             return Err(query_err("Relation is not ManyToMany"));
         }
     }
+
+    async fn load_linked<L, T, C>(&self, linked: L, db: &C) -> Result<Vec<Vec<T::Model>>, DbErr>
+    where
+        C: ConnectionTrait,
+        L: Linked<FromEntity = <<Self as LoaderTrait>::Model as ModelTrait>::Entity, ToEntity = T>
+            + Send,
+        T: EntityTrait,
+        T::Model: Send + Sync,
+    {
+        let rel_def = match linked.link().into_iter().next() {
+            Some(rel_def) => rel_def,
+            None => return Err(query_err("Linked has no relation")),
+        };
+
+        let keys: Vec<ValueTuple> = self
+            .models
+            .iter()
+            .map(|model: &M| extract_key(&rel_def.from_col, model))
+            .collect();
+
+        let stmt =
+            <<Self as LoaderTrait>::Model as ModelTrait>::Entity::find().find_also_linked(linked);
+
+        let data = self
+            .load_in_chunks(
+                &rel_def.from_tbl,
+                &rel_def.from_col,
+                &keys,
+                db,
+                |condition| {
+                    stmt.clone()
+                        .filter(condition)
+                        .into_model::<<M::Entity as EntityTrait>::Model, T::Model>()
+                },
+            )
+            .await?;
+
+        // Map of M::Key -> Vec<T::Model>
+        let mut hashmap: HashMap<String, Vec<<T as EntityTrait>::Model>> = keys
+            .iter()
+            .map(|key: &ValueTuple| (format!("{key:?}"), Vec::new()))
+            .collect();
+
+        data.into_iter().for_each(|(model, value)| {
+            if let Some(value) = value {
+                let key = extract_key(&rel_def.from_col, &model);
+
+                let vec = hashmap
+                    .get_mut(&format!("{key:?}"))
+                    .expect("Failed at finding key on hashmap");
+
+                vec.push(value);
+            }
+        });
+
+        let result: Vec<Vec<T::Model>> = keys
+            .iter()
+            .map(|key: &ValueTuple| {
+                hashmap
+                    .get(&format!("{key:?}"))
+                    .cloned()
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        Ok(result)
+    }
+
+    async fn load_nested<L, C>(&self, loader: L, db: &C) -> Result<Vec<L::Output>, DbErr>
+    where
+        C: ConnectionTrait,
+        L: NestedLoader<<Self as LoaderTrait>::Model>,
+    {
+        loader.load(self, db).await
+    }
+}
+
+/// Describes the relations to eager load for a list of models, to be used in [`LoaderTrait::load_nested`].
+///
+/// The loaders of a relation are [`LoadOne`], [`LoadMany`] and [`LoadManyToMany`],
+/// the relations of the loaded models can be loaded in turn with [`LoadOne::with`] and friends,
+/// and a tuple of loaders loads several relations of the same models.
+/// Each level of the tree is loaded with one batch of queries.
+///
+/// ```
+/// # use sea_orm::{error::*, tests_cfg::*, *};
+/// #
+/// # #[smol_potat::main]
+/// # #[cfg(feature = "mock")]
+/// # pub async fn main() -> Result<(), DbErr> {
+/// #
+/// # let db = MockDatabase::new(DbBackend::Postgres)
+/// #     .append_query_results([[cake_filling::Model {
+/// #         cake_id: 1,
+/// #         filling_id: 1,
+/// #     }]])
+/// #     .append_query_results([[filling::Model {
+/// #         id: 1,
+/// #         name: "Lemon".to_owned(),
+/// #         vendor_id: Some(1),
+/// #         ignored_attr: 0,
+/// #     }]])
+/// #     .append_query_results([[cake_filling::Model {
+/// #         cake_id: 1,
+/// #         filling_id: 1,
+/// #     }]])
+/// #     .append_query_results([[cake::Model {
+/// #         id: 1,
+/// #         name: "Lemon Cake".to_owned(),
+/// #     }]])
+/// #     .append_query_results([[fruit::Model {
+/// #         id: 1,
+/// #         name: "Lemon".to_owned(),
+/// #         cake_id: Some(1),
+/// #     }]])
+/// #     .into_connection();
+/// #
+/// let cakes: Vec<cake::Model> = vec![cake::Model {
+///     id: 1,
+///     name: "Lemon Cake".to_owned(),
+/// }];
+///
+/// let fillings: Vec<Vec<(filling::Model, Vec<(cake::Model, Vec<fruit::Model>)>)>> = cakes
+///     .load_nested(
+///         LoadManyToMany::new(filling::Entity, cake_filling::Entity).with(
+///             LoadManyToMany::new(cake::Entity, cake_filling::Entity)
+///                 .with(LoadMany::new(fruit::Entity)),
+///         ),
+///         &db,
+///     )
+///     .await?;
+///
+/// assert_eq!(fillings[0][0].0.name, "Lemon");
+/// assert_eq!(fillings[0][0].1[0].0.name, "Lemon Cake");
+/// assert_eq!(fillings[0][0].1[0].1[0].name, "Lemon");
+/// #
+/// # Ok(())
+/// # }
+/// ```
+#[async_trait]
+pub trait NestedLoader<M>: Send + Sync {
+    /// What is loaded for each model
+    type Output: Send;
+
+    /// Load for each of the models, in the same order
+    async fn load<C>(&self, models: &Loader<'_, M>, db: &C) -> Result<Vec<Self::Output>, DbErr>
+    where
+        C: ConnectionTrait;
+}
+
+/// Eager load a has_one relation, see [`NestedLoader`]
+#[derive(Clone, Debug)]
+pub struct LoadOne<R>
+where
+    R: EntityTrait,
+{
+    select: Select<R>,
+}
+
+/// Eager load a has_many relation, see [`NestedLoader`]
+#[derive(Clone, Debug)]
+pub struct LoadMany<R>
+where
+    R: EntityTrait,
+{
+    select: Select<R>,
+}
+
+/// Eager load a many_to_many relation, see [`NestedLoader`]
+#[derive(Clone, Debug)]
+pub struct LoadManyToMany<R, V>
+where
+    R: EntityTrait,
+    V: EntityTrait,
+{
+    select: Select<R>,
+    via: PhantomData<V>,
+}
+
+/// Eager load a relation, and the relations of the loaded models, see [`NestedLoader`]
+#[derive(Clone, Debug)]
+pub struct LoadWith<L, N> {
+    loader: L,
+    nested: N,
+}
+
+impl<R> LoadOne<R>
+where
+    R: EntityTrait,
+{
+    /// Load the related Entity, or the models selected by a Select<Entity>
+    pub fn new<S>(stmt: S) -> Self
+    where
+        S: EntityOrSelect<R>,
+    {
+        Self {
+            select: stmt.select(),
+        }
+    }
+
+    /// Also load the relations of the loaded models
+    pub fn with<N>(self, nested: N) -> LoadWith<Self, N> {
+        LoadWith {
+            loader: self,
+            nested,
+        }
+    }
+}
+
+impl<R> LoadMany<R>
+where
+    R: EntityTrait,
+{
+    /// Load the related Entity, or the models selected by a Select<Entity>
+    pub fn new<S>(stmt: S) -> Self
+    where
+        S: EntityOrSelect<R>,
+    {
+        Self {
+            select: stmt.select(),
+        }
+    }
+
+    /// Also load the relations of the loaded models
+    pub fn with<N>(self, nested: N) -> LoadWith<Self, N> {
+        LoadWith {
+            loader: self,
+            nested,
+        }
+    }
+}
+
+impl<R, V> LoadManyToMany<R, V>
+where
+    R: EntityTrait,
+    V: EntityTrait,
+{
+    /// Load the related Entity, or the models selected by a Select<Entity>, via the junction Entity
+    pub fn new<S>(stmt: S, _: V) -> Self
+    where
+        S: EntityOrSelect<R>,
+    {
+        Self {
+            select: stmt.select(),
+            via: PhantomData,
+        }
+    }
+
+    /// Also load the relations of the loaded models
+    pub fn with<N>(self, nested: N) -> LoadWith<Self, N> {
+        LoadWith {
+            loader: self,
+            nested,
+        }
+    }
+}
+
+#[async_trait]
+impl<M, R> NestedLoader<M> for LoadOne<R>
+where
+    M: ModelTrait + Sync,
+    M::Entity: Related<R>,
+    R: EntityTrait,
+    R::Model: Send + Sync,
+{
+    type Output = Option<R::Model>;
+
+    async fn load<C>(&self, models: &Loader<'_, M>, db: &C) -> Result<Vec<Self::Output>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        models.load_one(self.select.clone(), db).await
+    }
+}
+
+#[async_trait]
+impl<M, R> NestedLoader<M> for LoadMany<R>
+where
+    M: ModelTrait + Sync,
+    M::Entity: Related<R>,
+    R: EntityTrait,
+    R::Model: Send + Sync,
+{
+    type Output = Vec<R::Model>;
+
+    async fn load<C>(&self, models: &Loader<'_, M>, db: &C) -> Result<Vec<Self::Output>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        models.load_many(self.select.clone(), db).await
+    }
+}
+
+#[async_trait]
+impl<M, R, V> NestedLoader<M> for LoadManyToMany<R, V>
+where
+    M: ModelTrait + Sync,
+    M::Entity: Related<R>,
+    R: EntityTrait,
+    R::Model: Send + Sync,
+    V: EntityTrait,
+    V::Model: Send + Sync,
+{
+    type Output = Vec<R::Model>;
+
+    async fn load<C>(&self, models: &Loader<'_, M>, db: &C) -> Result<Vec<Self::Output>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        models
+            .load_many_to_many(self.select.clone(), V::default(), db)
+            .await
+    }
+}
+
+#[async_trait]
+impl<M, R, N> NestedLoader<M> for LoadWith<LoadOne<R>, N>
+where
+    M: ModelTrait + Sync,
+    M::Entity: Related<R>,
+    R: EntityTrait,
+    R::Model: Send + Sync,
+    N: NestedLoader<R::Model>,
+{
+    type Output = Option<(R::Model, N::Output)>;
+
+    async fn load<C>(&self, models: &Loader<'_, M>, db: &C) -> Result<Vec<Self::Output>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let data = self.loader.load(models, db).await?;
+        let related: Vec<R::Model> = data.iter().flatten().cloned().collect();
+        let mut nested = self
+            .nested
+            .load(&models.with_models(&related), db)
+            .await?
+            .into_iter();
+
+        let result = data
+            .into_iter()
+            .map(|model| {
+                model.map(|model| {
+                    let nested = nested.next().expect("Failed at zipping nested models");
+                    (model, nested)
+                })
+            })
+            .collect();
+
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl<M, R, N> NestedLoader<M> for LoadWith<LoadMany<R>, N>
+where
+    M: ModelTrait + Sync,
+    M::Entity: Related<R>,
+    R: EntityTrait,
+    R::Model: Send + Sync,
+    N: NestedLoader<R::Model>,
+{
+    type Output = Vec<(R::Model, N::Output)>;
+
+    async fn load<C>(&self, models: &Loader<'_, M>, db: &C) -> Result<Vec<Self::Output>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let data = self.loader.load(models, db).await?;
+        load_nested_many(models, data, &self.nested, db).await
+    }
+}
+
+#[async_trait]
+impl<M, R, V, N> NestedLoader<M> for LoadWith<LoadManyToMany<R, V>, N>
+where
+    M: ModelTrait + Sync,
+    M::Entity: Related<R>,
+    R: EntityTrait,
+    R::Model: Send + Sync,
+    V: EntityTrait,
+    V::Model: Send + Sync,
+    N: NestedLoader<R::Model>,
+{
+    type Output = Vec<(R::Model, N::Output)>;
+
+    async fn load<C>(&self, models: &Loader<'_, M>, db: &C) -> Result<Vec<Self::Output>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let data = self.loader.load(models, db).await?;
+        load_nested_many(models, data, &self.nested, db).await
+    }
+}
+
+#[async_trait]
+impl<M, A, B> NestedLoader<M> for (A, B)
+where
+    M: ModelTrait + Sync,
+    A: NestedLoader<M>,
+    B: NestedLoader<M>,
+{
+    type Output = (A::Output, B::Output);
+
+    async fn load<C>(&self, models: &Loader<'_, M>, db: &C) -> Result<Vec<Self::Output>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let a = self.0.load(models, db).await?;
+        let b = self.1.load(models, db).await?;
+
+        Ok(a.into_iter().zip(b).collect())
+    }
+}
+
+#[async_trait]
+impl<M, A, B, C> NestedLoader<M> for (A, B, C)
+where
+    M: ModelTrait + Sync,
+    A: NestedLoader<M>,
+    B: NestedLoader<M>,
+    C: NestedLoader<M>,
+{
+    type Output = (A::Output, B::Output, C::Output);
+
+    async fn load<D>(&self, models: &Loader<'_, M>, db: &D) -> Result<Vec<Self::Output>, DbErr>
+    where
+        D: ConnectionTrait,
+    {
+        let a = self.0.load(models, db).await?;
+        let b = self.1.load(models, db).await?;
+        let c = self.2.load(models, db).await?;
+
+        Ok(a.into_iter()
+            .zip(b)
+            .zip(c)
+            .map(|((a, b), c)| (a, b, c))
+            .collect())
+    }
+}
+
+async fn load_nested_many<M, R, N, C>(
+    models: &Loader<'_, M>,
+    data: Vec<Vec<R>>,
+    nested: &N,
+    db: &C,
+) -> Result<Vec<Vec<(R, N::Output)>>, DbErr>
+where
+    M: ModelTrait + Sync,
+    R: ModelTrait + Sync,
+    N: NestedLoader<R>,
+    C: ConnectionTrait,
+{
+    let related: Vec<R> = data.iter().flatten().cloned().collect();
+    let mut nested = nested
+        .load(&models.with_models(&related), db)
+        .await?
+        .into_iter();
+
+    let result = data
+        .into_iter()
+        .map(|models| {
+            models
+                .into_iter()
+                .map(|model| {
+                    let nested = nested.next().expect("Failed at zipping nested models");
+                    (model, nested)
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(result)
 }
 
 fn cmp_table_ref(left: &TableRef, right: &TableRef) -> bool {
@@ -387,7 +1117,6 @@ where
 }
 
 fn prepare_condition(table: &TableRef, col: &Identity, keys: &[ValueTuple]) -> Condition {
-    // TODO when value is hashable, retain only unique values
     let keys = keys.to_owned();
     match col {
         Identity::Unary(column_a) => {
@@ -412,6 +1141,14 @@ fn prepare_condition(table: &TableRef, col: &Identity, keys: &[ValueTuple]) -> C
     }
 }
 
+fn identity_arity(col: &Identity) -> usize {
+    match col {
+        Identity::Unary(_) => 1,
+        Identity::Binary(_, _) => 2,
+        Identity::Ternary(_, _, _) => 3,
+    }
+}
+
 fn table_column(tbl: &TableRef, col: &DynIden) -> ColumnRef {
     match tbl.to_owned() {
         TableRef::Table(tbl) => (tbl, col.clone()).into_column_ref(),
@@ -506,4 +1243,224 @@ mod tests {
             ]
         );
     }
+
+    #[tokio::test]
+    async fn test_load_linked() {
+        use crate::{
+            entity::prelude::*, tests_cfg::*, DbBackend, IntoMockRow, LoaderTrait, MockDatabase,
+        };
+
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results([[
+                (
+                    cake::Model {
+                        id: 1,
+                        name: "New York Cheese".to_owned(),
+                    },
+                    vendor::Model {
+                        id: 1,
+                        name: "Bakery One".to_owned(),
+                    },
+                )
+                    .into_mock_row(),
+                (
+                    cake::Model {
+                        id: 1,
+                        name: "New York Cheese".to_owned(),
+                    },
+                    vendor::Model {
+                        id: 2,
+                        name: "Bakery Two".to_owned(),
+                    },
+                )
+                    .into_mock_row(),
+            ]])
+            .into_connection();
+
+        let cakes = vec![
+            cake::Model {
+                id: 1,
+                name: "New York Cheese".to_owned(),
+            },
+            cake::Model {
+                id: 2,
+                name: "London Cheese".to_owned(),
+            },
+        ];
+
+        let vendors = cakes
+            .load_linked(entity_linked::CakeToFillingVendor, &db)
+            .await
+            .expect("Should return something");
+
+        assert_eq!(
+            vendors,
+            [
+                vec![
+                    vendor::Model {
+                        id: 1,
+                        name: "Bakery One".to_owned(),
+                    },
+                    vendor::Model {
+                        id: 2,
+                        name: "Bakery Two".to_owned(),
+                    },
+                ],
+                vec![]
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_nested() {
+        use crate::{
+            entity::prelude::*, tests_cfg::*, DbBackend, IntoMockRow, LoadMany, LoadManyToMany,
+            LoaderTrait, MockDatabase,
+        };
+
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results([[fruit::Model {
+                id: 1,
+                name: "Apple".to_owned(),
+                cake_id: Some(2),
+            }
+            .into_mock_row()]])
+            .append_query_results([[cake_filling::Model {
+                cake_id: 1,
+                filling_id: 1,
+            }
+            .into_mock_row()]])
+            .append_query_results([[filling::Model {
+                id: 1,
+                name: "Lemon".to_owned(),
+                vendor_id: None,
+                ignored_attr: 0,
+            }
+            .into_mock_row()]])
+            .into_connection();
+
+        let cakes = vec![
+            cake::Model {
+                id: 1,
+                name: "New York Cheese".to_owned(),
+            },
+            cake::Model {
+                id: 2,
+                name: "London Cheese".to_owned(),
+            },
+        ];
+
+        let loaded = cakes
+            .load_nested(
+                (
+                    LoadMany::new(fruit::Entity),
+                    LoadManyToMany::new(filling::Entity, cake_filling::Entity),
+                ),
+                &db,
+            )
+            .await
+            .expect("Should return something");
+
+        assert_eq!(
+            loaded,
+            [
+                (
+                    vec![],
+                    vec![filling::Model {
+                        id: 1,
+                        name: "Lemon".to_owned(),
+                        vendor_id: None,
+                        ignored_attr: 0,
+                    }]
+                ),
+                (
+                    vec![fruit::Model {
+                        id: 1,
+                        name: "Apple".to_owned(),
+                        cake_id: Some(2),
+                    }],
+                    vec![]
+                )
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_composite_key_in_chunks() {
+        use crate::{
+            entity::prelude::*, tests_cfg::*, DbBackend, IntoMockRow, Loader, LoaderTrait,
+            MockDatabase, Transaction,
+        };
+
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results([[cake_filling::Model {
+                cake_id: 1,
+                filling_id: 1,
+            }
+            .into_mock_row()]])
+            .append_query_results([[cake_filling::Model {
+                cake_id: 1,
+                filling_id: 2,
+            }
+            .into_mock_row()]])
+            .into_connection();
+
+        let prices = vec![
+            cake_filling_price::Model {
+                cake_id: 1,
+                filling_id: 1,
+                ignored_attr: 0,
+            },
+            cake_filling_price::Model {
+                cake_id: 1,
+                filling_id: 2,
+                ignored_attr: 0,
+            },
+            cake_filling_price::Model {
+                cake_id: 1,
+                filling_id: 1,
+                ignored_attr: 0,
+            },
+        ];
+
+        let cake_fillings = Loader::new(&prices)
+            .chunk_size(1)
+            .load_one(cake_filling::Entity, &db)
+            .await
+            .expect("Should return something");
+
+        assert_eq!(
+            cake_fillings,
+            [
+                Some(cake_filling::Model {
+                    cake_id: 1,
+                    filling_id: 1,
+                }),
+                Some(cake_filling::Model {
+                    cake_id: 1,
+                    filling_id: 2,
+                }),
+                Some(cake_filling::Model {
+                    cake_id: 1,
+                    filling_id: 1,
+                }),
+            ]
+        );
+
+        assert_eq!(
+            db.into_transaction_log(),
+            [
+                Transaction::from_sql_and_values(
+                    DbBackend::Postgres,
+                    r#"SELECT "cake_filling"."cake_id", "cake_filling"."filling_id" FROM "cake_filling" WHERE ("cake_filling"."cake_id", "cake_filling"."filling_id") IN (($1, $2))"#,
+                    [1.into(), 1.into()]
+                ),
+                Transaction::from_sql_and_values(
+                    DbBackend::Postgres,
+                    r#"SELECT "cake_filling"."cake_id", "cake_filling"."filling_id" FROM "cake_filling" WHERE ("cake_filling"."cake_id", "cake_filling"."filling_id") IN (($1, $2))"#,
+                    [1.into(), 2.into()]
+                ),
+            ]
+        );
+    }
 }
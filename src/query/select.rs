@@ -1,8 +1,15 @@
-use crate::{ColumnTrait, EntityTrait, Iterable, QueryFilter, QueryOrder, QuerySelect, QueryTrait};
+use crate::{
+    ColumnTrait, DbBackend, EntityTrait, IntoIdentity, Iterable, QueryFilter, QueryOrder,
+    QuerySelect, QueryTrait, Statement,
+};
 use core::fmt::Debug;
 use core::marker::PhantomData;
 pub use sea_query::JoinType;
-use sea_query::{Expr, IntoColumnRef, SelectStatement, SimpleExpr};
+use sea_query::{
+    ColumnRef, CommonTableExpression, DynIden, Expr, IntoColumnRef, IntoIden, Order,
+    OrderedStatement, OverStatement, SeaRc, SelectExpr, SelectStatement, SimpleExpr, UnionType,
+    WindowStatement, WithClause,
+};
 
 /// Defines a structure to perform select operations
 #[derive(Clone, Debug)]
@@ -12,6 +19,40 @@ where
 {
     pub(crate) query: SelectStatement,
     pub(crate) entity: PhantomData<E>,
+    pub(crate) with: CommonTableExpressions,
+}
+
+/// The common table expressions of a [Select], kept apart from a [WithClause]
+/// so that the expressions of combined queries can be merged
+#[derive(Clone, Debug, Default)]
+pub(crate) struct CommonTableExpressions {
+    recursive: bool,
+    ctes: Vec<CommonTableExpression>,
+}
+
+impl CommonTableExpressions {
+    fn push(&mut self, cte: CommonTableExpression, recursive: bool) {
+        self.recursive |= recursive;
+        self.ctes.push(cte);
+    }
+
+    pub(crate) fn append(&mut self, mut other: Self) {
+        self.recursive |= other.recursive;
+        self.ctes.append(&mut other.ctes);
+    }
+
+    /// The `WITH` clause of the expressions, `None` if there is none
+    pub(crate) fn into_with_clause(self) -> Option<WithClause> {
+        if self.ctes.is_empty() {
+            return None;
+        }
+        let mut with = WithClause::new();
+        with.recursive(self.recursive);
+        for cte in self.ctes {
+            with.cte(cte);
+        }
+        Some(with)
+    }
 }
 
 /// Defines a structure to perform a SELECT operation on two Models
@@ -23,6 +64,34 @@ where
 {
     pub(crate) query: SelectStatement,
     pub(crate) entity: PhantomData<(E, F)>,
+    pub(crate) with: Option<WithClause>,
+}
+
+/// Defines a structure to perform a SELECT operation on three Models
+#[derive(Clone, Debug)]
+pub struct SelectThree<E, F, G>
+where
+    E: EntityTrait,
+    F: EntityTrait,
+    G: EntityTrait,
+{
+    pub(crate) query: SelectStatement,
+    pub(crate) entity: PhantomData<(E, F, G)>,
+    pub(crate) with: Option<WithClause>,
+}
+
+/// Defines a structure to perform a SELECT operation on four Models
+#[derive(Clone, Debug)]
+pub struct SelectFour<E, F, G, H>
+where
+    E: EntityTrait,
+    F: EntityTrait,
+    G: EntityTrait,
+    H: EntityTrait,
+{
+    pub(crate) query: SelectStatement,
+    pub(crate) entity: PhantomData<(E, F, G, H)>,
+    pub(crate) with: Option<WithClause>,
 }
 
 /// Defines a structure to perform a SELECT operation on many Models
@@ -34,6 +103,7 @@ where
 {
     pub(crate) query: SelectStatement,
     pub(crate) entity: PhantomData<(E, F)>,
+    pub(crate) with: Option<WithClause>,
 }
 
 /// Performs a conversion to [SimpleExpr]
@@ -78,6 +148,33 @@ macro_rules! impl_trait {
                 &mut self.query
             }
         }
+
+        impl<E, F, G> $trait for SelectThree<E, F, G>
+        where
+            E: EntityTrait,
+            F: EntityTrait,
+            G: EntityTrait,
+        {
+            type QueryStatement = SelectStatement;
+
+            fn query(&mut self) -> &mut SelectStatement {
+                &mut self.query
+            }
+        }
+
+        impl<E, F, G, H> $trait for SelectFour<E, F, G, H>
+        where
+            E: EntityTrait,
+            F: EntityTrait,
+            G: EntityTrait,
+            H: EntityTrait,
+        {
+            type QueryStatement = SelectStatement;
+
+            fn query(&mut self) -> &mut SelectStatement {
+                &mut self.query
+            }
+        }
     };
 }
 
@@ -114,6 +211,7 @@ where
         Self {
             query: SelectStatement::new(),
             entity: PhantomData,
+            with: CommonTableExpressions::default(),
         }
         .prepare_select()
         .prepare_from()
@@ -136,6 +234,273 @@ where
     }
 }
 
+impl<E> Select<E>
+where
+    E: EntityTrait,
+{
+    /// Add a common table expression to the `WITH` clause of the query,
+    /// which can then be joined or used in subqueries by its name
+    ///
+    /// ```
+    /// use sea_orm::sea_query::{Alias, Expr, Query};
+    /// use sea_orm::{entity::*, query::*, tests_cfg::cake, DbBackend};
+    ///
+    /// assert_eq!(
+    ///     cake::Entity::find()
+    ///         .with_cte(
+    ///             Alias::new("cheese"),
+    ///             Query::select()
+    ///                 .column(cake::Column::Id)
+    ///                 .from(cake::Entity)
+    ///                 .and_where(cake::Column::Name.contains("cheese"))
+    ///                 .to_owned(),
+    ///         )
+    ///         .filter(
+    ///             cake::Column::Id.in_subquery(
+    ///                 Query::select()
+    ///                     .column(cake::Column::Id)
+    ///                     .from(Alias::new("cheese"))
+    ///                     .to_owned(),
+    ///             ),
+    ///         )
+    ///         .build(DbBackend::Postgres)
+    ///         .to_string(),
+    ///     [
+    ///         r#"WITH "cheese" ("id") AS (SELECT "id" FROM "cake" WHERE "cake"."name" LIKE '%cheese%')"#,
+    ///         r#"SELECT "cake"."id", "cake"."name" FROM "cake""#,
+    ///         r#"WHERE "cake"."id" IN (SELECT "id" FROM "cheese")"#,
+    ///     ]
+    ///     .join(" ")
+    /// );
+    /// ```
+    ///
+    /// The `WITH` clause is kept when the query is converted by `into_model`, `into_tuple`,
+    /// `paginate` or `cursor_by`, combined with `find_also_related` and the like, or aggregated
+    /// with [AggregatorTrait](crate::AggregatorTrait), but is not part of [QueryTrait::into_query].
+    pub fn with_cte<N>(mut self, name: N, query: SelectStatement) -> Self
+    where
+        N: IntoIden,
+    {
+        let cte = CommonTableExpression::from_select(query)
+            .table_name(name)
+            .to_owned();
+        self.with.push(cte, false);
+        self
+    }
+
+    /// Add a recursive common table expression to the `WITH` clause of the query.
+    /// The columns of the expression have to be named, as its query refers to itself.
+    ///
+    /// ```
+    /// use sea_orm::sea_query::{Alias, Expr, Query, UnionType};
+    /// use sea_orm::{entity::*, query::*, tests_cfg::cake, DbBackend};
+    ///
+    /// let numbers = Query::select()
+    ///     .expr(Expr::val(1))
+    ///     .union(
+    ///         UnionType::All,
+    ///         Query::select()
+    ///             .expr(Expr::col(Alias::new("n")).add(1))
+    ///             .from(Alias::new("numbers"))
+    ///             .and_where(Expr::col(Alias::new("n")).lt(3))
+    ///             .to_owned(),
+    ///     )
+    ///     .to_owned();
+    ///
+    /// assert_eq!(
+    ///     cake::Entity::find()
+    ///         .with_recursive_cte(Alias::new("numbers"), [Alias::new("n")], numbers)
+    ///         .filter(
+    ///             cake::Column::Id.in_subquery(
+    ///                 Query::select()
+    ///                     .column(Alias::new("n"))
+    ///                     .from(Alias::new("numbers"))
+    ///                     .to_owned(),
+    ///             ),
+    ///         )
+    ///         .build(DbBackend::Sqlite)
+    ///         .to_string(),
+    ///     [
+    ///         r#"WITH RECURSIVE "numbers" ("n") AS (SELECT 1 UNION ALL"#,
+    ///         r#"SELECT "n" + 1 FROM "numbers" WHERE "n" < 3)"#,
+    ///         r#"SELECT "cake"."id", "cake"."name" FROM "cake""#,
+    ///         r#"WHERE "cake"."id" IN (SELECT "n" FROM "numbers")"#,
+    ///     ]
+    ///     .join(" ")
+    /// );
+    /// ```
+    pub fn with_recursive_cte<N, I, C>(
+        mut self,
+        name: N,
+        columns: I,
+        query: SelectStatement,
+    ) -> Self
+    where
+        N: IntoIden,
+        I: IntoIterator<Item = C>,
+        C: IntoIden,
+    {
+        let cte = CommonTableExpression::from_select(query)
+            .table_name(name)
+            .columns(columns)
+            .to_owned();
+        self.with.push(cte, true);
+        self
+    }
+
+    /// Select a window function over partitions of the entity's columns, aliased as `alias`
+    ///
+    /// ```
+    /// use sea_orm::sea_query::{Expr, Order};
+    /// use sea_orm::{entity::*, query::*, tests_cfg::fruit, DbBackend};
+    ///
+    /// assert_eq!(
+    ///     fruit::Entity::find()
+    ///         .column_window(
+    ///             Expr::cust("ROW_NUMBER()"),
+    ///             [fruit::Column::CakeId],
+    ///             [(fruit::Column::Name, Order::Asc)],
+    ///             "rank",
+    ///         )
+    ///         .build(DbBackend::Postgres)
+    ///         .to_string(),
+    ///     [
+    ///         r#"SELECT "fruit"."id", "fruit"."name", "fruit"."cake_id","#,
+    ///         r#"ROW_NUMBER() OVER ( PARTITION BY "fruit"."cake_id" ORDER BY "fruit"."name" ASC ) AS "rank""#,
+    ///         r#"FROM "fruit""#,
+    ///     ]
+    ///     .join(" ")
+    /// );
+    /// ```
+    pub fn column_window<T, P, O, A>(
+        mut self,
+        expr: T,
+        partition_by: P,
+        order_by: O,
+        alias: A,
+    ) -> Self
+    where
+        T: IntoSimpleExpr,
+        P: IntoIterator<Item = E::Column>,
+        O: IntoIterator<Item = (E::Column, Order)>,
+        A: IntoIdentity,
+    {
+        let mut window = WindowStatement::new();
+        for col in partition_by {
+            window.add_partition_by(col.into_simple_expr());
+        }
+        for (col, order) in order_by {
+            window.order_by_expr(col.into_simple_expr(), order);
+        }
+        self.query
+            .expr_window_as(expr.into_simple_expr(), window, alias.into_identity());
+        self
+    }
+
+    /// Combine the rows of two queries with `UNION`, removing the duplicated rows.
+    ///
+    /// The combined query is selected from as a subquery aliased with the name of the table,
+    /// so it can be filtered, ordered, limited, paginated or used as a cursor like any [Select].
+    /// The columns selected by `self` are selected from the subquery, and the common table
+    /// expressions of both queries are merged into one `WITH` clause.
+    ///
+    /// ```
+    /// use sea_orm::{entity::*, query::*, tests_cfg::cake, DbBackend};
+    ///
+    /// assert_eq!(
+    ///     cake::Entity::find()
+    ///         .filter(cake::Column::Name.contains("cheese"))
+    ///         .union(cake::Entity::find().filter(cake::Column::Id.lt(3)))
+    ///         .order_by_asc(cake::Column::Name)
+    ///         .limit(10)
+    ///         .build(DbBackend::Postgres)
+    ///         .to_string(),
+    ///     [
+    ///         r#"SELECT "cake"."id", "cake"."name" FROM"#,
+    ///         r#"(SELECT "cake"."id", "cake"."name" FROM "cake" WHERE "cake"."name" LIKE '%cheese%'"#,
+    ///         r#"UNION (SELECT "cake"."id", "cake"."name" FROM "cake" WHERE "cake"."id" < 3)) AS "cake""#,
+    ///         r#"ORDER BY "cake"."name" ASC LIMIT 10"#,
+    ///     ]
+    ///     .join(" ")
+    /// );
+    /// ```
+    pub fn union(self, other: Self) -> Self {
+        self.combine(UnionType::Distinct, other)
+    }
+
+    /// Combine the rows of two queries with `UNION ALL`, keeping the duplicated rows.
+    /// See [Select::union].
+    pub fn union_all(self, other: Self) -> Self {
+        self.combine(UnionType::All, other)
+    }
+
+    /// Keep the rows returned by both queries with `INTERSECT`. See [Select::union].
+    ///
+    /// MySQL supports `INTERSECT` since 8.0.31.
+    pub fn intersect(self, other: Self) -> Self {
+        self.combine(UnionType::Intersect, other)
+    }
+
+    /// Keep the rows of this query not returned by the other with `EXCEPT`. See [Select::union].
+    ///
+    /// MySQL supports `EXCEPT` since 8.0.31.
+    pub fn except(self, other: Self) -> Self {
+        self.combine(UnionType::Except, other)
+    }
+
+    fn combine(mut self, union_type: UnionType, other: Self) -> Self {
+        // Select `*` if any of the columns of `self` can't be referred to by name
+        let mut names = Some(Vec::new());
+        self.query
+            .exprs_mut_for_each(|sel| match (select_expr_name(sel), &mut names) {
+                (Some(name), Some(names)) => names.push(name),
+                _ => names = None,
+            });
+        self.with.append(other.with);
+        let mut query = self.query;
+        query.union(union_type, other.query);
+        let mut select = Self {
+            query: SelectStatement::new(),
+            entity: PhantomData,
+            with: self.with,
+        };
+        match names {
+            Some(names) => {
+                for name in names {
+                    let table: DynIden = SeaRc::new(E::default());
+                    select.query.column(ColumnRef::TableColumn(table, name));
+                }
+            }
+            None => {
+                select.query.column(ColumnRef::Asterisk);
+            }
+        }
+        select.query.from_subquery(query, E::default());
+        select
+    }
+}
+
+/// The name of the column a select expression is read from, if any
+fn select_expr_name(sel: &SelectExpr) -> Option<DynIden> {
+    if let Some(alias) = &sel.alias {
+        return Some(SeaRc::clone(alias));
+    }
+    let col_ref = match &sel.expr {
+        SimpleExpr::Column(col_ref) => col_ref,
+        SimpleExpr::AsEnum(_, expr) => match expr.as_ref() {
+            SimpleExpr::Column(col_ref) => col_ref,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    match col_ref {
+        ColumnRef::Column(col)
+        | ColumnRef::TableColumn(_, col)
+        | ColumnRef::SchemaTableColumn(_, _, col) => Some(SeaRc::clone(col)),
+        ColumnRef::Asterisk | ColumnRef::TableAsterisk(_) => None,
+    }
+}
+
 impl<E> QueryTrait for Select<E>
 where
     E: EntityTrait,
@@ -150,6 +515,13 @@ where
     fn into_query(self) -> SelectStatement {
         self.query
     }
+    fn build(&self, db_backend: DbBackend) -> Statement {
+        crate::executor::build_select(
+            db_backend,
+            &self.with.clone().into_with_clause(),
+            &self.query,
+        )
+    }
 }
 
 macro_rules! select_two {
@@ -169,9 +541,55 @@ macro_rules! select_two {
             fn into_query(self) -> SelectStatement {
                 self.query
             }
+            fn build(&self, db_backend: DbBackend) -> Statement {
+                crate::executor::build_select(db_backend, &self.with, &self.query)
+            }
         }
     };
 }
 
 select_two!(SelectTwo);
 select_two!(SelectTwoMany);
+
+impl<E, F, G> QueryTrait for SelectThree<E, F, G>
+where
+    E: EntityTrait,
+    F: EntityTrait,
+    G: EntityTrait,
+{
+    type QueryStatement = SelectStatement;
+    fn query(&mut self) -> &mut SelectStatement {
+        &mut self.query
+    }
+    fn as_query(&self) -> &SelectStatement {
+        &self.query
+    }
+    fn into_query(self) -> SelectStatement {
+        self.query
+    }
+    fn build(&self, db_backend: DbBackend) -> Statement {
+        crate::executor::build_select(db_backend, &self.with, &self.query)
+    }
+}
+
+impl<E, F, G, H> QueryTrait for SelectFour<E, F, G, H>
+where
+    E: EntityTrait,
+    F: EntityTrait,
+    G: EntityTrait,
+    H: EntityTrait,
+{
+    type QueryStatement = SelectStatement;
+    fn query(&mut self) -> &mut SelectStatement {
+        &mut self.query
+    }
+    fn as_query(&self) -> &SelectStatement {
+        &self.query
+    }
+    fn into_query(self) -> SelectStatement {
+        self.query
+    }
+    fn build(&self, db_backend: DbBackend) -> Statement {
+        crate::executor::build_select(db_backend, &self.with, &self.query)
+    }
+}
@@ -1,9 +1,9 @@
 use crate::{
-    ActiveModelTrait, ActiveValue, ColumnTrait, EntityTrait, Iterable, PrimaryKeyToColumn,
+    ActiveModelTrait, ActiveValue, ColumnTrait, DbErr, EntityTrait, Iterable, PrimaryKeyToColumn,
     QueryFilter, QueryTrait,
 };
 use core::marker::PhantomData;
-use sea_query::{Expr, IntoIden, SimpleExpr, UpdateStatement};
+use sea_query::{CaseStatement, Condition, Expr, IntoIden, SimpleExpr, UpdateStatement, Value};
 
 /// Defines a structure to perform UPDATE query operations on a ActiveModel
 #[derive(Clone, Debug)]
@@ -83,6 +83,106 @@ impl Update {
             entity: PhantomData,
         }
     }
+
+    /// Update many ActiveModels with different values in one statement.
+    /// Each column `Set` in any of the models is set to a `CASE` expression picking
+    /// the value of each model by its primary key, keeping the current value for the
+    /// models where the column is not `Set`.
+    ///
+    /// Returns [DbErr::UpdateGetPrimaryKey] if the primary key of a model is not set.
+    ///
+    /// ```
+    /// use sea_orm::{entity::*, query::*, tests_cfg::fruit, DbBackend};
+    ///
+    /// assert_eq!(
+    ///     Update::batch([
+    ///         fruit::ActiveModel {
+    ///             id: ActiveValue::set(1),
+    ///             name: ActiveValue::set("Orange".to_owned()),
+    ///             cake_id: ActiveValue::not_set(),
+    ///         },
+    ///         fruit::ActiveModel {
+    ///             id: ActiveValue::set(2),
+    ///             name: ActiveValue::set("Apple".to_owned()),
+    ///             cake_id: ActiveValue::set(Some(3)),
+    ///         },
+    ///     ])?
+    ///     .build(DbBackend::Postgres)
+    ///     .to_string(),
+    ///     [
+    ///         r#"UPDATE "fruit" SET"#,
+    ///         r#""name" = (CASE WHEN ("fruit"."id" = 1) THEN 'Orange' WHEN ("fruit"."id" = 2) THEN 'Apple' ELSE "name" END),"#,
+    ///         r#""cake_id" = (CASE WHEN ("fruit"."id" = 2) THEN 3 ELSE "cake_id" END)"#,
+    ///         r#"WHERE "fruit"."id" IN (1, 2)"#,
+    ///     ]
+    ///     .join(" ")
+    /// );
+    /// # Ok::<(), sea_orm::DbErr>(())
+    /// ```
+    pub fn batch<E, A, I>(models: I) -> Result<UpdateMany<E>, DbErr>
+    where
+        E: EntityTrait,
+        A: ActiveModelTrait<Entity = E>,
+        I: IntoIterator<Item = A>,
+    {
+        let models: Vec<A> = models.into_iter().collect();
+        let key_values = models
+            .iter()
+            .map(primary_key_values)
+            .collect::<Result<Vec<_>, _>>()?;
+        let keys: Vec<Condition> = key_values
+            .iter()
+            .map(|values| {
+                E::PrimaryKey::iter()
+                    .zip(values.iter())
+                    .fold(Condition::all(), |condition, (key, value)| {
+                        condition.add(key.into_column().eq(value.clone()))
+                    })
+            })
+            .collect();
+
+        let mut update = Self::many(E::default());
+        for col in E::Column::iter() {
+            if E::PrimaryKey::from_column(col).is_some() {
+                continue;
+            }
+            let mut case: Option<CaseStatement> = None;
+            for (model, key) in models.iter().zip(keys.iter()) {
+                if let ActiveValue::Set(value) = model.get(col) {
+                    let expr = col.save_as(Expr::val(value));
+                    case = Some(match case {
+                        Some(case) => case.case(key.clone(), expr),
+                        None => Expr::case(key.clone(), expr),
+                    });
+                }
+            }
+            if let Some(case) = case {
+                update.query.value(col, case.finally(Expr::col(col)));
+            }
+        }
+
+        let mut primary_keys = E::PrimaryKey::iter();
+        Ok(match (primary_keys.next(), primary_keys.next()) {
+            // Each model has a single key value
+            (Some(key), None) => {
+                update.filter(key.into_column().is_in(key_values.into_iter().flatten()))
+            }
+            _ => update.filter(keys.into_iter().fold(Condition::any(), Condition::add)),
+        })
+    }
+}
+
+/// The values of the primary key of an ActiveModel, in the order of the primary key columns
+fn primary_key_values<A>(model: &A) -> Result<Vec<Value>, DbErr>
+where
+    A: ActiveModelTrait,
+{
+    <A::Entity as EntityTrait>::PrimaryKey::iter()
+        .map(|key| match model.get(key.into_column()) {
+            ActiveValue::Set(value) | ActiveValue::Unchanged(value) => Ok(value),
+            ActiveValue::NotSet => Err(DbErr::UpdateGetPrimaryKey),
+        })
+        .collect()
 }
 
 impl<A> UpdateOne<A>
@@ -213,7 +313,7 @@ where
 #[cfg(test)]
 mod tests {
     use crate::tests_cfg::{cake, fruit, lunch_set, sea_orm_active_enums::Tea};
-    use crate::{entity::*, query::*, DbBackend};
+    use crate::{entity::*, query::*, DbBackend, DbErr};
     use sea_query::{Expr, Value};
 
     #[test]
@@ -328,4 +428,68 @@ mod tests {
             r#"UPDATE "lunch_set" SET "tea" = CAST('EverydayTea' AS tea) WHERE "lunch_set"."id" = 1"#,
         );
     }
+
+    #[test]
+    fn update_9() {
+        assert_eq!(
+            Update::batch([
+                lunch_set::ActiveModel {
+                    id: Unchanged(1),
+                    tea: Set(Tea::EverydayTea),
+                    ..Default::default()
+                },
+                lunch_set::ActiveModel {
+                    id: Unchanged(2),
+                    name: Set("Lunch".to_owned()),
+                    ..Default::default()
+                },
+                lunch_set::ActiveModel {
+                    id: Unchanged(3),
+                    ..Default::default()
+                },
+                lunch_set::ActiveModel {
+                    id: Unchanged(4),
+                    tea: Set(Tea::BreakfastTea),
+                    ..Default::default()
+                },
+            ])
+            .unwrap()
+            .build(DbBackend::Postgres)
+            .to_string(),
+            [
+                r#"UPDATE "lunch_set" SET"#,
+                r#""name" = (CASE WHEN ("lunch_set"."id" = 2) THEN 'Lunch' ELSE "name" END),"#,
+                r#""tea" = (CASE WHEN ("lunch_set"."id" = 1) THEN CAST('EverydayTea' AS tea)"#,
+                r#"WHEN ("lunch_set"."id" = 4) THEN CAST('BreakfastTea' AS tea) ELSE "tea" END)"#,
+                r#"WHERE "lunch_set"."id" IN (1, 2, 3, 4)"#,
+            ]
+            .join(" ")
+        );
+    }
+
+    #[test]
+    fn update_10() {
+        assert_eq!(
+            Update::batch([
+                cake::ActiveModel {
+                    id: Unchanged(1),
+                    name: Set("Apple Pie".to_owned()),
+                },
+                cake::ActiveModel {
+                    name: Set("Orange Scone".to_owned()),
+                    ..Default::default()
+                },
+            ])
+            .err(),
+            Some(DbErr::UpdateGetPrimaryKey)
+        );
+
+        assert_eq!(
+            Update::batch(Vec::<cake::ActiveModel>::new())
+                .unwrap()
+                .build(DbBackend::Postgres)
+                .to_string(),
+            r#"UPDATE "cake" SET  WHERE 1 = 2"#,
+        );
+    }
 }
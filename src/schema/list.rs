@@ -0,0 +1,159 @@
+use crate::{EntityTrait, Schema, Statement};
+use sea_query::{
+    extension::postgres::TypeCreateStatement, IndexCreateStatement, TableCreateStatement, TableRef,
+};
+
+/// A list of entities to create the schema of, implemented for an entity and for tuples of entities
+pub trait EntityList {
+    /// Append the schema of each entity to `schemas`, in the order they are listed
+    fn entity_schemas(schema: &Schema, schemas: &mut Vec<EntitySchema>);
+}
+
+/// The statements to create the schema of an entity
+#[derive(Debug, Clone)]
+pub struct EntitySchema {
+    table_name: String,
+    enums: Vec<TypeCreateStatement>,
+    table: TableCreateStatement,
+    indexes: Vec<IndexCreateStatement>,
+}
+
+impl EntitySchema {
+    /// Generate the enums, table and indexes of an Entity
+    pub fn new<E>(schema: &Schema, entity: E) -> Self
+    where
+        E: EntityTrait,
+    {
+        Self {
+            table_name: entity.table_name().to_owned(),
+            enums: schema.create_enum_from_entity(entity),
+            table: schema.create_table_from_entity(entity),
+            indexes: schema.create_index_from_entity(entity),
+        }
+    }
+
+    /// The tables referenced by foreign keys of this table
+    fn references(&self) -> impl Iterator<Item = String> + '_ {
+        self.table
+            .get_foreign_key_create_stmts()
+            .iter()
+            .filter_map(|fk| match fk.get_foreign_key().get_ref_table()? {
+                TableRef::Table(tbl)
+                | TableRef::SchemaTable(_, tbl)
+                | TableRef::DatabaseSchemaTable(_, _, tbl)
+                | TableRef::TableAlias(tbl, _)
+                | TableRef::SchemaTableAlias(_, tbl, _)
+                | TableRef::DatabaseSchemaTableAlias(_, _, tbl, _) => Some(tbl.to_string()),
+                _ => None,
+            })
+    }
+}
+
+impl<E> EntityList for E
+where
+    E: EntityTrait,
+{
+    fn entity_schemas(schema: &Schema, schemas: &mut Vec<EntitySchema>) {
+        schemas.push(EntitySchema::new(schema, E::default()));
+    }
+}
+
+macro_rules! impl_entity_list {
+    ( $($name: ident),+ ) => {
+        impl<$($name),+> EntityList for ($($name,)+)
+        where
+            $($name: EntityList),+
+        {
+            fn entity_schemas(schema: &Schema, schemas: &mut Vec<EntitySchema>) {
+                $($name::entity_schemas(schema, schemas);)+
+            }
+        }
+    };
+}
+
+impl_entity_list!(A);
+impl_entity_list!(A, B);
+impl_entity_list!(A, B, C);
+impl_entity_list!(A, B, C, D);
+impl_entity_list!(A, B, C, D, E);
+impl_entity_list!(A, B, C, D, E, F);
+impl_entity_list!(A, B, C, D, E, F, G);
+impl_entity_list!(A, B, C, D, E, F, G, H);
+impl_entity_list!(A, B, C, D, E, F, G, H, I);
+impl_entity_list!(A, B, C, D, E, F, G, H, I, J);
+impl_entity_list!(A, B, C, D, E, F, G, H, I, J, K);
+impl_entity_list!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+impl Schema {
+    /// Creates the enums, tables and indexes of a list of entities, ordered such that
+    /// every table is created after the tables it references by foreign key.
+    /// Tables in a reference cycle are created in the order they are listed.
+    pub fn create_statements_for<L>(&self) -> Vec<Statement>
+    where
+        L: EntityList,
+    {
+        let mut pending = Vec::new();
+        L::entity_schemas(self, &mut pending);
+
+        let mut sorted: Vec<EntitySchema> = Vec::with_capacity(pending.len());
+        while !pending.is_empty() {
+            let next = pending
+                .iter()
+                .position(|entity| {
+                    entity.references().all(|table| {
+                        table == entity.table_name
+                            || !pending.iter().any(|other| other.table_name == table)
+                    })
+                })
+                .unwrap_or(0);
+            sorted.push(pending.remove(next));
+        }
+
+        let backend = self.backend;
+        let mut stmts: Vec<Statement> = Vec::new();
+        // Entities may share an enum, which can only be created once
+        for stmt in sorted
+            .iter()
+            .flat_map(|entity| entity.enums.iter().map(|stmt| backend.build(stmt)))
+        {
+            if !stmts.iter().any(|created| created.sql == stmt.sql) {
+                stmts.push(stmt);
+            }
+        }
+        for entity in sorted.iter() {
+            stmts.push(backend.build(&entity.table));
+            stmts.extend(entity.indexes.iter().map(|stmt| backend.build(stmt)));
+        }
+        stmts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tests_cfg::*, DbBackend, Schema};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_create_statements_for() {
+        let tables: Vec<String> = Schema::new(DbBackend::Sqlite)
+            .create_statements_for::<(
+                cake_filling::Entity,
+                fruit::Entity,
+                filling::Entity,
+                cake::Entity,
+            )>()
+            .into_iter()
+            .map(|stmt| stmt.sql.split('(').next().unwrap().to_owned())
+            .collect();
+
+        assert_eq!(
+            tables,
+            [
+                r#"CREATE TABLE "filling" "#,
+                r#"CREATE TABLE "cake" "#,
+                r#"CREATE TABLE "cake_filling" "#,
+                r#"CREATE TABLE "fruit" "#,
+            ]
+        );
+    }
+}
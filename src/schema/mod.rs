@@ -1,6 +1,9 @@
 use crate::DbBackend;
 
 mod entity;
+mod list;
+
+pub use list::*;
 
 /// This is a helper struct to convert [`EntityTrait`](crate::EntityTrait)
 /// into different [`sea_query`](crate::sea_query) statements.
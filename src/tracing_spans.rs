@@ -0,0 +1,139 @@
+use crate::{metric::OperationKind, DbBackend, Statement};
+use tracing::{field::Empty, Span};
+
+/// Create the span of a statement, with attributes following the OpenTelemetry database semantic conventions.
+/// Values bound to the statement are never recorded, `db.statement` only holds the SQL with its placeholders.
+pub(crate) fn statement_span(stmt: &Statement, kind: OperationKind, in_transaction: bool) -> Span {
+    let system = match stmt.db_backend {
+        DbBackend::MySql => "mysql",
+        DbBackend::Postgres => "postgresql",
+        DbBackend::Sqlite => "sqlite",
+    };
+    let operation = operation(&stmt.sql);
+    let table = table(&stmt.sql, operation.as_deref());
+    let name = match (&operation, &table) {
+        (Some(operation), Some(table)) => format!("{operation} {table}"),
+        (Some(operation), None) => operation.clone(),
+        (None, _) => system.to_owned(),
+    };
+    let span = tracing::info_span!(
+        "sea_orm.statement",
+        otel.name = name.as_str(),
+        otel.kind = "client",
+        otel.status_code = Empty,
+        db.system = system,
+        db.statement = stmt.sql.as_str(),
+        db.operation = Empty,
+        db.sql.table = Empty,
+        db.rows_affected = Empty,
+        db.sea_orm.method = method(kind),
+        db.sea_orm.in_transaction = in_transaction,
+        error = Empty,
+    );
+    if let Some(operation) = &operation {
+        span.record("db.operation", operation.as_str());
+    }
+    if let Some(table) = &table {
+        span.record("db.sql.table", table.as_str());
+    }
+    span
+}
+
+/// Record the number of rows returned or affected, or the error, on the span of a statement
+#[cfg(feature = "sqlx-dep")]
+pub(crate) fn record_result<T, E>(span: &Span, res: &Result<T, E>)
+where
+    T: crate::metric::Rows,
+    E: std::fmt::Display,
+{
+    record_rows(span, res.as_ref().map(crate::metric::Rows::rows));
+}
+
+pub(crate) fn record_rows<E>(span: &Span, res: Result<u64, E>)
+where
+    E: std::fmt::Display,
+{
+    match res {
+        Ok(rows) => {
+            span.record("db.rows_affected", rows);
+        }
+        Err(err) => {
+            span.record("otel.status_code", "ERROR");
+            span.record("error", tracing::field::display(err));
+        }
+    }
+}
+
+fn method(kind: OperationKind) -> &'static str {
+    match kind {
+        OperationKind::Execute => "execute",
+        OperationKind::QueryOne => "query_one",
+        OperationKind::QueryAll => "query_all",
+        OperationKind::Stream => "stream",
+    }
+}
+
+/// The leading keyword of a statement, e.g. `SELECT`
+fn operation(sql: &str) -> Option<String> {
+    let keyword = sql
+        .trim_start()
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .next()?;
+    (!keyword.is_empty()).then(|| keyword.to_uppercase())
+}
+
+/// The table a statement reads from or writes into, with the identifier quotes stripped
+fn table(sql: &str, operation: Option<&str>) -> Option<String> {
+    let keyword = match operation? {
+        "SELECT" | "DELETE" => "FROM ",
+        "INSERT" | "REPLACE" => "INTO ",
+        "UPDATE" => "UPDATE ",
+        _ => return None,
+    };
+    let start = sql.to_ascii_uppercase().find(keyword)? + keyword.len();
+    let ident = sql[start..]
+        .trim_start()
+        .split(|c: char| c.is_whitespace() || c == '(' || c == ',')
+        .next()?;
+    let table = ident.replace(['"', '`'], "");
+    (!table.is_empty()).then_some(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{operation, table};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_statement_attributes() {
+        let cases = [
+            (
+                r#"SELECT "cake"."id", "cake"."name" FROM "cake" WHERE "cake"."id" = $1"#,
+                Some("SELECT"),
+                Some("cake"),
+            ),
+            (
+                "INSERT INTO `cake` (`name`) VALUES (?)",
+                Some("INSERT"),
+                Some("cake"),
+            ),
+            (
+                r#"UPDATE "public"."cake" SET "name" = $1"#,
+                Some("UPDATE"),
+                Some("public.cake"),
+            ),
+            (
+                r#"DELETE FROM "cake" WHERE "cake"."id" = $1"#,
+                Some("DELETE"),
+                Some("cake"),
+            ),
+            ("SELECT 1 AS `result`", Some("SELECT"), None),
+            ("BEGIN", Some("BEGIN"), None),
+        ];
+        for (sql, expected_operation, expected_table) in cases {
+            let op = operation(sql);
+            assert_eq!(op.as_deref(), expected_operation);
+            assert_eq!(table(sql, op.as_deref()).as_deref(), expected_table);
+        }
+    }
+}
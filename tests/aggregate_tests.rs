@@ -0,0 +1,183 @@
+pub mod common;
+
+pub use common::{bakery_chain::*, setup::*, TestContext};
+use pretty_assertions::assert_eq;
+use sea_orm::{entity::prelude::*, sea_query::Expr, QueryOrder, QuerySelect, Set};
+
+// Run the test locally:
+// DATABASE_URL="sqlite::memory:" cargo test --features sqlx-sqlite,runtime-async-std-native-tls --test aggregate_tests
+#[sea_orm_macros::test]
+#[cfg(any(
+    feature = "sqlx-mysql",
+    feature = "sqlx-sqlite",
+    feature = "sqlx-postgres"
+))]
+async fn main() -> Result<(), DbErr> {
+    let ctx = TestContext::new("aggregate_tests").await;
+    create_tables(&ctx.db).await?;
+    aggregate(&ctx.db).await?;
+    ctx.delete().await;
+
+    Ok(())
+}
+
+pub async fn aggregate(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let empty = bakery::Entity::find().order_by_asc(bakery::Column::Id);
+
+    assert!(!empty.clone().exists(db).await?);
+    assert_eq!(
+        empty
+            .clone()
+            .count_distinct(bakery::Column::Name, db)
+            .await?,
+        0
+    );
+    assert_eq!(
+        empty
+            .clone()
+            .sum::<f64, _, _>(bakery::Column::ProfitMargin, db)
+            .await?,
+        None
+    );
+    assert_eq!(empty.max::<i32, _, _>(bakery::Column::Id, db).await?, None);
+
+    bakery::Entity::insert_many(
+        [
+            ("SeaSide Bakery", 0.5),
+            ("LakeSide Bakery", 1.5),
+            ("SeaSide Bakery", 2.5),
+        ]
+        .map(|(name, profit_margin)| bakery::ActiveModel {
+            name: Set(name.to_owned()),
+            profit_margin: Set(profit_margin),
+            ..Default::default()
+        }),
+    )
+    .exec(db)
+    .await?;
+
+    let bakeries = bakery::Entity::find()
+        .order_by_asc(bakery::Column::Id)
+        .all(db)
+        .await?;
+
+    for (name, bakery_id) in [
+        ("Jane", bakeries[0].id),
+        ("Peter", bakeries[0].id),
+        ("Alice", bakeries[2].id),
+    ] {
+        baker::ActiveModel {
+            name: Set(name.to_owned()),
+            contact_details: Set(serde_json::json!({})),
+            bakery_id: Set(Some(bakery_id)),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+    }
+
+    let select = bakery::Entity::find().order_by_desc(bakery::Column::Id);
+
+    assert!(select.clone().exists(db).await?);
+    assert!(select.clone().offset(10).exists(db).await?);
+    assert!(
+        !select
+            .clone()
+            .filter(bakery::Column::Name.contains("Mountain"))
+            .exists(db)
+            .await?
+    );
+    assert_eq!(
+        select
+            .clone()
+            .count_distinct(bakery::Column::Name, db)
+            .await?,
+        2
+    );
+    assert_eq!(
+        select
+            .clone()
+            .sum::<f64, _, _>(bakery::Column::ProfitMargin, db)
+            .await?,
+        Some(4.5)
+    );
+    assert_eq!(
+        select
+            .clone()
+            .filter(bakery::Column::Name.eq("SeaSide Bakery"))
+            .avg::<f64, _, _>(bakery::Column::ProfitMargin, db)
+            .await?,
+        Some(1.5)
+    );
+    assert_eq!(
+        select
+            .clone()
+            .limit(1)
+            .min::<i32, _, _>(bakery::Column::Id, db)
+            .await?,
+        Some(bakeries[0].id)
+    );
+    assert_eq!(
+        select.max::<String, _, _>(bakery::Column::Name, db).await?,
+        Some("SeaSide Bakery".to_owned())
+    );
+
+    // the sum and average of an integer column are wider than the column
+    // https://www.postgresql.org/docs/current/functions-aggregate.html
+
+    #[cfg(all(feature = "sqlx-postgres", feature = "with-rust_decimal"))]
+    if db.get_database_backend() == sea_orm::DbBackend::Postgres {
+        use rust_decimal::Decimal;
+
+        let select = bakery::Entity::find().filter(bakery::Column::Name.eq("SeaSide Bakery"));
+        assert!(select
+            .clone()
+            .sum::<i32, _, _>(bakery::Column::Id, db)
+            .await
+            .is_err());
+        assert_eq!(
+            select
+                .clone()
+                .sum::<i64, _, _>(bakery::Column::Id, db)
+                .await?,
+            Some((bakeries[0].id + bakeries[2].id) as i64)
+        );
+        assert_eq!(
+            select.avg::<Decimal, _, _>(bakery::Column::Id, db).await?,
+            Some(Decimal::from(bakeries[0].id + bakeries[2].id) / Decimal::from(2))
+        );
+    }
+
+    // aggregate over a join
+
+    let select = baker::Entity::find()
+        .find_also_related(bakery::Entity)
+        .order_by_asc(baker::Column::Id);
+
+    assert_eq!(
+        select
+            .clone()
+            .count_distinct(bakery::Column::Id, db)
+            .await?,
+        2
+    );
+    assert_eq!(
+        select
+            .filter(baker::Column::Name.ne("Alice"))
+            .sum::<f64, _, _>(bakery::Column::ProfitMargin, db)
+            .await?,
+        Some(1.0)
+    );
+
+    // aggregate over the groups
+
+    assert_eq!(
+        baker::Entity::find()
+            .group_by(baker::Column::BakeryId)
+            .max::<i64, _, _>(Expr::col(baker::Column::Id).count(), db)
+            .await?,
+        Some(2)
+    );
+
+    Ok(())
+}
@@ -15,6 +15,12 @@ pub enum Relation {
     SelfReferencing,
 }
 
+impl Related<Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SelfReferencing.def()
+    }
+}
+
 pub struct SelfReferencingLink;
 
 impl Linked for SelfReferencingLink {
@@ -33,7 +39,10 @@ impl ActiveModelBehavior for ActiveModel {}
 mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
-    use sea_orm::*;
+    use sea_orm::{
+        sea_query::{Alias, Expr},
+        *,
+    };
 
     #[test]
     fn find_linked_001() {
@@ -74,4 +83,23 @@ mod tests {
             .join(" ")
         );
     }
+
+    #[test]
+    fn filter_related_exists_001() {
+        assert_eq!(
+            Entity::find()
+                .filter_related_exists(Entity, |q| {
+                    q.filter(Expr::col((Alias::new("r0"), Column::Time)).is_not_null())
+                })
+                .build(DbBackend::MySql)
+                .to_string(),
+            [
+                r#"SELECT `self_join`.`uuid`, `self_join`.`uuid_ref`, `self_join`.`time`"#,
+                r#"FROM `self_join`"#,
+                r#"WHERE EXISTS(SELECT 1 FROM `self_join` AS `r0`"#,
+                r#"WHERE `self_join`.`uuid_ref` = `r0`.`uuid` AND `r0`.`time` IS NOT NULL)"#,
+            ]
+            .join(" ")
+        );
+    }
 }
@@ -0,0 +1,67 @@
+pub mod common;
+
+pub use common::bakery_chain::*;
+use pretty_assertions::assert_eq;
+use sea_orm::{entity::prelude::*, Database, Set, SqlErr};
+
+// Run the test locally:
+// cargo test --features sqlx-sqlite,runtime-async-std-native-tls --test connect_in_memory_tests
+#[sea_orm_macros::test]
+#[cfg(feature = "sqlx-sqlite")]
+async fn main() -> Result<(), DbErr> {
+    // Listed in reverse, each table is still created after the tables it references
+    let db = Database::connect_in_memory_for::<(
+        lineitem::Entity,
+        order::Entity,
+        customer::Entity,
+        cakes_bakers::Entity,
+        cake::Entity,
+        baker::Entity,
+        bakery::Entity,
+    )>()
+    .await?;
+
+    let bakery = bakery::ActiveModel {
+        name: Set("SeaSide Bakery".to_owned()),
+        profit_margin: Set(10.4),
+        ..Default::default()
+    }
+    .insert(&db)
+    .await?;
+
+    for name in ["Baker Bob", "Baker Bobby"] {
+        baker::ActiveModel {
+            name: Set(name.to_owned()),
+            contact_details: Set(serde_json::json!({})),
+            bakery_id: Set(Some(bakery.id)),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await?;
+    }
+
+    let bakers = baker::Entity::find()
+        .filter(baker::Column::Name.contains("Bobby"))
+        .all(&db)
+        .await?;
+    assert_eq!(bakers.len(), 1);
+    assert_eq!(bakers[0].name, "Baker Bobby");
+
+    assert_eq!(bakery.find_related(baker::Entity).count(&db).await?, 2);
+
+    // Foreign keys are enforced
+    let res = baker::ActiveModel {
+        name: Set("Baker Alice".to_owned()),
+        contact_details: Set(serde_json::json!({})),
+        bakery_id: Set(Some(i32::MAX)),
+        ..Default::default()
+    }
+    .insert(&db)
+    .await;
+    assert!(matches!(
+        res.unwrap_err().sql_err(),
+        Some(SqlErr::ForeignKeyConstraintViolation(_))
+    ));
+
+    Ok(())
+}
@@ -53,4 +53,52 @@ pub async fn test_cake_error_sqlx(db: &DbConn) {
         },
         _ => panic!("Unexpected Error kind"),
     }
+
+    #[cfg(any(
+        feature = "sqlx-mysql",
+        feature = "sqlx-sqlite",
+        feature = "sqlx-postgres"
+    ))]
+    match error.sql_err() {
+        Some(SqlErr::UniqueConstraintViolation(name)) => {
+            #[cfg(feature = "sqlx-mysql")]
+            assert!(name.ends_with("PRIMARY"));
+            #[cfg(feature = "sqlx-postgres")]
+            assert_eq!(name, "cake_pkey");
+            #[cfg(feature = "sqlx-sqlite")]
+            assert_eq!(name, "cake.id");
+        }
+        _ => panic!("Unexpected SqlErr kind"),
+    }
+
+    let fk_cake = cake::ActiveModel {
+        name: Set("Orphan Cake".to_owned()),
+        price: Set(dec!(10.25)),
+        gluten_free: Set(false),
+        serial: Set(Uuid::new_v4()),
+        bakery_id: Set(Some(i32::MAX)),
+        ..Default::default()
+    };
+
+    #[allow(unused_variables)]
+    let error: DbErr = fk_cake
+        .insert(db)
+        .await
+        .expect_err("inserting should fail due to foreign key constraint");
+
+    #[cfg(any(
+        feature = "sqlx-mysql",
+        feature = "sqlx-sqlite",
+        feature = "sqlx-postgres"
+    ))]
+    match error.sql_err() {
+        // SQLite does not name the violated foreign key
+        Some(SqlErr::ForeignKeyConstraintViolation(name)) => {
+            #[cfg(any(feature = "sqlx-mysql", feature = "sqlx-postgres"))]
+            assert_eq!(name, "fk-cake-bakery_id");
+            #[cfg(feature = "sqlx-sqlite")]
+            assert_eq!(name, "FOREIGN KEY constraint failed");
+        }
+        _ => panic!("Unexpected SqlErr kind"),
+    }
 }
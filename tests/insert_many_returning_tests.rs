@@ -0,0 +1,108 @@
+pub mod common;
+
+pub use common::{features::*, setup::*, TestContext};
+use pretty_assertions::assert_eq;
+use sea_orm::{entity::prelude::*, QueryOrder, Set};
+
+// Run the test locally:
+// DATABASE_URL="sqlite::memory:" cargo test --features sqlx-sqlite,runtime-async-std-native-tls --test insert_many_returning_tests
+#[sea_orm_macros::test]
+#[cfg(any(
+    feature = "sqlx-mysql",
+    feature = "sqlx-sqlite",
+    feature = "sqlx-postgres"
+))]
+async fn main() -> Result<(), DbErr> {
+    let ctx = TestContext::new("insert_many_returning_tests").await;
+    create_tables(&ctx.db).await?;
+    insert_many_with_primary_keys(&ctx.db).await?;
+    insert_many_auto_increment(&ctx.db).await?;
+    ctx.delete().await;
+
+    Ok(())
+}
+
+pub async fn insert_many_with_primary_keys(db: &DatabaseConnection) -> Result<(), DbErr> {
+    use repository::*;
+
+    let models = ["c", "a", "b"].map(|id| Model {
+        id: id.to_owned(),
+        owner: "GC".to_owned(),
+        name: format!("G.C. {id}"),
+        description: None,
+    });
+
+    let inserted = Entity::insert_many(models.clone().map(|model| ActiveModel {
+        id: Set(model.id),
+        owner: Set(model.owner),
+        name: Set(model.name),
+        ..Default::default()
+    }))
+    .exec_with_returning_many(db)
+    .await?;
+
+    assert_eq!(inserted, models);
+
+    let inserted = Entity::insert_many(Vec::<ActiveModel>::new())
+        .exec_with_returning_many(db)
+        .await?;
+
+    assert_eq!(inserted, []);
+
+    Ok(())
+}
+
+pub async fn insert_many_auto_increment(db: &DatabaseConnection) -> Result<(), DbErr> {
+    use applog::*;
+
+    let created_at = "2021-09-17T17:50:20+08:00".parse().unwrap();
+    let actions = ["first", "second", "third"];
+
+    Entity::insert(ActiveModel {
+        action: Set("existing".to_owned()),
+        json: Set(Json::Null),
+        created_at: Set(created_at),
+        ..Default::default()
+    })
+    .exec(db)
+    .await?;
+
+    let res = Entity::insert_many(actions.map(|action| ActiveModel {
+        action: Set(action.to_owned()),
+        json: Set(Json::Null),
+        created_at: Set(created_at),
+        ..Default::default()
+    }))
+    .exec_with_returning_many(db)
+    .await;
+
+    if !db.support_returning() {
+        // Without `RETURNING`, rows without primary keys can't be selected back reliably
+        assert!(matches!(res, Err(DbErr::BackendNotSupported { .. })));
+        assert_eq!(Entity::find().count(db).await?, 1);
+        return Ok(());
+    }
+    let inserted = res?;
+
+    assert_eq!(
+        inserted
+            .iter()
+            .map(|model| model.action.as_str())
+            .collect::<Vec<_>>(),
+        actions
+    );
+    assert_eq!(
+        inserted.iter().map(|model| model.id).collect::<Vec<_>>(),
+        [2, 3, 4]
+    );
+    assert_eq!(
+        inserted,
+        Entity::find()
+            .filter(Column::Id.gt(1))
+            .order_by_asc(Column::Id)
+            .all(db)
+            .await?
+    );
+
+    Ok(())
+}
@@ -207,6 +207,194 @@ async fn loader_load_many_to_many() -> Result<(), DbErr> {
     Ok(())
 }
 
+#[sea_orm_macros::test]
+#[cfg(any(
+    feature = "sqlx-mysql",
+    feature = "sqlx-sqlite",
+    feature = "sqlx-postgres"
+))]
+async fn loader_load_linked() -> Result<(), DbErr> {
+    let ctx = TestContext::new("loader_test_load_linked").await;
+    create_tables(&ctx.db).await?;
+
+    let bakery_1 = insert_bakery(&ctx.db, "SeaSide Bakery").await?;
+
+    let baker_1 = insert_baker(&ctx.db, "Jane", bakery_1.id).await?;
+    let baker_2 = insert_baker(&ctx.db, "Peter", bakery_1.id).await?;
+    let baker_3 = insert_baker(&ctx.db, "Alice", bakery_1.id).await?; // bakes nothing
+
+    let cake_1 = insert_cake(&ctx.db, "Cheesecake", None).await?;
+    let cake_2 = insert_cake(&ctx.db, "Coffee", None).await?;
+    let cake_3 = insert_cake(&ctx.db, "Chiffon", None).await?;
+
+    insert_cake_baker(&ctx.db, baker_1.id, cake_1.id).await?;
+    insert_cake_baker(&ctx.db, baker_1.id, cake_2.id).await?;
+    insert_cake_baker(&ctx.db, baker_2.id, cake_2.id).await?;
+    insert_cake_baker(&ctx.db, baker_2.id, cake_3.id).await?;
+
+    let bakers = baker::Entity::find().all(&ctx.db).await?;
+    let cakes = bakers.load_linked(BakerToCake, &ctx.db).await?;
+
+    assert_eq!(bakers, [baker_1.clone(), baker_2.clone(), baker_3.clone()]);
+    assert_eq!(
+        cakes,
+        [
+            vec![cake_1.clone(), cake_2.clone()],
+            vec![cake_2.clone(), cake_3.clone()],
+            vec![]
+        ]
+    );
+
+    // same, but with a single query grouping the linked models under each baker
+
+    let bakers_with_cakes = baker::Entity::find()
+        .find_with_linked(BakerToCake)
+        .all(&ctx.db)
+        .await?;
+
+    assert_eq!(
+        bakers_with_cakes,
+        [
+            (baker_1, vec![cake_1, cake_2.clone()]),
+            (baker_2, vec![cake_2, cake_3]),
+            (baker_3, vec![])
+        ]
+    );
+
+    Ok(())
+}
+
+#[sea_orm_macros::test]
+#[cfg(any(
+    feature = "sqlx-mysql",
+    feature = "sqlx-sqlite",
+    feature = "sqlx-postgres"
+))]
+async fn loader_load_nested() -> Result<(), DbErr> {
+    let ctx = TestContext::new("loader_test_load_nested").await;
+    create_tables(&ctx.db).await?;
+
+    let bakery_1 = insert_bakery(&ctx.db, "SeaSide Bakery").await?;
+    let bakery_2 = insert_bakery(&ctx.db, "LakeSide Bakery").await?;
+    let bakery_3 = insert_bakery(&ctx.db, "Empty Bakery").await?;
+
+    let baker_1 = insert_baker(&ctx.db, "Jane", bakery_1.id).await?;
+    let baker_2 = insert_baker(&ctx.db, "Peter", bakery_2.id).await?;
+    let baker_3 = insert_baker(&ctx.db, "Alice", bakery_2.id).await?;
+
+    let cake_1 = insert_cake(&ctx.db, "Cheesecake", Some(bakery_1.id)).await?;
+    let cake_2 = insert_cake(&ctx.db, "Coffee", Some(bakery_2.id)).await?;
+    let cake_3 = insert_cake(&ctx.db, "Chiffon", None).await?;
+
+    insert_cake_baker(&ctx.db, baker_1.id, cake_1.id).await?;
+    insert_cake_baker(&ctx.db, baker_1.id, cake_2.id).await?;
+    insert_cake_baker(&ctx.db, baker_2.id, cake_2.id).await?;
+    insert_cake_baker(&ctx.db, baker_2.id, cake_3.id).await?;
+
+    let bakeries = bakery::Entity::find().all(&ctx.db).await?;
+    let bakers = bakeries
+        .load_nested(
+            LoadMany::new(baker::Entity).with(
+                LoadManyToMany::new(cake::Entity, cakes_bakers::Entity)
+                    .with(LoadOne::new(bakery::Entity)),
+            ),
+            &ctx.db,
+        )
+        .await?;
+
+    assert_eq!(
+        bakeries,
+        [bakery_1.clone(), bakery_2.clone(), bakery_3.clone()]
+    );
+    assert_eq!(
+        bakers,
+        [
+            vec![(
+                baker_1,
+                vec![
+                    (cake_1, Some(bakery_1.clone())),
+                    (cake_2.clone(), Some(bakery_2.clone()))
+                ]
+            )],
+            vec![
+                (
+                    baker_2,
+                    vec![(cake_2, Some(bakery_2.clone())), (cake_3, None)]
+                ),
+                (baker_3, vec![])
+            ],
+            vec![]
+        ]
+    );
+
+    Ok(())
+}
+
+#[sea_orm_macros::test]
+#[cfg(any(
+    feature = "sqlx-mysql",
+    feature = "sqlx-sqlite",
+    feature = "sqlx-postgres"
+))]
+async fn loader_load_many_in_chunks() -> Result<(), DbErr> {
+    let ctx = TestContext::new("loader_test_load_many_in_chunks").await;
+    create_tables(&ctx.db).await?;
+
+    // more keys than the bind parameter limit of SQLite
+    for i in 0..15 {
+        bakery::Entity::insert_many((0..100).map(|j| bakery::ActiveModel {
+            name: Set(format!("Bakery {}", i * 100 + j)),
+            profit_margin: Set(1.0),
+            ..Default::default()
+        }))
+        .exec(&ctx.db)
+        .await?;
+    }
+
+    let bakeries = bakery::Entity::find()
+        .order_by_asc(bakery::Column::Id)
+        .all(&ctx.db)
+        .await?;
+    assert_eq!(bakeries.len(), 1500);
+
+    let baker_1 = insert_baker(&ctx.db, "Jane", bakeries[0].id).await?;
+    let baker_2 = insert_baker(&ctx.db, "Peter", bakeries[1499].id).await?;
+
+    let bakers = bakeries.load_many(baker::Entity, &ctx.db).await?;
+
+    assert_eq!(bakers.len(), 1500);
+    assert_eq!(bakers[0], [baker_1.clone()]);
+    assert_eq!(bakers[1499], [baker_2.clone()]);
+    assert!(bakers[1..1499].iter().all(|bakers| bakers.is_empty()));
+
+    // same, but with a custom chunk size
+
+    let chunked = Loader::new(&bakeries)
+        .chunk_size(100)
+        .load_many(baker::Entity, &ctx.db)
+        .await?;
+
+    assert_eq!(chunked, bakers);
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct BakerToCake;
+
+impl Linked for BakerToCake {
+    type FromEntity = baker::Entity;
+
+    type ToEntity = cake::Entity;
+
+    fn link(&self) -> Vec<RelationDef> {
+        vec![
+            cakes_bakers::Relation::Baker.def().rev(),
+            cakes_bakers::Relation::Cake.def(),
+        ]
+    }
+}
+
 pub async fn insert_bakery(db: &DbConn, name: &str) -> Result<bakery::Model, DbErr> {
     bakery::ActiveModel {
         name: Set(name.to_owned()),
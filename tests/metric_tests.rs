@@ -0,0 +1,65 @@
+pub mod common;
+
+pub use common::features::*;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+use sea_orm::{
+    entity::prelude::*, metric::OperationKind, Database, Set, Statement, TransactionTrait,
+};
+use std::sync::{Arc, Mutex};
+
+// Run the test locally:
+// cargo test --features sqlx-sqlite,runtime-async-std-native-tls --test metric_tests
+#[sea_orm_macros::test]
+#[cfg(feature = "sqlx-sqlite")]
+async fn main() -> Result<(), DbErr> {
+    use insert_default::*;
+
+    let mut db = Database::connect_in_memory_for::<Entity>().await?;
+
+    let infos = Arc::new(Mutex::new(Vec::new()));
+    let captured = Arc::clone(&infos);
+    db.set_metric_callback(move |info| {
+        captured
+            .lock()
+            .unwrap()
+            .push((info.kind, info.rows, info.failed, info.in_transaction));
+    });
+
+    Entity::insert_many([ActiveModel { id: Set(1) }, ActiveModel { id: Set(2) }])
+        .exec(&db)
+        .await?;
+    Entity::find().all(&db).await?;
+    Entity::find_by_id(3).one(&db).await?;
+    let rows: Vec<Model> = Entity::find().stream(&db).await?.try_collect().await?;
+    assert_eq!(rows.len(), 2);
+
+    let txn = db.begin().await?;
+    Entity::delete_by_id(1).exec(&txn).await?;
+    Entity::find().one(&txn).await?;
+    txn.commit().await?;
+
+    let backend = db.get_database_backend();
+    assert!(db
+        .execute(Statement::from_string(
+            backend,
+            "SELECT * FROM missing".to_owned()
+        ))
+        .await
+        .is_err());
+
+    assert_eq!(
+        *infos.lock().unwrap(),
+        [
+            (OperationKind::Execute, Some(2), false, false),
+            (OperationKind::QueryAll, Some(2), false, false),
+            (OperationKind::QueryOne, Some(0), false, false),
+            (OperationKind::Stream, Some(2), false, false),
+            (OperationKind::Execute, Some(1), false, true),
+            (OperationKind::QueryOne, Some(1), false, true),
+            (OperationKind::Execute, None, true, false),
+        ]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+pub mod common;
+
+pub use common::{bakery_chain::*, setup::*, TestContext};
+use pretty_assertions::assert_eq;
+use sea_orm::{
+    entity::prelude::*,
+    sea_query::{Expr, Func},
+    DerivePartialModel, FromQueryResult, QueryOrder, Set,
+};
+
+#[derive(Debug, PartialEq, FromQueryResult, DerivePartialModel)]
+#[sea_orm(entity = "bakery::Entity")]
+struct PartialBakery {
+    name: String,
+    #[sea_orm(from_col = "id")]
+    bakery_id: i32,
+    #[sea_orm(from_expr = "Expr::expr(Func::upper(Expr::col(bakery::Column::Name)))")]
+    upper_name: String,
+}
+
+#[derive(Debug, PartialEq, FromQueryResult, DerivePartialModel)]
+struct BakeryCount {
+    #[sea_orm(from_expr = "Expr::col(bakery::Column::Id).count()")]
+    count: i64,
+}
+
+// Run the test locally:
+// DATABASE_URL="sqlite::memory:" cargo test --features sqlx-sqlite,runtime-async-std-native-tls --test partial_model_tests
+#[sea_orm_macros::test]
+#[cfg(any(
+    feature = "sqlx-mysql",
+    feature = "sqlx-sqlite",
+    feature = "sqlx-postgres"
+))]
+async fn main() -> Result<(), DbErr> {
+    let ctx = TestContext::new("partial_model_tests").await;
+    create_tables(&ctx.db).await?;
+    select_partial_model(&ctx.db).await?;
+    ctx.delete().await;
+
+    Ok(())
+}
+
+pub async fn select_partial_model(db: &DatabaseConnection) -> Result<(), DbErr> {
+    bakery::Entity::insert_many([("SeaSide Bakery", 0.4), ("LakeSide Bakery", 0.6)].map(
+        |(name, profit_margin)| bakery::ActiveModel {
+            name: Set(name.to_owned()),
+            profit_margin: Set(profit_margin),
+            ..Default::default()
+        },
+    ))
+    .exec(db)
+    .await?;
+
+    assert_eq!(
+        bakery::Entity::find()
+            .order_by_asc(bakery::Column::Id)
+            .into_partial_model::<PartialBakery>()
+            .all(db)
+            .await?,
+        [
+            PartialBakery {
+                name: "SeaSide Bakery".to_owned(),
+                bakery_id: 1,
+                upper_name: "SEASIDE BAKERY".to_owned(),
+            },
+            PartialBakery {
+                name: "LakeSide Bakery".to_owned(),
+                bakery_id: 2,
+                upper_name: "LAKESIDE BAKERY".to_owned(),
+            },
+        ]
+    );
+
+    assert_eq!(
+        bakery::Entity::find()
+            .into_partial_model::<BakeryCount>()
+            .one(db)
+            .await?,
+        Some(BakeryCount { count: 2 })
+    );
+
+    Ok(())
+}
@@ -0,0 +1,155 @@
+pub mod common;
+
+pub use common::{bakery_chain::*, setup::*, TestContext};
+use pretty_assertions::assert_eq;
+use sea_orm::{entity::prelude::*, QueryOrder, Set};
+
+// Run the test locally:
+// DATABASE_URL="sqlite::memory:" cargo test --features sqlx-sqlite,runtime-async-std-native-tls --test related_exists_tests
+#[sea_orm_macros::test]
+#[cfg(any(
+    feature = "sqlx-mysql",
+    feature = "sqlx-sqlite",
+    feature = "sqlx-postgres"
+))]
+async fn main() -> Result<(), DbErr> {
+    let ctx = TestContext::new("related_exists_tests").await;
+    create_tables(&ctx.db).await?;
+    filter_related_exists(&ctx.db).await?;
+    ctx.delete().await;
+
+    Ok(())
+}
+
+pub async fn filter_related_exists(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let seaside = insert_bakery(db, "SeaSide Bakery").await?;
+    let lakeside = insert_bakery(db, "LakeSide Bakery").await?;
+    let _mountain = insert_bakery(db, "Mountain Bakery").await?;
+
+    let jane = insert_baker(db, "Jane", seaside.id).await?;
+    let peter = insert_baker(db, "Peter", seaside.id).await?;
+    let alice = insert_baker(db, "Alice", lakeside.id).await?;
+
+    let cheesecake = insert_cake(db, "Cheesecake", Some(seaside.id)).await?;
+    let chocolate = insert_cake(db, "Chocolate Cake", Some(lakeside.id)).await?;
+    let _lemon = insert_cake(db, "Lemon Cake", None).await?;
+
+    insert_cake_baker(db, jane.id, cheesecake.id).await?;
+    insert_cake_baker(db, peter.id, cheesecake.id).await?;
+    insert_cake_baker(db, alice.id, chocolate.id).await?;
+
+    // has many
+
+    let bakeries = bakery::Entity::find()
+        .filter_related_exists(baker::Entity, |q| q)
+        .order_by_asc(bakery::Column::Id)
+        .all(db)
+        .await?;
+
+    assert_eq!(bakeries, [seaside.clone(), lakeside.clone()]);
+
+    let bakeries = bakery::Entity::find()
+        .filter_related_exists(baker::Entity, |q| q.filter(baker::Column::Name.eq("Alice")))
+        .all(db)
+        .await?;
+
+    assert_eq!(bakeries, [lakeside.clone()]);
+
+    let bakeries = bakery::Entity::find()
+        .filter_related_not_exists(baker::Entity, |q| q.filter(baker::Column::Name.eq("Peter")))
+        .filter(bakery::Column::Name.ne("Mountain Bakery"))
+        .all(db)
+        .await?;
+
+    assert_eq!(bakeries, [lakeside.clone()]);
+
+    // belongs to
+
+    let bakers = baker::Entity::find()
+        .filter_related_exists(bakery::Entity, |q| {
+            q.filter(bakery::Column::Name.eq("SeaSide Bakery"))
+        })
+        .order_by_asc(baker::Column::Id)
+        .all(db)
+        .await?;
+
+    assert_eq!(bakers, [jane.clone(), peter.clone()]);
+
+    // many to many
+
+    let cakes = cake::Entity::find()
+        .filter_related_exists(baker::Entity, |q| q.filter(baker::Column::Name.eq("Peter")))
+        .all(db)
+        .await?;
+
+    assert_eq!(cakes, [cheesecake]);
+
+    let cakes = cake::Entity::find()
+        .filter_related_not_exists(baker::Entity, |q| q)
+        .all(db)
+        .await?;
+
+    assert_eq!(cakes.len(), 1);
+    assert_eq!(cakes[0].name, "Lemon Cake");
+
+    let bakers = baker::Entity::find()
+        .filter_related_exists(cake::Entity, |q| {
+            q.filter(cake::Column::BakeryId.eq(lakeside.id))
+        })
+        .all(db)
+        .await?;
+
+    assert_eq!(bakers, [alice]);
+
+    Ok(())
+}
+
+pub async fn insert_bakery(db: &DbConn, name: &str) -> Result<bakery::Model, DbErr> {
+    bakery::ActiveModel {
+        name: Set(name.to_owned()),
+        profit_margin: Set(1.0),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+}
+
+pub async fn insert_baker(db: &DbConn, name: &str, bakery_id: i32) -> Result<baker::Model, DbErr> {
+    baker::ActiveModel {
+        name: Set(name.to_owned()),
+        contact_details: Set(serde_json::json!({})),
+        bakery_id: Set(Some(bakery_id)),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+}
+
+pub async fn insert_cake(
+    db: &DbConn,
+    name: &str,
+    bakery_id: Option<i32>,
+) -> Result<cake::Model, DbErr> {
+    cake::ActiveModel {
+        name: Set(name.to_owned()),
+        price: Set(rust_decimal::Decimal::ONE),
+        gluten_free: Set(false),
+        bakery_id: Set(bakery_id),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+}
+
+pub async fn insert_cake_baker(
+    db: &DbConn,
+    baker_id: i32,
+    cake_id: i32,
+) -> Result<cakes_bakers::Model, DbErr> {
+    cakes_bakers::ActiveModel {
+        cake_id: Set(cake_id),
+        baker_id: Set(baker_id),
+    }
+    .insert(db)
+    .await
+}
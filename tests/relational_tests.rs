@@ -727,3 +727,162 @@ pub async fn linked() -> Result<(), DbErr> {
 
     Ok(())
 }
+
+#[sea_orm_macros::test]
+#[cfg(any(
+    feature = "sqlx-mysql",
+    feature = "sqlx-sqlite",
+    feature = "sqlx-postgres"
+))]
+pub async fn select_also_nested() -> Result<(), DbErr> {
+    let ctx = TestContext::new("test_select_also_nested").await;
+    let db = &ctx.db;
+    create_tables(db).await?;
+
+    let bakery = bakery::ActiveModel {
+        name: Set("SeaSide Bakery".to_owned()),
+        profit_margin: Set(10.4),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    let customer_kate = customer::ActiveModel {
+        name: Set("Kate".to_owned()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    let customer_jim = customer::ActiveModel {
+        name: Set("Jim".to_owned()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    let kate_order = order::ActiveModel {
+        bakery_id: Set(bakery.id),
+        customer_id: Set(customer_kate.id),
+        total: Set(dec!(15.10)),
+        placed_at: Set(Utc::now().naive_utc()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    let cake = cake::ActiveModel {
+        name: Set("Cheesecake".to_owned()),
+        price: Set(dec!(15.10)),
+        bakery_id: Set(Some(bakery.id)),
+        gluten_free: Set(false),
+        serial: Set(Uuid::new_v4()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    let lineitem = lineitem::ActiveModel {
+        price: Set(dec!(15.10)),
+        quantity: Set(1),
+        order_id: Set(kate_order.id),
+        cake_id: Set(cake.id),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    let select_three = customer::Entity::find()
+        .left_join(order::Entity)
+        .join(JoinType::LeftJoin, order::Relation::Bakery.def())
+        .select_also(order::Entity)
+        .select_also(bakery::Entity)
+        .order_by_asc(customer::Column::Id);
+
+    assert_eq!(
+        select_three.clone().all(db).await?,
+        [
+            (
+                customer_kate.clone(),
+                Some(kate_order.clone()),
+                Some(bakery.clone())
+            ),
+            (customer_jim.clone(), None, None),
+        ]
+    );
+
+    #[derive(Debug, PartialEq, FromQueryResult)]
+    struct CustomerOrder {
+        #[sea_orm(nested, prefix = "A_")]
+        customer: customer::Model,
+        #[sea_orm(nested, prefix = "B_")]
+        order: Option<order::Model>,
+        #[sea_orm(nested, prefix = "C_")]
+        bakery: Option<bakery::Model>,
+    }
+
+    assert_eq!(
+        select_three
+            .into_nested_model::<CustomerOrder>()
+            .all(db)
+            .await?,
+        [
+            CustomerOrder {
+                customer: customer_kate.clone(),
+                order: Some(kate_order.clone()),
+                bakery: Some(bakery.clone()),
+            },
+            CustomerOrder {
+                customer: customer_jim.clone(),
+                order: None,
+                bakery: None,
+            },
+        ]
+    );
+
+    assert_eq!(
+        customer::Entity::find()
+            .left_join(order::Entity)
+            .join(JoinType::LeftJoin, order::Relation::Bakery.def())
+            .join(JoinType::LeftJoin, order::Relation::Lineitem.def())
+            .select_also(order::Entity)
+            .select_also(bakery::Entity)
+            .select_also(lineitem::Entity)
+            .order_by_asc(customer::Column::Id)
+            .all(db)
+            .await?,
+        [
+            (
+                customer_kate,
+                Some(kate_order),
+                Some(bakery.clone()),
+                Some(lineitem)
+            ),
+            (customer_jim, None, None, None),
+        ]
+    );
+
+    #[derive(Debug, PartialEq, FromQueryResult)]
+    struct CakeWithBakeryName {
+        #[sea_orm(nested)]
+        cake: cake::Model,
+        bakery_name: String,
+    }
+
+    assert_eq!(
+        cake::Entity::find()
+            .left_join(bakery::Entity)
+            .column_as(bakery::Column::Name, "bakery_name")
+            .into_model::<CakeWithBakeryName>()
+            .all(db)
+            .await?,
+        [CakeWithBakeryName {
+            cake,
+            bakery_name: bakery.name,
+        }]
+    );
+
+    ctx.delete().await;
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ pub mod common;
 
 pub use common::{bakery_chain::*, setup::*, TestContext};
 pub use sea_orm::{entity::prelude::*, *};
-pub use sea_query::Query;
+pub use sea_query::{Expr, Query};
 
 #[sea_orm_macros::test]
 #[cfg(any(
@@ -66,3 +66,86 @@ async fn main() -> Result<(), DbErr> {
 
     Ok(())
 }
+
+#[sea_orm_macros::test]
+#[cfg(any(
+    feature = "sqlx-mysql",
+    feature = "sqlx-sqlite",
+    feature = "sqlx-postgres"
+))]
+async fn update_many_delete_many() -> Result<(), DbErr> {
+    use bakery::*;
+
+    let ctx = TestContext::new("returning_many_tests").await;
+    let db = &ctx.db;
+
+    create_tables(db).await?;
+
+    Entity::insert_many(
+        ["Bakery Shop", "Bakery Hut", "Cake Shop"].map(|name| ActiveModel {
+            name: Set(name.to_owned()),
+            profit_margin: Set(0.5),
+            ..Default::default()
+        }),
+    )
+    .exec(db)
+    .await?;
+
+    let update = Entity::update_many()
+        .col_expr(Column::ProfitMargin, Expr::value(0.8))
+        .filter(Column::Name.starts_with("Bakery"))
+        .exec_with_returning(db)
+        .await;
+    let delete = Entity::delete_many()
+        .filter(Column::Name.starts_with("Cake"))
+        .exec_with_returning(db)
+        .await;
+
+    if db.get_database_backend() == DbBackend::MySql {
+        assert_eq!(
+            update,
+            Err(DbErr::BackendNotSupported {
+                db: "MySQL",
+                ctx: "UPDATE RETURNING",
+            })
+        );
+        assert_eq!(
+            delete,
+            Err(DbErr::BackendNotSupported {
+                db: "MySQL",
+                ctx: "DELETE RETURNING",
+            })
+        );
+    } else {
+        let mut updated = update?;
+        updated.sort_by_key(|model| model.id);
+        assert_eq!(
+            updated,
+            [
+                Model {
+                    id: 1,
+                    name: "Bakery Shop".to_owned(),
+                    profit_margin: 0.8,
+                },
+                Model {
+                    id: 2,
+                    name: "Bakery Hut".to_owned(),
+                    profit_margin: 0.8,
+                },
+            ]
+        );
+        assert_eq!(
+            delete?,
+            [Model {
+                id: 3,
+                name: "Cake Shop".to_owned(),
+                profit_margin: 0.5,
+            }]
+        );
+        assert_eq!(Entity::find().all(db).await?, updated);
+    }
+
+    ctx.delete().await;
+
+    Ok(())
+}
@@ -80,10 +80,27 @@ pub async fn create_metadata(db: &DatabaseConnection) -> Result<(), DbErr> {
             .await?,
         [
             (model.clone(), None),
-            (linked_model, Some(model)),
-            (not_linked_model, None),
+            (linked_model.clone(), Some(model.clone())),
+            (not_linked_model.clone(), None),
         ]
     );
 
+    assert_eq!(
+        self_join::Entity::find()
+            .filter_related_exists(self_join::Entity, |q| q)
+            .all(db)
+            .await?,
+        [linked_model.clone()]
+    );
+
+    assert_eq!(
+        self_join::Entity::find()
+            .filter_related_not_exists(self_join::Entity, |q| q)
+            .order_by_asc(self_join::Column::Time)
+            .all(db)
+            .await?,
+        [model, not_linked_model]
+    );
+
     Ok(())
 }
@@ -0,0 +1,91 @@
+pub mod common;
+
+pub use common::features::*;
+use pretty_assertions::assert_eq;
+use sea_orm::{entity::prelude::*, ConnectOptions, Database, Set, TransactionTrait};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+// Run the test locally:
+// cargo test --features sqlx-sqlite,runtime-async-std-native-tls --test slow_statement_tests
+#[sea_orm_macros::test]
+#[cfg(feature = "sqlx-sqlite")]
+async fn main() -> Result<(), DbErr> {
+    use insert_default::*;
+    use tracing_subscriber::util::SubscriberInitExt;
+
+    // Log records are forwarded to the `tracing` subscriber
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let buffer = Buffer::default();
+    let writer = buffer.clone();
+    let _guard = tracing_subscriber::fmt()
+        .with_env_filter("sea_orm::slow_statement=trace")
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish()
+        .set_default();
+    let records = || String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+
+    let mut opt = ConnectOptions::new("sqlite::memory:".to_owned());
+    opt.max_connections(1)
+        .slow_statement_threshold(Duration::from_secs(3600));
+    let db = Database::connect(opt).await?;
+    create_insert_default_table(&db).await?;
+    ActiveModel { id: Set(1) }.insert(&db).await?;
+    Entity::find_by_id(1).one(&db).await?;
+    db.close().await?;
+
+    // Nothing ran longer than the threshold
+    assert_eq!(records(), "");
+
+    let mut opt = ConnectOptions::new("sqlite::memory:".to_owned());
+    opt.max_connections(1)
+        .slow_statement_threshold(Duration::ZERO)
+        .slow_statement_explain(true);
+    let db = Database::connect(opt).await?;
+    create_insert_default_table(&db).await?;
+    ActiveModel { id: Set(1) }.insert(&db).await?;
+    Entity::find_by_id(1).one(&db).await?;
+    let txn = db.begin().await?;
+    Entity::find().filter(Column::Id.gt(0)).all(&txn).await?;
+    txn.commit().await?;
+    db.close().await?;
+
+    let records = records();
+    let select = records
+        .split("slow statement: ")
+        .find(|record| record.starts_with("SELECT"))
+        .unwrap();
+    assert!(select.contains(r#"LIMIT ?; values: [Int(Some(1)), BigUnsigned(Some(1))]; elapsed: "#));
+    assert!(select.contains("plan:\nSEARCH insert_default USING INTEGER PRIMARY KEY"));
+
+    // `CREATE TABLE` is logged without a plan
+    let create = records
+        .split("slow statement: ")
+        .find(|record| record.starts_with("CREATE TABLE"))
+        .unwrap();
+    assert!(!create.contains("plan:"));
+
+    // The plan isn't fetched inside a transaction, where a failing `EXPLAIN` would abort it
+    let select = records
+        .split("slow statement: ")
+        .find(|record| record.starts_with("SELECT") && record.contains(" > ?"))
+        .unwrap();
+    assert!(!select.contains("plan:"));
+
+    Ok(())
+}
@@ -37,3 +37,73 @@ pub async fn stream() -> Result<(), DbErr> {
 
     Ok(())
 }
+
+#[sea_orm_macros::test]
+#[cfg(any(
+    feature = "sqlx-mysql",
+    feature = "sqlx-sqlite",
+    feature = "sqlx-postgres"
+))]
+pub async fn stream_consolidated() -> Result<(), DbErr> {
+    use futures::TryStreamExt;
+    use sea_orm::QueryOrder;
+
+    let ctx = TestContext::new("stream_consolidated").await;
+    create_tables(&ctx.db).await?;
+
+    for name in ["SeaSide Bakery", "LakeSide Bakery", "Mountain Bakery"] {
+        bakery::ActiveModel {
+            name: Set(name.to_owned()),
+            profit_margin: Set(10.4),
+            ..Default::default()
+        }
+        .insert(&ctx.db)
+        .await?;
+    }
+
+    for (name, bakery_id) in [("Jane", 1), ("Alice", 3), ("Peter", 1)] {
+        baker::ActiveModel {
+            name: Set(name.to_owned()),
+            contact_details: Set(serde_json::json!({})),
+            bakery_id: Set(Some(bakery_id)),
+            ..Default::default()
+        }
+        .insert(&ctx.db)
+        .await?;
+    }
+
+    let bakeries: Vec<(bakery::Model, Vec<baker::Model>)> = Bakery::find()
+        .find_with_related(Baker)
+        .order_by_asc(baker::Column::Id)
+        .stream_consolidated(&ctx.db)
+        .await?
+        .try_collect()
+        .await?;
+
+    assert_eq!(
+        bakeries
+            .iter()
+            .map(|(bakery, bakers)| (
+                bakery.name.as_str(),
+                bakers.iter().map(|b| b.id).collect::<Vec<_>>()
+            ))
+            .collect::<Vec<_>>(),
+        [
+            ("SeaSide Bakery", vec![1, 3]),
+            ("LakeSide Bakery", vec![]),
+            ("Mountain Bakery", vec![2]),
+        ]
+    );
+    assert_eq!(
+        bakeries,
+        Bakery::find()
+            .find_with_related(Baker)
+            .order_by_asc(baker::Column::Id)
+            .all(&ctx.db)
+            .await?
+    );
+
+    ctx.delete().await;
+
+    Ok(())
+}
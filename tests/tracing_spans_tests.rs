@@ -0,0 +1,70 @@
+pub mod common;
+
+pub use common::features::*;
+use sea_orm::{entity::prelude::*, Database, Set, TransactionTrait};
+
+// Run the test locally:
+// cargo test --features sqlx-sqlite,runtime-async-std-native-tls,tracing-spans --test tracing_spans_tests
+#[sea_orm_macros::test]
+#[cfg(all(feature = "sqlx-sqlite", feature = "tracing-spans"))]
+async fn main() -> Result<(), DbErr> {
+    use insert_default::*;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::{fmt::format::FmtSpan, util::SubscriberInitExt};
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let buffer = Buffer::default();
+    let writer = buffer.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_span_events(FmtSpan::CLOSE)
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    let _guard = subscriber.set_default();
+
+    let db = Database::connect_in_memory_for::<Entity>().await?;
+
+    Entity::insert_many([ActiveModel { id: Set(1) }, ActiveModel { id: Set(2) }])
+        .exec(&db)
+        .await?;
+    let txn = db.begin().await?;
+    Entity::find().filter(Column::Id.gt(1)).all(&txn).await?;
+    txn.commit().await?;
+
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    let spans: Vec<&str> = output
+        .lines()
+        .filter(|line| line.contains("sea_orm.statement"))
+        .collect();
+
+    let insert = spans
+        .iter()
+        .find(|line| line.contains(r#"db.operation="INSERT""#))
+        .unwrap();
+    assert!(insert.contains(r#"db.system="sqlite""#));
+    assert!(insert.contains(r#"db.sql.table="insert_default""#));
+    assert!(insert.contains("db.rows_affected=2"));
+    assert!(insert.contains("db.sea_orm.in_transaction=false"));
+
+    let select = spans
+        .iter()
+        .find(|line| line.contains(r#"db.operation="SELECT""#))
+        .unwrap();
+    assert!(select.contains(r#"db.statement="SELECT \"insert_default\".\"id\" FROM \"insert_default\" WHERE \"insert_default\".\"id\" > ?""#));
+    assert!(select.contains("db.rows_affected=1"));
+    assert!(select.contains("db.sea_orm.in_transaction=true"));
+
+    Ok(())
+}
@@ -196,6 +196,55 @@ pub async fn transaction_begin_commit() -> Result<(), DbErr> {
     Ok(())
 }
 
+#[sea_orm_macros::test]
+#[cfg(any(
+    feature = "sqlx-mysql",
+    feature = "sqlx-sqlite",
+    feature = "sqlx-postgres"
+))]
+pub async fn transaction_savepoint() -> Result<(), DbErr> {
+    let ctx = TestContext::new("transaction_savepoint_test").await;
+    create_tables(&ctx.db).await?;
+
+    let txn = ctx.db.begin().await?;
+
+    for (i, name) in ["SeaSide Bakery", "Top Bakery", "Bottom Bakery"]
+        .into_iter()
+        .enumerate()
+    {
+        let savepoint = format!("bakery_{i}");
+        txn.savepoint(&savepoint).await?;
+
+        bakery::ActiveModel {
+            name: Set(name.to_owned()),
+            profit_margin: Set(10.4),
+            ..Default::default()
+        }
+        .save(&txn)
+        .await?;
+
+        // Partially roll back the second item, keeping the others
+        if i == 1 {
+            txn.rollback_to(&savepoint).await?;
+        }
+        txn.release(&savepoint).await?;
+    }
+
+    txn.commit().await?;
+
+    let bakeries: Vec<String> = bakery::Entity::find()
+        .order_by_asc(bakery::Column::Id)
+        .all(&ctx.db)
+        .await?
+        .into_iter()
+        .map(|bakery| bakery.name)
+        .collect();
+    assert_eq!(bakeries, ["SeaSide Bakery", "Bottom Bakery"]);
+
+    ctx.delete().await;
+    Ok(())
+}
+
 #[sea_orm_macros::test]
 #[cfg(any(
     feature = "sqlx-mysql",
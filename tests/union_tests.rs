@@ -0,0 +1,185 @@
+pub mod common;
+
+pub use common::{bakery_chain::*, setup::*, TestContext};
+use pretty_assertions::assert_eq;
+use sea_orm::{
+    entity::prelude::*,
+    sea_query::{Alias, Expr, Query},
+    CursorTrait, FromQueryResult, PaginatorTrait, QueryOrder, QuerySelect, Set,
+};
+
+// Run the test locally:
+// DATABASE_URL="sqlite::memory:" cargo test --features sqlx-sqlite,runtime-async-std-native-tls --test union_tests
+#[sea_orm_macros::test]
+#[cfg(any(
+    feature = "sqlx-mysql",
+    feature = "sqlx-sqlite",
+    feature = "sqlx-postgres"
+))]
+async fn main() -> Result<(), DbErr> {
+    let ctx = TestContext::new("union_tests").await;
+    create_tables(&ctx.db).await?;
+    seed_data(&ctx.db).await?;
+    union(&ctx.db).await?;
+    union_columns_and_ctes(&ctx.db).await?;
+    #[cfg(any(feature = "sqlx-sqlite", feature = "sqlx-postgres"))]
+    intersect_except(&ctx.db).await?;
+    ctx.delete().await;
+
+    Ok(())
+}
+
+pub async fn seed_data(db: &DatabaseConnection) -> Result<(), DbErr> {
+    bakery::Entity::insert_many(
+        [
+            ("SeaSide Bakery", 0.5),
+            ("LakeSide Bakery", 1.5),
+            ("Mountain Bakery", 2.5),
+            ("Forest Bakery", 3.5),
+        ]
+        .map(|(name, profit_margin)| bakery::ActiveModel {
+            name: Set(name.to_owned()),
+            profit_margin: Set(profit_margin),
+            ..Default::default()
+        }),
+    )
+    .exec(db)
+    .await?;
+
+    Ok(())
+}
+
+fn names(bakeries: Vec<bakery::Model>) -> Vec<String> {
+    bakeries.into_iter().map(|bakery| bakery.name).collect()
+}
+
+pub async fn union(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let side = || bakery::Entity::find().filter(bakery::Column::Name.contains("Side"));
+    let profitable = || bakery::Entity::find().filter(bakery::Column::ProfitMargin.gt(1.0));
+
+    let bakeries = side()
+        .union(profitable())
+        .order_by_desc(bakery::Column::ProfitMargin)
+        .all(db)
+        .await?;
+
+    assert_eq!(
+        names(bakeries),
+        [
+            "Forest Bakery",
+            "Mountain Bakery",
+            "LakeSide Bakery",
+            "SeaSide Bakery"
+        ]
+    );
+
+    let union_all = || {
+        side()
+            .union_all(profitable())
+            .order_by_asc(bakery::Column::Id)
+    };
+
+    let bakeries = union_all()
+        .filter(bakery::Column::Name.starts_with("LakeSide"))
+        .all(db)
+        .await?;
+
+    assert_eq!(names(bakeries), ["LakeSide Bakery", "LakeSide Bakery"]);
+
+    // paginate the combined result
+
+    assert_eq!(union_all().count(db).await?, 5);
+
+    let bakeries = union_all().paginate(db, 2).fetch_page(1).await?;
+
+    assert_eq!(names(bakeries), ["LakeSide Bakery", "Mountain Bakery"]);
+
+    let bakeries = union_all().limit(1).offset(4).all(db).await?;
+
+    assert_eq!(names(bakeries), ["Forest Bakery"]);
+
+    // cursor over the combined result
+
+    let mut cursor = side().union(profitable()).cursor_by(bakery::Column::Id);
+    cursor.after(2);
+
+    assert_eq!(names(cursor.first(1).all(db).await?), ["Mountain Bakery"]);
+
+    Ok(())
+}
+
+pub async fn union_columns_and_ctes(db: &DatabaseConnection) -> Result<(), DbErr> {
+    #[derive(Debug, PartialEq, FromQueryResult)]
+    struct DoubledMargin {
+        name: String,
+        double_margin: f64,
+    }
+
+    // Each query filters by its own common table expression
+    let named = |cte: &str, pattern: &str| {
+        bakery::Entity::find()
+            .with_cte(
+                Alias::new(cte),
+                Query::select()
+                    .column(bakery::Column::Id)
+                    .from(bakery::Entity)
+                    .and_where(bakery::Column::Name.contains(pattern))
+                    .to_owned(),
+            )
+            .filter(
+                bakery::Column::Id.in_subquery(
+                    Query::select()
+                        .column(bakery::Column::Id)
+                        .from(Alias::new(cte))
+                        .to_owned(),
+                ),
+            )
+            .column_as(
+                Expr::col(bakery::Column::ProfitMargin).mul(2),
+                "double_margin",
+            )
+    };
+
+    let bakeries = named("sea", "Sea")
+        .union(named("forest", "Forest"))
+        .order_by_asc(bakery::Column::Id)
+        .into_model::<DoubledMargin>()
+        .all(db)
+        .await?;
+
+    assert_eq!(
+        bakeries,
+        [
+            DoubledMargin {
+                name: "SeaSide Bakery".to_owned(),
+                double_margin: 1.0,
+            },
+            DoubledMargin {
+                name: "Forest Bakery".to_owned(),
+                double_margin: 7.0,
+            },
+        ]
+    );
+
+    Ok(())
+}
+
+#[cfg(any(feature = "sqlx-sqlite", feature = "sqlx-postgres"))]
+pub async fn intersect_except(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let side = || bakery::Entity::find().filter(bakery::Column::Name.contains("Side"));
+    let profitable = || bakery::Entity::find().filter(bakery::Column::ProfitMargin.gt(1.0));
+
+    let bakeries = side().intersect(profitable()).all(db).await?;
+
+    assert_eq!(names(bakeries), ["LakeSide Bakery"]);
+
+    let bakeries = profitable()
+        .except(side())
+        .order_by_asc(bakery::Column::Name)
+        .all(db)
+        .await?;
+
+    assert_eq!(names(bakeries), ["Forest Bakery", "Mountain Bakery"]);
+
+    Ok(())
+}
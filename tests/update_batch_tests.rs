@@ -0,0 +1,97 @@
+pub mod common;
+
+pub use common::{bakery_chain::*, setup::*, TestContext};
+use pretty_assertions::assert_eq;
+use sea_orm::{entity::prelude::*, QueryOrder, Set, Unchanged};
+
+// Run the test locally:
+// DATABASE_URL="sqlite::memory:" cargo test --features sqlx-sqlite,runtime-async-std-native-tls --test update_batch_tests
+#[sea_orm_macros::test]
+#[cfg(any(
+    feature = "sqlx-mysql",
+    feature = "sqlx-sqlite",
+    feature = "sqlx-postgres"
+))]
+async fn main() -> Result<(), DbErr> {
+    let ctx = TestContext::new("update_batch_tests").await;
+    create_tables(&ctx.db).await?;
+    update_batch(&ctx.db).await?;
+    ctx.delete().await;
+
+    Ok(())
+}
+
+pub async fn update_batch(db: &DatabaseConnection) -> Result<(), DbErr> {
+    bakery::Entity::insert_many(
+        [
+            ("SeaSide Bakery", 0.5),
+            ("LakeSide Bakery", 1.5),
+            ("Mountain Bakery", 2.5),
+        ]
+        .map(|(name, profit_margin)| bakery::ActiveModel {
+            name: Set(name.to_owned()),
+            profit_margin: Set(profit_margin),
+            ..Default::default()
+        }),
+    )
+    .exec(db)
+    .await?;
+
+    let res = bakery::Entity::update_batch([
+        bakery::ActiveModel {
+            id: Unchanged(1),
+            profit_margin: Set(10.5),
+            ..Default::default()
+        },
+        bakery::ActiveModel {
+            id: Unchanged(3),
+            name: Set("Forest Bakery".to_owned()),
+            profit_margin: Set(30.5),
+        },
+    ])?
+    .exec(db)
+    .await?;
+
+    assert_eq!(res.rows_affected, 2);
+
+    assert_eq!(
+        bakery::Entity::find()
+            .order_by_asc(bakery::Column::Id)
+            .all(db)
+            .await?,
+        [
+            bakery::Model {
+                id: 1,
+                name: "SeaSide Bakery".to_owned(),
+                profit_margin: 10.5,
+            },
+            bakery::Model {
+                id: 2,
+                name: "LakeSide Bakery".to_owned(),
+                profit_margin: 1.5,
+            },
+            bakery::Model {
+                id: 3,
+                name: "Forest Bakery".to_owned(),
+                profit_margin: 30.5,
+            },
+        ]
+    );
+
+    let res = bakery::Entity::update_batch(Vec::<bakery::ActiveModel>::new())?
+        .exec(db)
+        .await?;
+
+    assert_eq!(res.rows_affected, 0);
+
+    assert_eq!(
+        bakery::Entity::update_batch([bakery::ActiveModel {
+            name: Set("Mountain Bakery".to_owned()),
+            ..Default::default()
+        }])
+        .err(),
+        Some(DbErr::UpdateGetPrimaryKey)
+    );
+
+    Ok(())
+}
@@ -3,7 +3,7 @@ pub mod common;
 pub use common::{features::*, setup::*, TestContext};
 use pretty_assertions::assert_eq;
 use sea_orm::entity::prelude::*;
-use sea_orm::{sea_query::OnConflict, Set};
+use sea_orm::{sea_query::OnConflict, IntoActiveModel, QueryOrder, Schema, Set, TryInsertResult};
 
 #[sea_orm_macros::test]
 #[cfg(feature = "sqlx-postgres")]
@@ -16,6 +16,66 @@ async fn main() -> Result<(), DbErr> {
     Ok(())
 }
 
+// Run the test locally:
+// DATABASE_URL="sqlite::memory:" cargo test --features sqlx-sqlite,runtime-async-std-native-tls --test upsert_tests
+#[sea_orm_macros::test]
+#[cfg(any(feature = "sqlx-sqlite", feature = "sqlx-postgres"))]
+async fn try_insert() -> Result<(), DbErr> {
+    let ctx = TestContext::new("try_insert_tests").await;
+    create_tables(&ctx.db).await?;
+    try_insert_do_nothing(&ctx.db).await?;
+    ctx.delete().await;
+
+    Ok(())
+}
+
+pub async fn try_insert_do_nothing(db: &DatabaseConnection) -> Result<(), DbErr> {
+    use insert_default::*;
+
+    let on_conflict = OnConflict::column(Column::Id).do_nothing().to_owned();
+
+    let res = Entity::insert_many(Vec::<ActiveModel>::new())
+        .on_empty_do_nothing()
+        .exec(db)
+        .await?;
+
+    assert!(matches!(res, TryInsertResult::Empty));
+
+    let res = Entity::insert_many([ActiveModel { id: Set(1) }, ActiveModel { id: Set(2) }])
+        .on_conflict(on_conflict.clone())
+        .do_nothing()
+        .exec(db)
+        .await?;
+
+    assert!(matches!(res, TryInsertResult::Inserted(res) if res.last_insert_id == 2));
+
+    let res = Entity::insert_many([ActiveModel { id: Set(1) }, ActiveModel { id: Set(2) }])
+        .on_conflict(on_conflict.clone())
+        .do_nothing()
+        .exec(db)
+        .await?;
+
+    assert!(matches!(res, TryInsertResult::Conflicted));
+
+    let res = Entity::insert(ActiveModel { id: Set(2) })
+        .on_conflict(on_conflict.clone())
+        .do_nothing()
+        .exec_with_returning(db)
+        .await?;
+
+    assert!(matches!(res, TryInsertResult::Conflicted));
+
+    let res = Entity::insert(ActiveModel { id: Set(3) })
+        .on_conflict(on_conflict)
+        .do_nothing()
+        .exec_without_returning(db)
+        .await?;
+
+    assert!(matches!(res, TryInsertResult::Inserted(1)));
+
+    Ok(())
+}
+
 pub async fn create_insert_default(db: &DatabaseConnection) -> Result<(), DbErr> {
     use insert_default::*;
 
@@ -58,3 +118,186 @@ pub async fn create_insert_default(db: &DatabaseConnection) -> Result<(), DbErr>
 
     Ok(())
 }
+
+// Run the test locally:
+// DATABASE_URL="sqlite::memory:" cargo test --features sqlx-sqlite,runtime-async-std-native-tls --test upsert_tests
+#[sea_orm_macros::test]
+#[cfg(any(
+    feature = "sqlx-mysql",
+    feature = "sqlx-sqlite",
+    feature = "sqlx-postgres"
+))]
+async fn upsert() -> Result<(), DbErr> {
+    let ctx = TestContext::new("upsert_helper_tests").await;
+    let db = &ctx.db;
+    let builder = db.get_database_backend();
+    db.execute(builder.build(&Schema::new(builder).create_table_from_entity(upsert_user::Entity)))
+        .await?;
+    upsert_primary_key(db).await?;
+    upsert_unique(db).await?;
+    ctx.delete().await;
+
+    Ok(())
+}
+
+pub async fn upsert_primary_key(db: &DatabaseConnection) -> Result<(), DbErr> {
+    use upsert_user::*;
+
+    let jane = ActiveModel {
+        email: Set("jane@sea-ql.org".to_owned()),
+        name: Set("Jane".to_owned()),
+        ..Default::default()
+    }
+    .upsert(db)
+    .await?;
+
+    assert_eq!(
+        jane,
+        Model {
+            id: 1,
+            email: "jane@sea-ql.org".to_owned(),
+            name: "Jane".to_owned(),
+        }
+    );
+
+    let jane = ActiveModel {
+        id: Set(1),
+        email: Set("jane@sea-ql.org".to_owned()),
+        name: Set("Jane Doe".to_owned()),
+    }
+    .upsert(db)
+    .await?;
+
+    assert_eq!(jane.name, "Jane Doe");
+
+    // upsert a row with the same values
+    assert_eq!(jane.clone().into_active_model().upsert(db).await?, jane);
+
+    let peter = ActiveModel {
+        id: Set(5),
+        email: Set("peter@sea-ql.org".to_owned()),
+        name: Set("Peter".to_owned()),
+    }
+    .upsert(db)
+    .await?;
+
+    assert_eq!(peter.id, 5);
+    assert_eq!(
+        Entity::find().order_by_asc(Column::Id).all(db).await?,
+        [jane, peter]
+    );
+
+    Ok(())
+}
+
+pub async fn upsert_unique(db: &DatabaseConnection) -> Result<(), DbErr> {
+    use upsert_user::*;
+
+    let peter = Entity::insert(ActiveModel {
+        id: Set(5),
+        email: Set("peter@sea-ql.org".to_owned()),
+        name: Set("Peter Pan".to_owned()),
+    })
+    .on_conflict_unique(Column::Email)?
+    .exec_with_returning(db)
+    .await?;
+
+    assert_eq!(
+        peter,
+        Model {
+            id: 5,
+            email: "peter@sea-ql.org".to_owned(),
+            name: "Peter Pan".to_owned(),
+        }
+    );
+
+    let res = Entity::insert_many([
+        ActiveModel {
+            id: Set(1),
+            email: Set("jane@sea-ql.org".to_owned()),
+            name: Set("Jane".to_owned()),
+        },
+        ActiveModel {
+            id: Set(6),
+            email: Set("alice@sea-ql.org".to_owned()),
+            name: Set("Alice".to_owned()),
+        },
+    ])
+    .on_conflict_unique(Column::Email)?
+    .exec(db)
+    .await?;
+
+    assert_eq!(res.last_insert_id, 6);
+    assert_eq!(
+        Entity::find()
+            .order_by_asc(Column::Id)
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|user| user.name)
+            .collect::<Vec<_>>(),
+        ["Jane", "Peter Pan", "Alice"]
+    );
+
+    // The upserted row is found by the unique column if the primary key is not set
+    let peter = Entity::insert(ActiveModel {
+        email: Set("peter@sea-ql.org".to_owned()),
+        name: Set("Peter Parker".to_owned()),
+        ..Default::default()
+    })
+    .on_conflict_unique(Column::Email)?
+    .exec_with_returning(db)
+    .await?;
+
+    assert_eq!(
+        peter,
+        Model {
+            id: 5,
+            email: "peter@sea-ql.org".to_owned(),
+            name: "Peter Parker".to_owned(),
+        }
+    );
+
+    let bob = Entity::insert(ActiveModel {
+        email: Set("bob@sea-ql.org".to_owned()),
+        name: Set("Bob".to_owned()),
+        ..Default::default()
+    })
+    .on_conflict_unique(Column::Email)?
+    .exec_with_returning(db)
+    .await?;
+
+    assert_eq!(bob.email, "bob@sea-ql.org");
+    assert_eq!(Entity::find_by_id(bob.id).one(db).await?, Some(bob));
+
+    assert!(matches!(
+        Entity::insert(ActiveModel {
+            email: Set("jane@sea-ql.org".to_owned()),
+            name: Set("Jane".to_owned()),
+            ..Default::default()
+        })
+        .on_conflict_unique(Column::Name),
+        Err(DbErr::Custom(_))
+    ));
+
+    Ok(())
+}
+
+pub mod upsert_user {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "upsert_user")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        #[sea_orm(unique)]
+        pub email: String,
+        pub name: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
@@ -0,0 +1,206 @@
+pub mod common;
+
+pub use common::{bakery_chain::*, setup::*, TestContext};
+use pretty_assertions::assert_eq;
+use sea_orm::{
+    entity::prelude::*,
+    sea_query::{Alias, Expr, Order, Query, UnionType},
+    AggregatorTrait, FromQueryResult, PaginatorTrait, QueryOrder, QuerySelect, Set,
+};
+
+// Run the test locally:
+// DATABASE_URL="sqlite::memory:" cargo test --features sqlx-sqlite,runtime-async-std-native-tls --test window_cte_tests
+#[sea_orm_macros::test]
+#[cfg(any(
+    feature = "sqlx-mysql",
+    feature = "sqlx-sqlite",
+    feature = "sqlx-postgres"
+))]
+async fn main() -> Result<(), DbErr> {
+    let ctx = TestContext::new("window_cte_tests").await;
+    create_tables(&ctx.db).await?;
+    seed_data(&ctx.db).await?;
+    window_function(&ctx.db).await?;
+    common_table_expression(&ctx.db).await?;
+    recursive_common_table_expression(&ctx.db).await?;
+    ctx.delete().await;
+
+    Ok(())
+}
+
+pub async fn seed_data(db: &DatabaseConnection) -> Result<(), DbErr> {
+    for (id, name, profit_margin) in [
+        (1, "SeaSide Bakery", 10.4),
+        (2, "LakeSide Bakery", 5.2),
+        (3, "Mountain Bakery", 7.1),
+        (4, "Forest Bakery", 3.3),
+    ] {
+        bakery::ActiveModel {
+            id: Set(id),
+            name: Set(name.to_owned()),
+            profit_margin: Set(profit_margin),
+        }
+        .insert(db)
+        .await?;
+    }
+
+    for (name, bakery_id) in [
+        ("Jane", 1),
+        ("Peter", 1),
+        ("Alice", 1),
+        ("Bob", 2),
+        ("Carol", 2),
+    ] {
+        baker::ActiveModel {
+            name: Set(name.to_owned()),
+            contact_details: Set(serde_json::json!({})),
+            bakery_id: Set(Some(bakery_id)),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn window_function(db: &DatabaseConnection) -> Result<(), DbErr> {
+    #[derive(Debug, PartialEq, FromQueryResult)]
+    struct RankedBaker {
+        name: String,
+        bakery_id: Option<i32>,
+    }
+
+    let first_bakers: Vec<RankedBaker> = baker::Entity::find()
+        .select_only()
+        .column(baker::Column::Name)
+        .column(baker::Column::BakeryId)
+        .column_window(
+            Expr::cust("ROW_NUMBER()"),
+            [baker::Column::BakeryId],
+            [(baker::Column::Name, Order::Asc)],
+            "rank",
+        )
+        .order_by_asc(Expr::col(Alias::new("rank")))
+        .order_by_asc(baker::Column::BakeryId)
+        .limit(2)
+        .into_model()
+        .all(db)
+        .await?;
+
+    assert_eq!(
+        first_bakers,
+        [
+            RankedBaker {
+                name: "Alice".to_owned(),
+                bakery_id: Some(1),
+            },
+            RankedBaker {
+                name: "Bob".to_owned(),
+                bakery_id: Some(2),
+            },
+        ]
+    );
+
+    Ok(())
+}
+
+pub async fn common_table_expression(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let staffed = || {
+        bakery::Entity::find()
+            .with_cte(
+                Alias::new("staffed_bakery"),
+                Query::select()
+                    .column(baker::Column::BakeryId)
+                    .from(baker::Entity)
+                    .to_owned(),
+            )
+            .filter(
+                bakery::Column::Id.in_subquery(
+                    Query::select()
+                        .column(baker::Column::BakeryId)
+                        .from(Alias::new("staffed_bakery"))
+                        .to_owned(),
+                ),
+            )
+            .order_by_asc(bakery::Column::Id)
+    };
+
+    let names: Vec<String> = staffed()
+        .select_only()
+        .column(bakery::Column::Name)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    assert_eq!(names, ["SeaSide Bakery", "LakeSide Bakery"]);
+    assert_eq!(staffed().count(db).await?, 2);
+    assert!(staffed().exists(db).await?);
+    assert!(
+        !staffed()
+            .filter(bakery::Column::Name.contains("Mountain"))
+            .exists(db)
+            .await?
+    );
+    assert_eq!(
+        staffed()
+            .max::<f64, _, _>(bakery::Column::ProfitMargin, db)
+            .await?,
+        Some(10.4)
+    );
+    assert_eq!(
+        staffed()
+            .find_with_related(baker::Entity)
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|(bakery, bakers)| (bakery.id, bakers.len()))
+            .collect::<Vec<_>>(),
+        [(1, 3), (2, 2)]
+    );
+    assert_eq!(
+        staffed().paginate(db, 1).fetch_page(1).await?,
+        [bakery::Model {
+            id: 2,
+            name: "LakeSide Bakery".to_owned(),
+            profit_margin: 5.2,
+        }]
+    );
+
+    Ok(())
+}
+
+pub async fn recursive_common_table_expression(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let numbers = Query::select()
+        .expr(Expr::cust("1"))
+        .union(
+            UnionType::All,
+            Query::select()
+                .expr(Expr::col(Alias::new("n")).add(Expr::cust("1")))
+                .from(Alias::new("numbers"))
+                .and_where(Expr::col(Alias::new("n")).lt(Expr::cust("3")))
+                .to_owned(),
+        )
+        .to_owned();
+
+    let bakeries = bakery::Entity::find()
+        .with_recursive_cte(Alias::new("numbers"), [Alias::new("n")], numbers)
+        .filter(
+            bakery::Column::Id.in_subquery(
+                Query::select()
+                    .column(Alias::new("n"))
+                    .from(Alias::new("numbers"))
+                    .to_owned(),
+            ),
+        )
+        .order_by_desc(bakery::Column::Id)
+        .all(db)
+        .await?;
+
+    assert_eq!(
+        bakeries.into_iter().map(|b| b.name).collect::<Vec<_>>(),
+        ["Mountain Bakery", "LakeSide Bakery", "SeaSide Bakery"]
+    );
+
+    Ok(())
+}